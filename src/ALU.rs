@@ -1,13 +1,13 @@
 use crate::{
     ast::Type,
     errors::{ComputationError, ErrorSeverity},
-    value::Value,
+    value::{Value, MAX_STRING_SIZE},
 };
 
 pub struct ALU;
 
 impl ALU {
-    fn check_int_operation<F>(val1: &Value, val2: &Value, op: F, op_name: &str) -> Result<Value, ComputationError>
+    fn check_int_operation<F>(val1: &Value, val2: &Value, op: F, op_name: &str, symbol: &str) -> Result<Value, ComputationError>
     where
         F: Fn(i64, i64) -> Option<i64>,
     {
@@ -16,13 +16,13 @@ impl ALU {
                 Some(result) => Ok(Value::I64(result)),
                 None => Err(ComputationError::new(
                     ErrorSeverity::HIGH,
-                    format!("Overflow occurred when performing {} on i64s.", op_name),
+                    format!("Overflow occurred when performing {} on i64s: {} {} {}.", op_name, a, symbol, b),
                 )),
             },
             _ => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
                 format!(
-                    "Cannot perform {} between values of type '{:?}' and '{:?}'.",
+                    "Cannot perform {} between values of type '{}' and '{}'.",
                     op_name,
                     val1.to_type(),
                     val2.to_type()
@@ -50,7 +50,7 @@ impl ALU {
             _ => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
                 format!(
-                    "Cannot perform {} between values of type '{:?}' and '{:?}'.",
+                    "Cannot perform {} between values of type '{}' and '{}'.",
                     op_name,
                     val1.to_type(),
                     val2.to_type()
@@ -58,35 +58,124 @@ impl ALU {
             )),
         }
     }
+
+    fn arithmetic_type_error(val1: &Value, val2: &Value, op_name: &str, symbol: &str) -> ComputationError {
+        // mixing i64 and f64 is the most common mistake, so a fix is suggested
+        let hint = match (val1, val2) {
+            (Value::I64(_), Value::F64(_)) => format!(" Consider casting with 'as', e.g. '1 as f64 {} 2.0'.", symbol),
+            (Value::F64(_), Value::I64(_)) => format!(" Consider casting with 'as', e.g. '1.0 {} 2 as f64'.", symbol),
+            _ => String::new(),
+        };
+        ComputationError::new(
+            ErrorSeverity::HIGH,
+            format!(
+                "Cannot perform {} between values of type '{}' and '{}'.{}",
+                op_name,
+                val1.to_type(),
+                val2.to_type(),
+                hint
+            ),
+        )
+    }
+
+    fn repeat_string(string: &str, count: i64) -> Result<Value, ComputationError> {
+        let count = usize::try_from(count).map_err(|_| {
+            ComputationError::new(
+                ErrorSeverity::HIGH,
+                format!("Cannot repeat string a negative number of times ('{}').", count),
+            )
+        })?;
+        // estimated before repeating, as the result may not fit in memory at all
+        let empty_size = Value::String(String::new()).approx_size();
+        match string.len().checked_mul(count).and_then(|length| length.checked_add(empty_size)) {
+            Some(size) if size <= MAX_STRING_SIZE => Ok(Value::String(string.repeat(count))),
+            _ => Err(ComputationError::new(
+                ErrorSeverity::HIGH,
+                format!("Repeated string would exceed the maximum size of {} bytes.", MAX_STRING_SIZE),
+            )),
+        }
+    }
+
+    fn bool_ordering_error() -> ComputationError {
+        ComputationError::new(
+            ErrorSeverity::HIGH,
+            format!("Ordering comparisons are not defined for type '{}'.", Type::Bool),
+        )
+    }
+
+    fn division_by_zero() -> ComputationError {
+        ComputationError::new(ErrorSeverity::HIGH, String::from("Division by zero."))
+    }
+
+    fn check_bitwise_operation<F>(val1: &Value, val2: &Value, op: F, op_name: &str, symbol: &str) -> Result<Value, ComputationError>
+    where
+        F: Fn(i64, i64) -> i64,
+    {
+        Self::check_int_operation(val1, val2, |a, b| Some(op(a, b)), op_name, symbol)
+    }
+
+    fn check_shift_operation<F>(val1: &Value, val2: &Value, op: F, op_name: &str, symbol: &str) -> Result<Value, ComputationError>
+    where
+        F: Fn(i64, u32) -> Option<i64>,
+    {
+        match (val1, val2) {
+            (Value::I64(a), Value::I64(b)) => match u32::try_from(*b).ok().and_then(|b| op(*a, b)) {
+                Some(result) => Ok(Value::I64(result)),
+                None => Err(ComputationError::new(
+                    ErrorSeverity::HIGH,
+                    format!("Cannot perform {} by '{}' - shift amount has to be in range from 0 to 63.", op_name, b),
+                )),
+            },
+            _ => Self::check_int_operation(val1, val2, |_, _| None, op_name, symbol),
+        }
+    }
 }
 
 impl ALU {
-    pub fn cast_to_type(val: Value, to_type: Type) -> Result<Value, ComputationError> {
+    pub fn cast_to_type(val: Value, to_type: &Type) -> Result<Value, ComputationError> {
         match (val, to_type) {
             (Value::I64(i64), Type::Str) => Ok(Value::String(i64.to_string())),
-            (Value::F64(f64), Type::Str) => Ok(Value::String(f64.to_string())),
+            (Value::F64(f64), Type::Str) => Ok(Value::String(Value::F64(f64).to_display_string())),
             (Value::I64(i64), Type::F64) => Ok(Value::F64(i64 as f64)),
-            (Value::F64(f64), Type::I64) => Ok(Value::I64(f64 as i64)),
+            // the fraction is truncated, values that don't fit in i64 are rejected instead of saturating
+            (Value::F64(f64), Type::I64) => match f64.is_finite() && f64 >= i64::MIN as f64 && f64 < i64::MAX as f64 {
+                true => Ok(Value::I64(f64 as i64)),
+                false => Err(ComputationError::new(ErrorSeverity::HIGH, format!("Cannot cast f64 '{}' to 'i64'.", f64))),
+            },
             (Value::I64(i64), Type::Bool) => Ok(Value::Bool(i64 > 0)),
             (Value::F64(f64), Type::Bool) => Ok(Value::Bool(f64 > 0.0)),
-            (Value::String(string), Type::I64) => match string.parse::<i64>() {
+            // surrounding whitespace is ignored, empty strings are rejected
+            (Value::String(string), Type::I64) => match string.trim().parse::<i64>() {
                 Ok(i64) => Ok(Value::I64(i64)),
                 Err(_) => Err(ComputationError::new(
                     ErrorSeverity::HIGH,
-                    format!("Cannot cast String '{}' to 'i64'.", string),
+                    format!("Cannot cast str '{}' to 'i64'.", string),
                 )),
             },
-            (Value::String(string), Type::F64) => match string.parse::<f64>() {
+            (Value::String(string), Type::F64) => match string.trim().parse::<f64>() {
                 Ok(f64) => Ok(Value::F64(f64)),
                 Err(_) => Err(ComputationError::new(
                     ErrorSeverity::HIGH,
-                    format!("Cannot cast String '{}' to 'f64'.", string),
+                    format!("Cannot cast str '{}' to 'f64'.", string),
+                )),
+            },
+            (Value::Bool(bool), Type::Str) => Ok(Value::String(bool.to_string())),
+            (Value::Bool(bool), Type::I64) => Ok(Value::I64(bool as i64)),
+            (Value::Bool(bool), Type::F64) => Ok(Value::F64(bool as i64 as f64)),
+            // 'false' is special-cased so that casting a bool to str and back keeps its value
+            (Value::String(string), Type::Bool) => Ok(Value::Bool(string.as_str() != "" && string.as_str() != "false")),
+            (Value::Char(char), Type::I64) => Ok(Value::I64(char as i64)),
+            (Value::Char(char), Type::Str) => Ok(Value::String(char.to_string())),
+            (Value::I64(i64), Type::Char) => match u32::try_from(i64).ok().and_then(char::from_u32) {
+                Some(char) => Ok(Value::Char(char)),
+                None => Err(ComputationError::new(
+                    ErrorSeverity::HIGH,
+                    format!("Cannot cast i64 '{}' to 'char'.", i64),
                 )),
             },
-            (Value::String(string), Type::Bool) => Ok(Value::Bool(string.as_str() != "")),
             (value, target_type) => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
-                format!("Cannot cast '{:?}' to '{:?}'.", value, target_type),
+                format!("Cannot cast '{}' to '{}'.", value.to_type(), target_type),
             )),
         }
     }
@@ -96,7 +185,7 @@ impl ALU {
             Value::Bool(bool) => Ok(Value::Bool(!bool)),
             val => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
-                format!("Cannot perform boolean negation on type '{:?}'.", val.to_type()),
+                format!("Cannot perform boolean negation on type '{}'.", val.to_type()),
             )),
         }
     }
@@ -107,69 +196,54 @@ impl ALU {
             Value::F64(f64) => Ok(Value::F64(-f64)),
             val => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
-                format!("Cannot perform arithmetic negation on type '{:?}'.", val.to_type()),
+                format!("Cannot perform arithmetic negation on type '{}'.", val.to_type()),
+            )),
+        }
+    }
+
+    pub fn unary_plus(val: Value) -> Result<Value, ComputationError> {
+        match val {
+            Value::I64(_) | Value::F64(_) => Ok(val),
+            val => Err(ComputationError::new(
+                ErrorSeverity::HIGH,
+                format!("Cannot perform unary plus on type '{}'.", val.to_type()),
             )),
         }
     }
 
     pub fn add(val1: Value, val2: Value) -> Result<Value, ComputationError> {
         match (&val1, &val2) {
-            (Value::I64(_), Value::I64(_)) => Self::check_int_operation(&val1, &val2, i64::checked_add, "addition"),
+            (Value::I64(_), Value::I64(_)) => Self::check_int_operation(&val1, &val2, i64::checked_add, "addition", "+"),
             (Value::F64(_), Value::F64(_)) => Self::check_float_operation(&val1, &val2, |a, b| a + b, "addition"),
             (Value::String(a), Value::String(b)) => Ok(Value::String(a.clone() + b)),
-            (a, b) => Err(ComputationError::new(
-                ErrorSeverity::HIGH,
-                format!(
-                    "Cannot perform addition between values of type '{:?}' and '{:?}'.",
-                    a.to_type(),
-                    b.to_type()
-                ),
-            )),
+            (a, b) => Err(Self::arithmetic_type_error(a, b, "addition", "+")),
         }
     }
 
     pub fn subtract(val1: Value, val2: Value) -> Result<Value, ComputationError> {
         match (&val1, &val2) {
-            (Value::I64(_), Value::I64(_)) => Self::check_int_operation(&val1, &val2, i64::checked_sub, "subtraction"),
+            (Value::I64(_), Value::I64(_)) => Self::check_int_operation(&val1, &val2, i64::checked_sub, "subtraction", "-"),
             (Value::F64(_), Value::F64(_)) => Self::check_float_operation(&val1, &val2, |a, b| a - b, "subtraction"),
-            (a, b) => Err(ComputationError::new(
-                ErrorSeverity::HIGH,
-                format!(
-                    "Cannot perform subtraction between values of type '{:?}' and '{:?}'.",
-                    a.to_type(),
-                    b.to_type()
-                ),
-            )),
+            (a, b) => Err(Self::arithmetic_type_error(a, b, "subtraction", "-")),
         }
     }
 
     pub fn multiplication(val1: Value, val2: Value) -> Result<Value, ComputationError> {
         match (&val1, &val2) {
-            (Value::I64(_), Value::I64(_)) => Self::check_int_operation(&val1, &val2, i64::checked_mul, "multiplication"),
+            (Value::I64(_), Value::I64(_)) => Self::check_int_operation(&val1, &val2, i64::checked_mul, "multiplication", "*"),
             (Value::F64(_), Value::F64(_)) => Self::check_float_operation(&val1, &val2, |a, b| a * b, "multiplication"),
-            (a, b) => Err(ComputationError::new(
-                ErrorSeverity::HIGH,
-                format!(
-                    "Cannot perform multiplication between values of type '{:?}' and '{:?}'.",
-                    a.to_type(),
-                    b.to_type()
-                ),
-            )),
+            (Value::String(string), Value::I64(count)) | (Value::I64(count), Value::String(string)) => Self::repeat_string(string, *count),
+            (a, b) => Err(Self::arithmetic_type_error(a, b, "multiplication", "*")),
         }
     }
 
     pub fn division(val1: Value, val2: Value) -> Result<Value, ComputationError> {
         match (&val1, &val2) {
-            (Value::I64(_), Value::I64(_)) => Self::check_int_operation(&val1, &val2, i64::checked_div, "division"),
+            (Value::I64(_), Value::I64(0)) => Err(Self::division_by_zero()),
+            (Value::F64(_), Value::F64(b)) if *b == 0.0 => Err(Self::division_by_zero()),
+            (Value::I64(_), Value::I64(_)) => Self::check_int_operation(&val1, &val2, i64::checked_div, "division", "/"),
             (Value::F64(_), Value::F64(_)) => Self::check_float_operation(&val1, &val2, |a, b| a / b, "division"),
-            (a, b) => Err(ComputationError::new(
-                ErrorSeverity::HIGH,
-                format!(
-                    "Cannot perform division between values of type '{:?}' and '{:?}'.",
-                    a.to_type(),
-                    b.to_type()
-                ),
-            )),
+            (a, b) => Err(Self::arithmetic_type_error(a, b, "division", "/")),
         }
     }
 
@@ -179,7 +253,7 @@ impl ALU {
             (a, b) => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
                 format!(
-                    "Cannot perform concatenation between values of type '{:?}' and '{:?}'.",
+                    "Cannot perform concatenation between values of type '{}' and '{}'.",
                     a.to_type(),
                     b.to_type()
                 ),
@@ -193,7 +267,7 @@ impl ALU {
             (a, b) => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
                 format!(
-                    "Cannot perform alternative between values of type '{:?}' and '{:?}'.",
+                    "Cannot perform alternative between values of type '{}' and '{}'.",
                     a.to_type(),
                     b.to_type()
                 ),
@@ -205,13 +279,11 @@ impl ALU {
         match (val1, val2) {
             (Value::I64(val1), Value::I64(val2)) => Ok(Value::Bool(val1 > val2)),
             (Value::F64(val1), Value::F64(val2)) => Ok(Value::Bool(val1 > val2)),
+            (Value::String(val1), Value::String(val2)) => Ok(Value::Bool(val1 > val2)),
+            (Value::Bool(_), Value::Bool(_)) => Err(Self::bool_ordering_error()),
             (a, b) => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
-                format!(
-                    "Cannot perform greater between values of type '{:?}' and '{:?}'.",
-                    a.to_type(),
-                    b.to_type()
-                ),
+                format!("Cannot perform greater between values of type '{}' and '{}'.", a.to_type(), b.to_type()),
             )),
         }
     }
@@ -220,10 +292,12 @@ impl ALU {
         match (val1, val2) {
             (Value::I64(val1), Value::I64(val2)) => Ok(Value::Bool(val1 >= val2)),
             (Value::F64(val1), Value::F64(val2)) => Ok(Value::Bool(val1 >= val2)),
+            (Value::String(val1), Value::String(val2)) => Ok(Value::Bool(val1 >= val2)),
+            (Value::Bool(_), Value::Bool(_)) => Err(Self::bool_ordering_error()),
             (a, b) => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
                 format!(
-                    "Cannot perform greater or equal between values of type '{:?}' and '{:?}'.",
+                    "Cannot perform greater or equal between values of type '{}' and '{}'.",
                     a.to_type(),
                     b.to_type()
                 ),
@@ -235,9 +309,11 @@ impl ALU {
         match (val1, val2) {
             (Value::I64(val1), Value::I64(val2)) => Ok(Value::Bool(val1 < val2)),
             (Value::F64(val1), Value::F64(val2)) => Ok(Value::Bool(val1 < val2)),
+            (Value::String(val1), Value::String(val2)) => Ok(Value::Bool(val1 < val2)),
+            (Value::Bool(_), Value::Bool(_)) => Err(Self::bool_ordering_error()),
             (a, b) => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
-                format!("Cannot perform less between values of type '{:?}' and '{:?}'.", a.to_type(), b.to_type()),
+                format!("Cannot perform less between values of type '{}' and '{}'.", a.to_type(), b.to_type()),
             )),
         }
     }
@@ -246,10 +322,12 @@ impl ALU {
         match (val1, val2) {
             (Value::I64(val1), Value::I64(val2)) => Ok(Value::Bool(val1 <= val2)),
             (Value::F64(val1), Value::F64(val2)) => Ok(Value::Bool(val1 <= val2)),
+            (Value::String(val1), Value::String(val2)) => Ok(Value::Bool(val1 <= val2)),
+            (Value::Bool(_), Value::Bool(_)) => Err(Self::bool_ordering_error()),
             (a, b) => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
                 format!(
-                    "Cannot perform less or equal between values of type '{:?}' and '{:?}'.",
+                    "Cannot perform less or equal between values of type '{}' and '{}'.",
                     a.to_type(),
                     b.to_type()
                 ),
@@ -262,10 +340,11 @@ impl ALU {
             (Value::I64(val1), Value::I64(val2)) => Ok(Value::Bool(val1 == val2)),
             (Value::F64(val1), Value::F64(val2)) => Ok(Value::Bool(val1 == val2)),
             (Value::String(val1), Value::String(val2)) => Ok(Value::Bool(val1 == val2)),
+            (Value::Char(val1), Value::Char(val2)) => Ok(Value::Bool(val1 == val2)),
             (Value::Bool(val1), Value::Bool(val2)) => Ok(Value::Bool(val1 == val2)),
             (a, b) => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
-                format!("Cannot perform equal between values of type '{:?}' and '{:?}'.", a.to_type(), b.to_type()),
+                format!("Cannot perform equal between values of type '{}' and '{}'.", a.to_type(), b.to_type()),
             )),
         }
     }
@@ -275,17 +354,34 @@ impl ALU {
             (Value::I64(val1), Value::I64(val2)) => Ok(Value::Bool(val1 != val2)),
             (Value::F64(val1), Value::F64(val2)) => Ok(Value::Bool(val1 != val2)),
             (Value::String(val1), Value::String(val2)) => Ok(Value::Bool(val1 != val2)),
+            (Value::Char(val1), Value::Char(val2)) => Ok(Value::Bool(val1 != val2)),
             (Value::Bool(val1), Value::Bool(val2)) => Ok(Value::Bool(val1 != val2)),
             (a, b) => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
-                format!(
-                    "Cannot perform not equal between values of type '{:?}' and '{:?}'.",
-                    a.to_type(),
-                    b.to_type()
-                ),
+                format!("Cannot perform not equal between values of type '{}' and '{}'.", a.to_type(), b.to_type()),
             )),
         }
     }
+
+    pub fn bit_and(val1: Value, val2: Value) -> Result<Value, ComputationError> {
+        Self::check_bitwise_operation(&val1, &val2, |a, b| a & b, "bitwise and", "&")
+    }
+
+    pub fn bit_or(val1: Value, val2: Value) -> Result<Value, ComputationError> {
+        Self::check_bitwise_operation(&val1, &val2, |a, b| a | b, "bitwise or", "|")
+    }
+
+    pub fn bit_xor(val1: Value, val2: Value) -> Result<Value, ComputationError> {
+        Self::check_bitwise_operation(&val1, &val2, |a, b| a ^ b, "bitwise xor", "^")
+    }
+
+    pub fn shift_left(val1: Value, val2: Value) -> Result<Value, ComputationError> {
+        Self::check_shift_operation(&val1, &val2, i64::checked_shl, "shift left", "<<")
+    }
+
+    pub fn shift_right(val1: Value, val2: Value) -> Result<Value, ComputationError> {
+        Self::check_shift_operation(&val1, &val2, i64::checked_shr, "shift right", ">>")
+    }
 }
 
 #[cfg(test)]
@@ -306,9 +402,22 @@ mod tests {
             (Value::F64(1.2), Type::Bool),
             (Value::F64(0.0), Type::Bool),
             (Value::String(String::from("1")), Type::I64),
+            (Value::String(String::from("-5")), Type::I64),
+            (Value::String(String::from("+5")), Type::I64),
+            (Value::String(String::from(" 5 ")), Type::I64),
             (Value::String(String::from("1.2")), Type::F64),
+            (Value::String(String::from(" -1.2\t")), Type::F64),
             (Value::String(String::from("some string")), Type::Bool),
             (Value::String(String::from("")), Type::Bool),
+            (Value::Char('a'), Type::I64),
+            (Value::I64(97), Type::Char),
+            (Value::Char('ż'), Type::Str),
+            (Value::Bool(true), Type::I64),
+            (Value::Bool(false), Type::I64),
+            (Value::Bool(true), Type::F64),
+            (Value::Bool(false), Type::F64),
+            (Value::Bool(true), Type::Str),
+            (Value::Bool(false), Type::Str),
         ];
 
         let expected = [
@@ -321,15 +430,64 @@ mod tests {
             Value::Bool(true),
             Value::Bool(false),
             Value::I64(1),
+            Value::I64(-5),
+            Value::I64(5),
+            Value::I64(5),
             Value::F64(1.2),
+            Value::F64(-1.2),
             Value::Bool(true),
             Value::Bool(false),
+            Value::I64(97),
+            Value::Char('a'),
+            Value::String(String::from("ż")),
+            Value::I64(1),
+            Value::I64(0),
+            Value::F64(1.0),
+            Value::F64(0.0),
+            Value::String(String::from("true")),
+            Value::String(String::from("false")),
         ];
 
         for idx in 0..data.len() {
             let (init, to_type) = &data[idx];
             let exp = &expected[idx];
-            assert_eq!(ALU::cast_to_type(init.clone(), *to_type).unwrap(), *exp);
+            assert_eq!(ALU::cast_to_type(init.clone(), to_type).unwrap(), *exp);
+        }
+    }
+
+    #[test]
+    fn cast_chains() {
+        fn cast_chain(value: Value, types: &[Type]) -> Value {
+            types.iter().fold(value, |value, to_type| ALU::cast_to_type(value, to_type).unwrap())
+        }
+
+        let data = [
+            (Value::I64(5), vec![Type::F64, Type::I64], Value::I64(5)),
+            (Value::I64(5), vec![Type::Str, Type::I64], Value::I64(5)),
+            (Value::I64(-5), vec![Type::Str, Type::F64, Type::I64], Value::I64(-5)),
+            (Value::F64(3.7), vec![Type::I64, Type::F64], Value::F64(3.0)),
+            (Value::F64(-3.7), vec![Type::I64], Value::I64(-3)),
+            (Value::F64(5.0), vec![Type::Str], Value::String(String::from("5.0"))),
+            (Value::F64(5.0), vec![Type::Str, Type::F64], Value::F64(5.0)),
+            (Value::F64(1.25), vec![Type::Str, Type::F64], Value::F64(1.25)),
+            (Value::Bool(true), vec![Type::Str], Value::String(String::from("true"))),
+            (Value::Bool(true), vec![Type::Str, Type::Bool], Value::Bool(true)),
+            (Value::Bool(false), vec![Type::Str, Type::Bool], Value::Bool(false)),
+            (Value::Char('a'), vec![Type::I64, Type::Char, Type::Str], Value::String(String::from("a"))),
+        ];
+
+        for (value, types, expected) in data {
+            assert_eq!(cast_chain(value, &types), expected);
+        }
+    }
+
+    #[test]
+    fn cast_out_of_range_float_to_int_fail() {
+        for value in [f64::NAN, f64::INFINITY, 1e19, -1e19] {
+            assert_eq!(
+                ALU::cast_to_type(Value::F64(value), &Type::I64).err().unwrap().message(),
+                format!("Cannot cast f64 '{}' to 'i64'.", value)
+            );
         }
     }
 
@@ -342,8 +500,88 @@ mod tests {
 
         for (val, to_type) in data {
             assert_eq!(
-                ALU::cast_to_type(val, to_type).err().unwrap().message(),
-                format!("Cannot cast String 'abc' to '{:?}'.", to_type)
+                ALU::cast_to_type(val, &to_type).err().unwrap().message(),
+                format!("Cannot cast str 'abc' to '{}'.", to_type)
+            );
+        }
+    }
+
+    #[test]
+    fn cast_signed_strings() {
+        let data = [
+            ("-5", Type::I64, Value::I64(-5)),
+            ("+5", Type::I64, Value::I64(5)),
+            ("+3.5", Type::F64, Value::F64(3.5)),
+            ("-3.5", Type::F64, Value::F64(-3.5)),
+        ];
+        for (text, to_type, expected) in data {
+            assert_eq!(ALU::cast_to_type(Value::String(String::from(text)), &to_type).unwrap(), expected);
+        }
+
+        for (text, to_type) in [("--5", Type::I64), ("+-5", Type::I64), ("++3.5", Type::F64), ("-+3.5", Type::F64)] {
+            assert_eq!(
+                ALU::cast_to_type(Value::String(String::from(text)), &to_type).err().unwrap().message(),
+                format!("Cannot cast str '{}' to '{}'.", text, to_type)
+            );
+        }
+    }
+
+    #[test]
+    fn cast_string_with_inner_whitespace_fail() {
+        let data = [("4 2", Type::I64), ("  4 2  ", Type::I64), ("1. 5", Type::F64), ("- 1", Type::I64)];
+
+        for (text, to_type) in data {
+            assert_eq!(
+                ALU::cast_to_type(Value::String(String::from(text)), &to_type).err().unwrap().message(),
+                format!("Cannot cast str '{}' to '{}'.", text, to_type)
+            );
+        }
+        assert_eq!(
+            ALU::cast_to_type(Value::String(String::from("  42  ")), &Type::I64).unwrap(),
+            Value::I64(42)
+        );
+        assert_eq!(
+            ALU::cast_to_type(Value::String(String::from("\n4.5 ")), &Type::F64).unwrap(),
+            Value::F64(4.5)
+        );
+    }
+
+    #[test]
+    fn cast_unsupported_types_fail() {
+        let data = [
+            (Value::Bool(true), Type::Char),
+            (Value::F64(1.5), Type::Char),
+            (Value::Char('a'), Type::Void),
+        ];
+        let expected = [
+            "Cannot cast 'bool' to 'char'.",
+            "Cannot cast 'f64' to 'char'.",
+            "Cannot cast 'char' to 'void'.",
+        ];
+
+        for ((val, to_type), exp) in data.into_iter().zip(expected) {
+            assert_eq!(ALU::cast_to_type(val, &to_type).err().unwrap().message(), exp);
+        }
+    }
+
+    #[test]
+    fn cast_empty_string_to_number_fail() {
+        for text in ["", "  "] {
+            for to_type in [Type::I64, Type::F64] {
+                assert_eq!(
+                    ALU::cast_to_type(Value::String(String::from(text)), &to_type).err().unwrap().message(),
+                    format!("Cannot cast str '{}' to '{}'.", text, to_type)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cast_to_char_fail() {
+        for value in [-1, 0xD800, 0x110000] {
+            assert_eq!(
+                ALU::cast_to_type(Value::I64(value), &Type::Char).err().unwrap().message(),
+                format!("Cannot cast i64 '{}' to 'char'.", value)
             );
         }
     }
@@ -368,6 +606,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unary_plus() {
+        assert_eq!(ALU::unary_plus(Value::I64(-1)).unwrap(), Value::I64(-1));
+        assert_eq!(ALU::unary_plus(Value::F64(21.37)).unwrap(), Value::F64(21.37));
+        assert_eq!(
+            ALU::unary_plus(Value::Bool(true)).err().unwrap().message(),
+            String::from("Cannot perform unary plus on type 'bool'.")
+        );
+    }
+
+    #[test]
+    fn mixed_number_types_hint() {
+        assert_eq!(
+            ALU::add(Value::I64(1), Value::F64(2.0)).err().unwrap().message(),
+            String::from("Cannot perform addition between values of type 'i64' and 'f64'. Consider casting with 'as', e.g. '1 as f64 + 2.0'.")
+        );
+        assert_eq!(
+            ALU::division(Value::F64(1.0), Value::I64(2)).err().unwrap().message(),
+            String::from("Cannot perform division between values of type 'f64' and 'i64'. Consider casting with 'as', e.g. '1.0 / 2 as f64'.")
+        );
+        assert!(!ALU::add(Value::Bool(true), Value::I64(1))
+            .err()
+            .unwrap()
+            .message()
+            .contains("Consider casting"));
+    }
+
     #[test]
     fn add() {
         let data = [
@@ -386,16 +651,20 @@ mod tests {
 
     #[test]
     fn add_fail() {
+        assert_eq!(
+            ALU::add(Value::I64(i64::MAX), Value::I64(1)).err().unwrap().message(),
+            String::from("Overflow occurred when performing addition on i64s: 9223372036854775807 + 1.")
+        );
         assert_eq!(
             ALU::add(Value::I64(6532475327647647762), Value::I64(6532475327647647762))
                 .err()
                 .unwrap()
                 .message(),
-            String::from("Overflow occurred when performing addition on i64s.")
+            String::from("Overflow occurred when performing addition on i64s: 6532475327647647762 + 6532475327647647762.")
         );
         assert_eq!(
             ALU::add(Value::I64(1), Value::F64(2.0)).err().unwrap().message(),
-            String::from("Cannot perform addition between values of type 'i64' and 'f64'.")
+            String::from("Cannot perform addition between values of type 'i64' and 'f64'. Consider casting with 'as', e.g. '1 as f64 + 2.0'.")
         );
     }
 
@@ -418,11 +687,11 @@ mod tests {
                 .err()
                 .unwrap()
                 .message(),
-            String::from("Overflow occurred when performing subtraction on i64s.")
+            String::from("Overflow occurred when performing subtraction on i64s: -6532475327647647762 - 6532475327647647762.")
         );
         assert_eq!(
             ALU::subtract(Value::I64(1), Value::F64(2.0)).err().unwrap().message(),
-            String::from("Cannot perform subtraction between values of type 'i64' and 'f64'.")
+            String::from("Cannot perform subtraction between values of type 'i64' and 'f64'. Consider casting with 'as', e.g. '1 as f64 - 2.0'.")
         );
         assert_eq!(
             ALU::subtract(Value::String(String::from("a")), Value::String(String::from("a")))
@@ -445,6 +714,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn string_repeat() {
+        let data = [
+            (Value::String(String::from("ab")), Value::I64(3)),
+            (Value::I64(3), Value::String(String::from("ab"))),
+            (Value::String(String::from("x")), Value::I64(0)),
+        ];
+
+        let expected = [
+            Value::String(String::from("ababab")),
+            Value::String(String::from("ababab")),
+            Value::String(String::new()),
+        ];
+
+        for idx in 0..data.len() {
+            let (val1, val2) = &data[idx];
+            assert_eq!(ALU::multiplication(val1.clone(), val2.clone()).unwrap(), expected[idx]);
+        }
+    }
+
+    #[test]
+    fn string_repeat_fail() {
+        assert_eq!(
+            ALU::multiplication(Value::String(String::from("ab")), Value::I64(-1))
+                .err()
+                .unwrap()
+                .message(),
+            String::from("Cannot repeat string a negative number of times ('-1').")
+        );
+        assert_eq!(
+            ALU::multiplication(Value::I64(i64::MAX), Value::String(String::from("ab")))
+                .err()
+                .unwrap()
+                .message(),
+            String::from("Repeated string would exceed the maximum size of 1000000 bytes.")
+        );
+        assert_eq!(
+            ALU::multiplication(Value::String(String::from("ab")), Value::F64(2.0))
+                .err()
+                .unwrap()
+                .message(),
+            String::from("Cannot perform multiplication between values of type 'str' and 'f64'.")
+        );
+    }
+
     #[test]
     fn multiplication_fail() {
         assert_eq!(
@@ -452,11 +766,11 @@ mod tests {
                 .err()
                 .unwrap()
                 .message(),
-            String::from("Overflow occurred when performing multiplication on i64s.")
+            String::from("Overflow occurred when performing multiplication on i64s: 6532475327647647762 * 6532475327647647762.")
         );
         assert_eq!(
             ALU::multiplication(Value::I64(1), Value::F64(2.0)).err().unwrap().message(),
-            String::from("Cannot perform multiplication between values of type 'i64' and 'f64'.")
+            String::from("Cannot perform multiplication between values of type 'i64' and 'f64'. Consider casting with 'as', e.g. '1 as f64 * 2.0'.")
         );
         assert_eq!(
             ALU::multiplication(Value::String(String::from("a")), Value::String(String::from("a")))
@@ -483,11 +797,21 @@ mod tests {
     fn division_fail() {
         assert_eq!(
             ALU::division(Value::I64(6532475327647647762), Value::I64(0)).err().unwrap().message(),
-            String::from("Overflow occurred when performing division on i64s.")
+            String::from("Division by zero.")
+        );
+        assert_eq!(
+            ALU::division(Value::I64(i64::MIN), Value::I64(-1)).err().unwrap().message(),
+            String::from("Overflow occurred when performing division on i64s: -9223372036854775808 / -1.")
         );
+        for val1 in [1.0, -1.0, 0.0] {
+            assert_eq!(
+                ALU::division(Value::F64(val1), Value::F64(0.0)).err().unwrap().message(),
+                String::from("Division by zero.")
+            );
+        }
         assert_eq!(
             ALU::division(Value::I64(1), Value::F64(2.0)).err().unwrap().message(),
-            String::from("Cannot perform division between values of type 'i64' and 'f64'.")
+            String::from("Cannot perform division between values of type 'i64' and 'f64'. Consider casting with 'as', e.g. '1 as f64 / 2.0'.")
         );
         assert_eq!(
             ALU::division(Value::String(String::from("a")), Value::String(String::from("a")))
@@ -530,6 +854,10 @@ mod tests {
         assert_eq!(ALU::greater(Value::F64(1.0), Value::F64(2.0)).unwrap(), Value::Bool(false));
         assert_eq!(ALU::greater(Value::F64(2.0), Value::F64(2.0)).unwrap(), Value::Bool(false));
         assert_eq!(ALU::greater(Value::F64(3.0), Value::F64(2.0)).unwrap(), Value::Bool(true));
+        assert_eq!(
+            ALU::greater(Value::Bool(true), Value::Bool(false)).err().unwrap().message(),
+            String::from("Ordering comparisons are not defined for type 'bool'.")
+        );
         assert_eq!(
             ALU::greater(Value::I64(2), Value::F64(3.0)).err().unwrap().message(),
             String::from("Cannot perform greater between values of type 'i64' and 'f64'.")
@@ -544,6 +872,10 @@ mod tests {
         assert_eq!(ALU::greater_or_equal(Value::F64(1.0), Value::F64(2.0)).unwrap(), Value::Bool(false));
         assert_eq!(ALU::greater_or_equal(Value::F64(2.0), Value::F64(2.0)).unwrap(), Value::Bool(true));
         assert_eq!(ALU::greater_or_equal(Value::F64(3.0), Value::F64(2.0)).unwrap(), Value::Bool(true));
+        assert_eq!(
+            ALU::greater_or_equal(Value::Bool(true), Value::Bool(true)).err().unwrap().message(),
+            String::from("Ordering comparisons are not defined for type 'bool'.")
+        );
         assert_eq!(
             ALU::greater_or_equal(Value::I64(2), Value::F64(3.0)).err().unwrap().message(),
             String::from("Cannot perform greater or equal between values of type 'i64' and 'f64'.")
@@ -558,6 +890,10 @@ mod tests {
         assert_eq!(ALU::less(Value::F64(1.0), Value::F64(2.0)).unwrap(), Value::Bool(true));
         assert_eq!(ALU::less(Value::F64(2.0), Value::F64(2.0)).unwrap(), Value::Bool(false));
         assert_eq!(ALU::less(Value::F64(3.0), Value::F64(2.0)).unwrap(), Value::Bool(false));
+        assert_eq!(
+            ALU::less(Value::Bool(false), Value::Bool(true)).err().unwrap().message(),
+            String::from("Ordering comparisons are not defined for type 'bool'.")
+        );
         assert_eq!(
             ALU::less(Value::I64(2), Value::F64(3.0)).err().unwrap().message(),
             String::from("Cannot perform less between values of type 'i64' and 'f64'.")
@@ -572,12 +908,50 @@ mod tests {
         assert_eq!(ALU::less_or_equal(Value::F64(1.0), Value::F64(2.0)).unwrap(), Value::Bool(true));
         assert_eq!(ALU::less_or_equal(Value::F64(2.0), Value::F64(2.0)).unwrap(), Value::Bool(true));
         assert_eq!(ALU::less_or_equal(Value::F64(3.0), Value::F64(2.0)).unwrap(), Value::Bool(false));
+        assert_eq!(
+            ALU::less_or_equal(Value::Bool(false), Value::Bool(false)).err().unwrap().message(),
+            String::from("Ordering comparisons are not defined for type 'bool'.")
+        );
         assert_eq!(
             ALU::less_or_equal(Value::I64(2), Value::F64(3.0)).err().unwrap().message(),
             String::from("Cannot perform less or equal between values of type 'i64' and 'f64'.")
         );
     }
 
+    #[test]
+    fn string_ordering() {
+        let apple = || Value::String(String::from("apple"));
+        let banana = || Value::String(String::from("banana"));
+
+        assert_eq!(ALU::less(apple(), banana()).unwrap(), Value::Bool(true));
+        assert_eq!(ALU::greater(apple(), banana()).unwrap(), Value::Bool(false));
+        assert_eq!(ALU::less_or_equal(apple(), apple()).unwrap(), Value::Bool(true));
+        assert_eq!(ALU::greater_or_equal(apple(), apple()).unwrap(), Value::Bool(true));
+        assert_eq!(ALU::less(apple(), apple()).unwrap(), Value::Bool(false));
+        assert_eq!(
+            ALU::greater(Value::String(String::from("b")), Value::String(String::from("abc"))).unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            ALU::less(Value::String(String::from("Z")), Value::String(String::from("a"))).unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            ALU::less(apple(), Value::Char('a')).err().unwrap().message(),
+            String::from("Cannot perform less between values of type 'str' and 'char'.")
+        );
+    }
+
+    #[test]
+    fn bool_comparison() {
+        assert_eq!(ALU::equal(Value::Bool(true), Value::Bool(true)).unwrap(), Value::Bool(true));
+        assert_eq!(ALU::not_equal(Value::Bool(true), Value::Bool(false)).unwrap(), Value::Bool(true));
+        assert_eq!(
+            ALU::less(Value::Bool(true), Value::Bool(false)).err().unwrap().message(),
+            String::from("Ordering comparisons are not defined for type 'bool'.")
+        );
+    }
+
     #[test]
     fn equal() {
         assert_eq!(ALU::equal(Value::I64(1), Value::I64(2)).unwrap(), Value::Bool(false));
@@ -621,4 +995,44 @@ mod tests {
             String::from("Cannot perform not equal between values of type 'bool' and 'i64'.")
         );
     }
+
+    #[test]
+    fn bitwise_operations() {
+        assert_eq!(ALU::bit_and(Value::I64(0b1100), Value::I64(0b1010)).unwrap(), Value::I64(0b1000));
+        assert_eq!(ALU::bit_or(Value::I64(0b1100), Value::I64(0b1010)).unwrap(), Value::I64(0b1110));
+        assert_eq!(ALU::bit_xor(Value::I64(0b1100), Value::I64(0b1010)).unwrap(), Value::I64(0b0110));
+        assert_eq!(ALU::bit_and(Value::I64(-1), Value::I64(5)).unwrap(), Value::I64(5));
+        assert_eq!(
+            ALU::bit_and(Value::Bool(true), Value::Bool(false)).err().unwrap().message(),
+            String::from("Cannot perform bitwise and between values of type 'bool' and 'bool'.")
+        );
+        assert_eq!(
+            ALU::bit_or(Value::I64(1), Value::F64(1.0)).err().unwrap().message(),
+            String::from("Cannot perform bitwise or between values of type 'i64' and 'f64'.")
+        );
+        assert_eq!(
+            ALU::bit_xor(Value::String(String::from("a")), Value::I64(1)).err().unwrap().message(),
+            String::from("Cannot perform bitwise xor between values of type 'str' and 'i64'.")
+        );
+    }
+
+    #[test]
+    fn shift_operations() {
+        assert_eq!(ALU::shift_left(Value::I64(1), Value::I64(4)).unwrap(), Value::I64(16));
+        assert_eq!(ALU::shift_left(Value::I64(1), Value::I64(63)).unwrap(), Value::I64(i64::MIN));
+        assert_eq!(ALU::shift_right(Value::I64(16), Value::I64(4)).unwrap(), Value::I64(1));
+        assert_eq!(ALU::shift_right(Value::I64(-16), Value::I64(2)).unwrap(), Value::I64(-4));
+        assert_eq!(
+            ALU::shift_left(Value::I64(1), Value::I64(64)).err().unwrap().message(),
+            String::from("Cannot perform shift left by '64' - shift amount has to be in range from 0 to 63.")
+        );
+        assert_eq!(
+            ALU::shift_right(Value::I64(1), Value::I64(-1)).err().unwrap().message(),
+            String::from("Cannot perform shift right by '-1' - shift amount has to be in range from 0 to 63.")
+        );
+        assert_eq!(
+            ALU::shift_left(Value::F64(1.0), Value::I64(1)).err().unwrap().message(),
+            String::from("Cannot perform shift left between values of type 'f64' and 'i64'.")
+        );
+    }
 }
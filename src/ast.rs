@@ -1,6 +1,11 @@
-use std::{collections::HashMap, fmt::Debug, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Debug, Display},
+    rc::Rc,
+};
 
-use crate::{lazy_stream_reader::Position, std_functions::StdFunction};
+use crate::{errors::ComputationError, lazy_stream_reader::Position, std_functions::StdFunction, value::Value};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Node<T> {
@@ -27,14 +32,41 @@ pub enum Expression {
     Subtraction(BNode<Expression>, BNode<Expression>),
     Multiplication(BNode<Expression>, BNode<Expression>),
     Division(BNode<Expression>, BNode<Expression>),
+    // Bitwise operations
+    BitwiseOr(BNode<Expression>, BNode<Expression>),
+    BitwiseXor(BNode<Expression>, BNode<Expression>),
+    BitwiseAnd(BNode<Expression>, BNode<Expression>),
+    ShiftLeft(BNode<Expression>, BNode<Expression>),
+    ShiftRight(BNode<Expression>, BNode<Expression>),
     // Unary operations
     BooleanNegation(BNode<Expression>),
     ArithmeticNegation(BNode<Expression>),
+    UnaryPlus(BNode<Expression>),
+    // Conditional
+    Ternary {
+        condition: BNode<Expression>,
+        if_true: BNode<Expression>,
+        if_false: BNode<Expression>,
+    },
+    // 'switch (x: y) { (y > 0) -> "positive", (true) -> "other" }' - the value of the first matching case
+    Switch {
+        expressions: Vec<Node<SwitchExpression>>,
+        cases: Vec<Node<SwitchValueCase>>,
+        default: Option<BNode<Expression>>,
+    },
     // Casting
     Casting {
         value: BNode<Expression>,
         to_type: Node<Type>,
     },
+    // Grouping
+    Parenthesized(BNode<Expression>),
+    Tuple(Vec<Node<Expression>>),
+    Array(Vec<Node<Expression>>),
+    Index {
+        value: BNode<Expression>,
+        index: BNode<Expression>,
+    },
     // Values
     Literal(Literal),
     Variable(String),
@@ -49,20 +81,30 @@ pub enum Literal {
     True,
     False,
     String(String),
+    Char(char),
     I64(i64),
     F64(f64),
 }
 
-#[derive(Clone, PartialEq, Copy)]
+#[derive(Clone, PartialEq)]
 pub enum Type {
     Bool,
     Str,
+    Char,
     I64,
     F64,
     Void,
+    Tuple(Vec<Type>),
+    Array(Box<Type>),
 }
 
 impl Debug for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Type::Bool => {
@@ -77,9 +119,19 @@ impl Debug for Type {
             Type::Str => {
                 write!(f, "str")
             }
+            Type::Char => {
+                write!(f, "char")
+            }
             Type::Void => {
                 write!(f, "void")
             }
+            Type::Tuple(types) => {
+                let types: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+                write!(f, "({})", types.join(", "))
+            }
+            Type::Array(element_type) => {
+                write!(f, "[{}]", element_type)
+            }
         }
     }
 }
@@ -94,6 +146,8 @@ pub enum PassedBy {
 pub struct Argument {
     pub value: Node<Expression>,
     pub passed_by: PassedBy,
+    // 'f(x = 1)' - named arguments always follow the positional ones
+    pub name: Option<Node<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -106,11 +160,18 @@ pub enum Statement {
         var_type: Node<Type>,
         identifier: Node<String>,
         value: Option<Node<Expression>>,
+        is_const: bool,
     },
     Assignment {
         identifier: Node<String>,
         value: Node<Expression>,
     },
+    // 'x[1][2] = value' - one index for every level of nesting
+    IndexAssignment {
+        identifier: Node<String>,
+        indices: Vec<Node<Expression>>,
+        value: Node<Expression>,
+    },
     Conditional {
         condition: Node<Expression>,
         if_block: Node<Block>,
@@ -125,9 +186,18 @@ pub enum Statement {
     Switch {
         expressions: Vec<Node<SwitchExpression>>,
         cases: Vec<Node<SwitchCase>>,
+        // run when none of the cases matched
+        default: Option<Node<Block>>,
     },
     Return(Option<Node<Expression>>),
     Break,
+    // ends a switch case, but keeps checking the following cases
+    Fallthrough,
+    // '(i64 x, i64 y) = f();' - only allowed when declaring the variables
+    Destructuring {
+        targets: Vec<(Node<Type>, Node<String>)>,
+        value: Node<Expression>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -135,6 +205,7 @@ pub struct Parameter {
     pub passed_by: PassedBy,
     pub parameter_type: Node<Type>,
     pub identifier: Node<String>,
+    pub default_value: Option<Node<Expression>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -149,6 +220,59 @@ pub struct SwitchCase {
     pub block: Node<Block>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchValueCase {
+    pub condition: Node<Expression>,
+    pub value: Node<Expression>,
+}
+
+impl Argument {
+    // only a variable can be modified by the called function
+    pub fn invalid_reference(&self) -> Option<&'static str> {
+        match (&self.passed_by, &self.value.value) {
+            (PassedBy::Value, _) | (PassedBy::Reference, Expression::Variable(_)) => None,
+            (PassedBy::Reference, Expression::Literal(_)) => Some("Cannot pass a literal by reference - only variables can be passed by reference."),
+            (PassedBy::Reference, _) => Some("Cannot pass an expression by reference - only variables can be passed by reference."),
+        }
+    }
+}
+
+impl FunctionDeclaration {
+    // index of the parameter every argument is given for - named arguments are matched by name
+    pub fn bind_arguments(&self, arguments: &[BNode<Argument>]) -> Result<Vec<usize>, String> {
+        let mut bound: Vec<usize> = vec![];
+        for (idx, argument) in arguments.iter().enumerate() {
+            let parameter_idx = match &argument.value.name {
+                Some(name) => self
+                    .parameters
+                    .iter()
+                    .position(|parameter| parameter.value.identifier.value == name.value)
+                    .ok_or_else(|| format!("Function '{}' has no parameter named '{}'.", self.identifier.value, name.value))?,
+                None => idx,
+            };
+            if bound.contains(&parameter_idx) {
+                return Err(format!(
+                    "Parameter '{}' of function '{}' is given more than once.",
+                    self.parameters[parameter_idx].value.identifier.value, self.identifier.value
+                ));
+            }
+            bound.push(parameter_idx);
+        }
+        Ok(bound)
+    }
+}
+
+impl Program {
+    // registered before checking, so the semantic checker sees it like any other std function
+    #[allow(dead_code)]
+    pub fn register_std_function<F>(&mut self, name: &str, params: Vec<Type>, function: F)
+    where
+        F: Fn(&Vec<Rc<RefCell<Value>>>) -> Result<Option<Value>, ComputationError> + 'static,
+    {
+        self.std_functions.insert(name.to_owned(), StdFunction::custom(name, params, function));
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Block(pub Vec<Node<Statement>>);
 
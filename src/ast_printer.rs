@@ -0,0 +1,427 @@
+use crate::{
+    ast::{Argument, Block, Expression, Literal, Node, Parameter, PassedBy, Program, Statement, SwitchCase, SwitchExpression, Type},
+    errors::IError,
+    lazy_stream_reader::Position,
+    visitor::Visitor,
+};
+
+const INDENTATION: &str = "  ";
+
+pub struct AstPrinter<'a> {
+    program: &'a Program,
+    output: String,
+    depth: usize,
+    position: Position,
+}
+
+impl<'a> AstPrinter<'a> {
+    #![allow(unused_must_use)]
+    pub fn new(program: &'a Program) -> Self {
+        Self {
+            program,
+            output: String::new(),
+            depth: 0,
+            position: Position {
+                line: 0,
+                column: 0,
+                offset: 0,
+            },
+        }
+    }
+
+    pub fn print(&mut self) -> String {
+        self.output.clear();
+        self.depth = 0;
+        self.visit_program(self.program);
+        self.output.clone()
+    }
+
+    fn write_line(&mut self, text: String, position: Position) {
+        self.output.push_str(&INDENTATION.repeat(self.depth));
+        self.output.push_str(&format!("{} ({:?})\n", text, position));
+    }
+
+    fn nested<F: FnOnce(&mut Self)>(&mut self, print_children: F) {
+        self.depth += 1;
+        print_children(self);
+        self.depth -= 1;
+    }
+
+    fn passed_by_prefix(passed_by: &PassedBy) -> &'static str {
+        match passed_by {
+            PassedBy::Reference => "&",
+            PassedBy::Value => "",
+        }
+    }
+
+    fn expression_children(expression: &Expression) -> (&'static str, Vec<&Node<Expression>>) {
+        match expression {
+            Expression::Alternative(lhs, rhs) => ("Alternative", vec![lhs, rhs]),
+            Expression::Concatenation(lhs, rhs) => ("Concatenation", vec![lhs, rhs]),
+            Expression::Greater(lhs, rhs) => ("Greater", vec![lhs, rhs]),
+            Expression::GreaterEqual(lhs, rhs) => ("GreaterEqual", vec![lhs, rhs]),
+            Expression::Less(lhs, rhs) => ("Less", vec![lhs, rhs]),
+            Expression::LessEqual(lhs, rhs) => ("LessEqual", vec![lhs, rhs]),
+            Expression::Equal(lhs, rhs) => ("Equal", vec![lhs, rhs]),
+            Expression::NotEqual(lhs, rhs) => ("NotEqual", vec![lhs, rhs]),
+            Expression::Addition(lhs, rhs) => ("Addition", vec![lhs, rhs]),
+            Expression::Subtraction(lhs, rhs) => ("Subtraction", vec![lhs, rhs]),
+            Expression::Multiplication(lhs, rhs) => ("Multiplication", vec![lhs, rhs]),
+            Expression::Division(lhs, rhs) => ("Division", vec![lhs, rhs]),
+            Expression::BitwiseOr(lhs, rhs) => ("BitwiseOr", vec![lhs, rhs]),
+            Expression::BitwiseXor(lhs, rhs) => ("BitwiseXor", vec![lhs, rhs]),
+            Expression::BitwiseAnd(lhs, rhs) => ("BitwiseAnd", vec![lhs, rhs]),
+            Expression::ShiftLeft(lhs, rhs) => ("ShiftLeft", vec![lhs, rhs]),
+            Expression::ShiftRight(lhs, rhs) => ("ShiftRight", vec![lhs, rhs]),
+            Expression::BooleanNegation(value) => ("BooleanNegation", vec![value]),
+            Expression::ArithmeticNegation(value) => ("ArithmeticNegation", vec![value]),
+            Expression::UnaryPlus(value) => ("UnaryPlus", vec![value]),
+            Expression::Parenthesized(value) => ("Parenthesized", vec![value]),
+            Expression::Tuple(values) => ("Tuple", values.iter().collect()),
+            Expression::Array(values) => ("Array", values.iter().collect()),
+            Expression::Index { value, index } => ("Index", vec![value, index]),
+            Expression::Ternary {
+                condition,
+                if_true,
+                if_false,
+            } => ("Ternary", vec![condition, if_true, if_false]),
+            Expression::Casting { value, .. } => ("Casting", vec![value]),
+            Expression::Literal(_) | Expression::Variable(_) | Expression::FunctionCall { .. } | Expression::Switch { .. } => ("", vec![]),
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for AstPrinter<'a> {
+    #![allow(unused_must_use)]
+    fn visit_program(&mut self, program: &'a Program) -> Result<(), Box<dyn IError>> {
+        self.output.push_str("Program\n");
+        self.nested(|printer| {
+            // functions are kept in a map - sort them so the dump is stable
+            let mut functions: Vec<_> = program.functions.values().collect();
+            functions.sort_by_key(|function| function.position.offset);
+
+            for function in functions {
+                let declaration = &function.value;
+                printer.write_line(
+                    format!(
+                        "FunctionDeclaration '{}' -> {}",
+                        declaration.identifier.value, declaration.return_type.value
+                    ),
+                    function.position,
+                );
+                printer.nested(|printer| {
+                    for parameter in &declaration.parameters {
+                        printer.visit_parameter(parameter);
+                    }
+                    printer.visit_block(&declaration.block);
+                });
+            }
+
+            for statement in &program.statements {
+                printer.visit_statement(statement);
+            }
+        });
+        Ok(())
+    }
+
+    fn visit_statement(&mut self, statement: &'a Node<Statement>) -> Result<(), Box<dyn IError>> {
+        let position = statement.position;
+        match &statement.value {
+            Statement::FunctionCall { identifier, arguments } => {
+                self.write_line(format!("FunctionCall '{}'", identifier.value), position);
+                self.nested(|printer| {
+                    for argument in arguments {
+                        printer.visit_argument(argument);
+                    }
+                });
+            }
+            Statement::Declaration {
+                var_type,
+                identifier,
+                value,
+                is_const,
+            } => {
+                let keyword = if *is_const { "const " } else { "" };
+                self.write_line(format!("Declaration {}{} '{}'", keyword, var_type.value, identifier.value), position);
+                self.nested(|printer| {
+                    if let Some(expression) = value {
+                        printer.visit_expression(expression);
+                    }
+                });
+            }
+            Statement::Assignment { identifier, value } => {
+                self.write_line(format!("Assignment '{}'", identifier.value), position);
+                self.nested(|printer| {
+                    printer.visit_expression(value);
+                });
+            }
+            Statement::IndexAssignment { identifier, indices, value } => {
+                self.write_line(format!("IndexAssignment '{}'", identifier.value), position);
+                self.nested(|printer| {
+                    for index in indices {
+                        printer.visit_expression(index);
+                    }
+                    printer.visit_expression(value);
+                });
+            }
+            Statement::Conditional {
+                condition,
+                if_block,
+                else_block,
+            } => {
+                self.write_line(String::from("Conditional"), position);
+                self.nested(|printer| {
+                    printer.visit_expression(condition);
+                    printer.visit_block(if_block);
+                    if let Some(else_block) = else_block {
+                        printer.visit_block(else_block);
+                    }
+                });
+            }
+            Statement::ForLoop {
+                declaration,
+                condition,
+                assignment,
+                block,
+            } => {
+                self.write_line(String::from("ForLoop"), position);
+                self.nested(|printer| {
+                    if let Some(declaration) = declaration {
+                        printer.visit_statement(declaration);
+                    }
+                    printer.visit_expression(condition);
+                    if let Some(assignment) = assignment {
+                        printer.visit_statement(assignment);
+                    }
+                    printer.visit_block(block);
+                });
+            }
+            Statement::Switch { expressions, cases, default } => {
+                self.write_line(String::from("Switch"), position);
+                self.nested(|printer| {
+                    for expression in expressions {
+                        printer.visit_switch_expression(expression);
+                    }
+                    for case in cases {
+                        printer.visit_switch_case(case);
+                    }
+                    if let Some(default) = default {
+                        printer.write_line(String::from("DefaultCase"), default.position);
+                        printer.nested(|printer| {
+                            printer.visit_block(default);
+                        });
+                    }
+                });
+            }
+            Statement::Return(value) => {
+                self.write_line(String::from("Return"), position);
+                self.nested(|printer| {
+                    if let Some(expression) = value {
+                        printer.visit_expression(expression);
+                    }
+                });
+            }
+            Statement::Break => {
+                self.write_line(String::from("Break"), position);
+            }
+            Statement::Fallthrough => {
+                self.write_line(String::from("Fallthrough"), position);
+            }
+            Statement::Destructuring { targets, value } => {
+                let targets: Vec<String> = targets
+                    .iter()
+                    .map(|(var_type, identifier)| format!("{} '{}'", var_type.value, identifier.value))
+                    .collect();
+                self.write_line(format!("Destructuring ({})", targets.join(", ")), position);
+                self.nested(|printer| {
+                    printer.visit_expression(value);
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_expression(&mut self, expression: &'a Node<Expression>) -> Result<(), Box<dyn IError>> {
+        self.position = expression.position;
+        match &expression.value {
+            Expression::Literal(literal) => return self.visit_literal(literal),
+            Expression::Variable(variable) => return self.visit_variable(variable),
+            Expression::FunctionCall { identifier, arguments } => {
+                self.write_line(format!("FunctionCall '{}'", identifier.value), expression.position);
+                self.nested(|printer| {
+                    for argument in arguments {
+                        printer.visit_argument(argument);
+                    }
+                });
+                return Ok(());
+            }
+            Expression::Switch { expressions, cases, default } => {
+                self.write_line(String::from("Switch"), expression.position);
+                self.nested(|printer| {
+                    for switch_expression in expressions {
+                        printer.visit_switch_expression(switch_expression);
+                    }
+                    for case in cases {
+                        printer.write_line(String::from("SwitchCase"), case.position);
+                        printer.nested(|printer| {
+                            printer.visit_expression(&case.value.condition);
+                            printer.visit_expression(&case.value.value);
+                        });
+                    }
+                    if let Some(default) = default {
+                        printer.write_line(String::from("DefaultCase"), default.position);
+                        printer.nested(|printer| {
+                            printer.visit_expression(default);
+                        });
+                    }
+                });
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let (name, children) = Self::expression_children(&expression.value);
+        self.write_line(String::from(name), expression.position);
+        self.nested(|printer| {
+            for child in children {
+                printer.visit_expression(child);
+            }
+            if let Expression::Casting { to_type, .. } = &expression.value {
+                printer.visit_type(to_type);
+            }
+        });
+        Ok(())
+    }
+
+    fn visit_parameter(&mut self, parameter: &'a Node<Parameter>) -> Result<(), Box<dyn IError>> {
+        let value = &parameter.value;
+        self.write_line(
+            format!(
+                "Parameter {}{} '{}'",
+                Self::passed_by_prefix(&value.passed_by),
+                value.parameter_type.value,
+                value.identifier.value
+            ),
+            parameter.position,
+        );
+        self.nested(|printer| {
+            if let Some(default_value) = &value.default_value {
+                printer.visit_expression(default_value);
+            }
+        });
+        Ok(())
+    }
+
+    fn visit_argument(&mut self, argument: &'a Node<Argument>) -> Result<(), Box<dyn IError>> {
+        let line = match &argument.value.name {
+            Some(name) => format!("Argument {:?} '{}'", argument.value.passed_by, name.value),
+            None => format!("Argument {:?}", argument.value.passed_by),
+        };
+        self.write_line(line, argument.position);
+        self.nested(|printer| {
+            printer.visit_expression(&argument.value.value);
+        });
+        Ok(())
+    }
+
+    fn visit_type(&mut self, node_type: &'a Node<Type>) -> Result<(), Box<dyn IError>> {
+        self.write_line(format!("Type {}", node_type.value), node_type.position);
+        Ok(())
+    }
+
+    fn visit_block(&mut self, block: &'a Node<Block>) -> Result<(), Box<dyn IError>> {
+        self.write_line(String::from("Block"), block.position);
+        self.nested(|printer| {
+            for statement in &block.value.0 {
+                printer.visit_statement(statement);
+            }
+        });
+        Ok(())
+    }
+
+    fn visit_switch_expression(&mut self, switch_expression: &'a Node<SwitchExpression>) -> Result<(), Box<dyn IError>> {
+        let text = match &switch_expression.value.alias {
+            Some(alias) => format!("SwitchExpression as '{}'", alias.value),
+            None => String::from("SwitchExpression"),
+        };
+        self.write_line(text, switch_expression.position);
+        self.nested(|printer| {
+            printer.visit_expression(&switch_expression.value.expression);
+        });
+        Ok(())
+    }
+
+    fn visit_switch_case(&mut self, switch_case: &'a Node<SwitchCase>) -> Result<(), Box<dyn IError>> {
+        self.write_line(String::from("SwitchCase"), switch_case.position);
+        self.nested(|printer| {
+            printer.visit_expression(&switch_case.value.condition);
+            printer.visit_block(&switch_case.value.block);
+        });
+        Ok(())
+    }
+
+    fn visit_literal(&mut self, literal: &'a Literal) -> Result<(), Box<dyn IError>> {
+        self.write_line(format!("Literal {:?}", literal), self.position);
+        Ok(())
+    }
+
+    fn visit_variable(&mut self, variable: &'a String) -> Result<(), Box<dyn IError>> {
+        self.write_line(format!("Variable '{}'", variable), self.position);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use crate::{
+        lazy_stream_reader::LazyStreamReader,
+        lexer::{Lexer, LexerOptions},
+        parser::{IParser, Parser},
+    };
+
+    use super::*;
+
+    fn on_warning(_err: Box<dyn IError>) {}
+
+    fn parse(text: &str) -> Program {
+        let options = LexerOptions {
+            max_comment_length: 100,
+            max_identifier_length: 100,
+            max_string_length: 1000,
+            strict_strings: false,
+        };
+        let reader = LazyStreamReader::new(BufReader::new(text.as_bytes()));
+        let lexer = Lexer::new(reader, options, on_warning);
+        let mut parser = Parser::new(lexer);
+        parser.parse().unwrap()
+    }
+
+    #[test]
+    fn prints_program() {
+        let program = parse("fn add(i64 a, &i64 b): i64 {\n  return a + b;\n}\ni64 x = add(1, &x) as i64;\n");
+        let output = AstPrinter::new(&program).print();
+
+        let expected = "\
+Program
+  FunctionDeclaration 'add' -> i64 (line: 1, column: 1)
+    Parameter i64 'a' (line: 1, column: 8)
+    Parameter &i64 'b' (line: 1, column: 15)
+    Block (line: 1, column: 28)
+      Return (line: 2, column: 3)
+        Addition (line: 2, column: 12)
+          Variable 'a' (line: 2, column: 10)
+          Variable 'b' (line: 2, column: 14)
+  Declaration i64 'x' (line: 4, column: 1)
+    Casting (line: 4, column: 9)
+      FunctionCall 'add' (line: 4, column: 9)
+        Argument Value (line: 4, column: 13)
+          Literal I64(1) (line: 4, column: 13)
+        Argument Reference (line: 4, column: 17)
+          Variable 'x' (line: 4, column: 17)
+      Type i64 (line: 4, column: 23)
+";
+        assert_eq!(output, expected);
+    }
+}
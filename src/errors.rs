@@ -1,4 +1,4 @@
-use crate::lazy_stream_reader::Position;
+use crate::lazy_stream_reader::{code_snippet, Position};
 use std::fmt::Debug;
 
 pub trait IError: Debug {
@@ -49,6 +49,7 @@ define_error!(ComputationError);
 define_error!(ScopeManagerError);
 define_error!(StackOverflowError);
 define_error!(StdFunctionError);
+define_error!(FileError);
 
 pub struct ErrorsManager;
 
@@ -57,4 +58,9 @@ impl ErrorsManager {
         error.set_message(format!("{}\nAt {:?}.", error.message(), position));
         error
     }
+
+    pub fn append_code_snippet(mut error: Box<dyn IError>, line: &str, position: Position) -> Box<dyn IError> {
+        error.set_message(format!("{}\n{}", error.message(), code_snippet(line, position.column)));
+        error
+    }
 }
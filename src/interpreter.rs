@@ -1,26 +1,58 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    io::{self, BufRead, BufReader, Write},
+    rc::Rc,
+};
 
 use crate::{
     alu::ALU,
     ast::{
-        Argument, Block, Expression, FunctionDeclaration, Literal, Node, Parameter, PassedBy, Program, Statement, SwitchCase, SwitchExpression, Type,
+        Argument, Block, Expression, FunctionDeclaration, Literal, Node, Parameter, PassedBy, Program, Statement, SwitchCase, SwitchExpression,
+        SwitchValueCase, Type,
     },
     errors::{ComputationError, ErrorSeverity, ErrorsManager, IError, InterpreterError},
     lazy_stream_reader::Position,
+    purity::find_pure_functions,
     stack::Stack,
     std_functions::StdFunction,
     value::Value,
     visitor::Visitor,
 };
 
+// array holding the element and the element's index
+type ElementLocation = (Rc<RefCell<Vec<Value>>>, usize);
+
+// state kept between runs of the interpreter, e.g. between the lines of a REPL session
+pub struct Environment<'a> {
+    pub stack: Stack<'a>,
+    pub writer: Box<dyn Write>,
+    pub reader: Box<dyn BufRead>,
+}
+
 pub struct Interpreter<'a> {
     program: &'a Program,
     stack: Stack<'a>,
     last_result: Option<Value>,
     is_breaking: bool,
     is_returning: bool,
+    is_case_matched: bool,
+    is_falling_through: bool,
     position: Position,
     last_arguments: Vec<Rc<RefCell<Value>>>,
+    // parameter index of every one of 'last_arguments', positional when empty
+    last_argument_binding: Vec<usize>,
+    writer: Box<dyn Write>,
+    reader: Box<dyn BufRead>,
+    echo_last_result: bool,
+    pure_functions: Option<HashSet<String>>,
+    call_memo: HashMap<String, Option<Value>>,
+    function_calls: usize,
+    source_lines: Vec<String>,
+    entry_point: Option<String>,
+    exit_code: Option<i64>,
+    // value left by the final top-level statement, e.g. a call whose result was not used
+    final_result: Option<Value>,
 }
 
 impl<'a> Interpreter<'a> {
@@ -31,17 +63,158 @@ impl<'a> Interpreter<'a> {
             last_result: None,
             is_breaking: false,
             is_returning: false,
+            is_case_matched: false,
+            is_falling_through: false,
             position: Position {
                 line: 0,
                 column: 0,
                 offset: 0,
             },
             last_arguments: vec![],
+            last_argument_binding: vec![],
+            writer: Box::new(io::stdout()),
+            reader: Box::new(BufReader::new(io::stdin())),
+            echo_last_result: false,
+            pure_functions: None,
+            call_memo: HashMap::new(),
+            function_calls: 0,
+            source_lines: vec![],
+            entry_point: None,
+            exit_code: None,
+            final_result: None,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_writer(mut self, writer: Box<dyn Write>) -> Self {
+        self.writer = writer;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.stack = Stack::with_max_depth(max_call_depth);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_input(mut self, reader: Box<dyn BufRead>) -> Self {
+        self.reader = reader;
+        self
+    }
+
+    pub fn with_environment(mut self, environment: Environment<'a>) -> Self {
+        self.stack = environment.stack;
+        self.writer = environment.writer;
+        self.reader = environment.reader;
+        self
+    }
+
+    // gives the variables and the streams back, so the next run can resume with them
+    pub fn into_environment(self) -> Environment<'a> {
+        Environment {
+            stack: self.stack,
+            writer: self.writer,
+            reader: self.reader,
         }
     }
 
+    pub fn with_result_echo(mut self, echo_last_result: bool) -> Self {
+        self.echo_last_result = echo_last_result;
+        self
+    }
+
+    pub fn with_call_memoization(mut self, memoize_calls: bool) -> Self {
+        self.pure_functions = match memoize_calls {
+            true => Some(find_pure_functions(self.program)),
+            false => None,
+        };
+        self
+    }
+
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source_lines = source.lines().map(String::from).collect();
+        self
+    }
+
+    // the function run after the top-level statements, e.g. 'main'
+    pub fn with_entry_point(mut self, entry_point: Option<&str>) -> Self {
+        self.entry_point = entry_point.map(String::from);
+        self
+    }
+
     pub fn interpret(&mut self) -> Result<(), Box<dyn IError>> {
-        self.visit_program(self.program)
+        self.visit_program(self.program)?;
+        // taken before the entry point runs, as it reuses 'last_result'
+        self.final_result = self.last_result.take();
+        self.run_entry_point()?;
+
+        // interactive mode - show the value left by the final statement,
+        // unless it is a call to 'print' or 'println' which already wrote it
+        let echoes_call = match self.program.statements.last().map(|statement| &statement.value) {
+            Some(Statement::FunctionCall { identifier, .. }) => !["print", "println"].contains(&identifier.value.as_str()),
+            _ => false,
+        };
+        if self.echo_last_result && echoes_call {
+            if let Some(value) = &self.final_result {
+                let _ = writeln!(self.writer, "{}", value);
+            }
+        }
+        Ok(())
+    }
+
+    // the snippet is only shown when the source code was given
+    fn append_position(&self, error: Box<dyn IError>) -> Box<dyn IError> {
+        let error = ErrorsManager::append_position(error, self.position);
+        match (self.position.line as usize)
+            .checked_sub(1)
+            .and_then(|index| self.source_lines.get(index))
+        {
+            Some(line) => ErrorsManager::append_code_snippet(error, line, self.position),
+            None => error,
+        }
+    }
+
+    fn run_entry_point(&mut self) -> Result<(), Box<dyn IError>> {
+        let name = match &self.entry_point {
+            Some(name) => name.clone(),
+            None => return Ok(()),
+        };
+        let function = match self.program.functions.get(&name) {
+            Some(function) => &function.value,
+            None => {
+                return Err(Box::new(InterpreterError::new(
+                    ErrorSeverity::HIGH,
+                    format!("Entry point function '{}' not found.", name),
+                )))
+            }
+        };
+
+        self.position = function.identifier.position;
+        let return_type = &function.return_type.value;
+        if !matches!(return_type, Type::Void | Type::I64) {
+            let error = Box::new(InterpreterError::new(
+                ErrorSeverity::HIGH,
+                format!("Entry point '{}' has to return 'void' or 'i64', but returns '{}'.", name, return_type),
+            ));
+            return Err(self.append_position(error));
+        }
+        if function.parameters.iter().any(|parameter| parameter.value.default_value.is_none()) {
+            let error = Box::new(InterpreterError::new(
+                ErrorSeverity::HIGH,
+                format!("Entry point '{}' cannot have parameters without default values.", name),
+            ));
+            return Err(self.append_position(error));
+        }
+
+        self.last_arguments = vec![];
+        self.last_argument_binding = vec![];
+        self.last_result = None;
+        self.execute_function(function)?;
+        if let Some(Value::I64(code)) = self.last_result.take() {
+            self.exit_code = Some(code);
+        }
+        Ok(())
     }
 
     fn read_last_result(&mut self) -> Result<Value, Box<dyn IError>> {
@@ -50,20 +223,65 @@ impl<'a> Interpreter<'a> {
                 ErrorSeverity::HIGH,
                 String::from("No value produced where it is needed."),
             ));
-            ErrorsManager::append_position(error, self.position)
+            self.append_position(error)
         })
     }
 
+    fn evaluate_switch_value(
+        &mut self,
+        expressions: &'a [Node<SwitchExpression>],
+        cases: &'a [Node<SwitchValueCase>],
+        default: Option<&'a Node<Expression>>,
+        position: Position,
+    ) -> Result<(), Box<dyn IError>> {
+        // aliases are only visible in the cases
+        self.stack.push_scope();
+        for expression in expressions {
+            self.visit_switch_expression(expression)?;
+        }
+        for case in cases {
+            self.position = case.position;
+            self.visit_expression(&case.value.condition)?;
+            let computed_value = self.read_last_result()?;
+            let boolean_value = computed_value
+                .try_into_bool()
+                .map_err(|_| self.condition_error(computed_value, "switch case"))?;
+
+            // only the first matching case is evaluated
+            if boolean_value {
+                self.visit_expression(&case.value.value)?;
+                self.stack.pop_scope();
+                return Ok(());
+            }
+        }
+        if let Some(default) = default {
+            self.visit_expression(default)?;
+            self.stack.pop_scope();
+            return Ok(());
+        }
+        self.stack.pop_scope();
+
+        self.position = position;
+        let error = Box::new(InterpreterError::new(
+            ErrorSeverity::HIGH,
+            String::from("None of the cases of switch expression matched."),
+        ));
+        Err(self.append_position(error))
+    }
+
     fn evaluate_binary_op<F>(&mut self, lhs: &'a Box<Node<Expression>>, rhs: &'a Box<Node<Expression>>, op: F) -> Result<(), Box<dyn IError>>
     where
         F: Fn(Value, Value) -> Result<Value, ComputationError>,
     {
+        // visiting the operands moves the position, errors should point at the operator
+        let position = self.position;
         self.visit_expression(lhs)?;
         let left_value = self.read_last_result()?;
         self.visit_expression(rhs)?;
         let right_value = self.read_last_result()?;
 
-        let value = op(left_value, right_value).map_err(|err| ErrorsManager::append_position(Box::new(err), self.position))?;
+        self.position = position;
+        let value = op(left_value, right_value).map_err(|err| self.append_position(Box::new(err)))?;
         self.last_result = Some(value);
         Ok(())
     }
@@ -72,9 +290,11 @@ impl<'a> Interpreter<'a> {
     where
         F: Fn(Value) -> Result<Value, ComputationError>,
     {
+        let position = self.position;
         self.visit_expression(value)?;
         let computed_value = self.read_last_result()?;
-        let value = op(computed_value).map_err(|err| ErrorsManager::append_position(Box::new(err), self.position))?;
+        self.position = position;
+        let value = op(computed_value).map_err(|err| self.append_position(Box::new(err)))?;
         self.last_result = Some(value);
         Ok(())
     }
@@ -83,13 +303,14 @@ impl<'a> Interpreter<'a> {
 impl<'a> Visitor<'a> for Interpreter<'a> {
     fn visit_program(&mut self, program: &'a Program) -> Result<(), Box<dyn IError>> {
         for statement in &program.statements {
+            self.last_result = None;
             self.visit_statement(&statement)?;
             if self.is_breaking {
                 let error = Box::new(InterpreterError::new(
                     ErrorSeverity::HIGH,
                     String::from("Break called outside 'for' or 'switch'."),
                 ));
-                return Err(ErrorsManager::append_position(error, self.position));
+                return Err(self.append_position(error));
             }
 
             if self.is_returning {
@@ -97,7 +318,7 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
                     ErrorSeverity::HIGH,
                     String::from("Return called outside a function."),
                 ));
-                return Err(ErrorsManager::append_position(error, self.position));
+                return Err(self.append_position(error));
             }
         }
         Ok(())
@@ -109,16 +330,40 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
             Expression::Casting { value, to_type } => {
                 self.visit_expression(&value)?;
                 let computed_value = self.read_last_result()?;
-                let value =
-                    ALU::cast_to_type(computed_value, to_type.value).map_err(|err| ErrorsManager::append_position(Box::new(err), self.position))?;
+                let value = ALU::cast_to_type(computed_value, &to_type.value).map_err(|err| self.append_position(Box::new(err)))?;
                 self.last_result = Some(value);
             }
+            Expression::Ternary {
+                condition,
+                if_true,
+                if_false,
+            } => {
+                self.visit_expression(condition)?;
+                let computed_condition = self.read_last_result()?;
+                let boolean_value = computed_condition
+                    .try_into_bool()
+                    .map_err(|_| self.condition_error(computed_condition, "ternary expression"))?;
+
+                match boolean_value {
+                    true => self.visit_expression(if_true)?,
+                    false => self.visit_expression(if_false)?,
+                }
+            }
+            Expression::Switch { expressions, cases, default } => {
+                self.evaluate_switch_value(expressions, cases, default.as_deref(), expression.position)?
+            }
             Expression::BooleanNegation(value) => self.evaluate_unary_op(value, ALU::boolean_negate)?,
             Expression::ArithmeticNegation(value) => self.evaluate_unary_op(value, ALU::arithmetic_negate)?,
+            Expression::UnaryPlus(value) => self.evaluate_unary_op(value, ALU::unary_plus)?,
             Expression::Addition(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::add)?,
             Expression::Subtraction(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::subtract)?,
             Expression::Multiplication(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::multiplication)?,
             Expression::Division(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::division)?,
+            Expression::BitwiseOr(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::bit_or)?,
+            Expression::BitwiseXor(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::bit_xor)?,
+            Expression::BitwiseAnd(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::bit_and)?,
+            Expression::ShiftLeft(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::shift_left)?,
+            Expression::ShiftRight(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::shift_right)?,
             Expression::Alternative(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::alternative)?,
             Expression::Concatenation(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::concatenation)?,
             Expression::Greater(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::greater)?,
@@ -127,6 +372,51 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
             Expression::LessEqual(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::less_or_equal)?,
             Expression::Equal(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::equal)?,
             Expression::NotEqual(lhs, rhs) => self.evaluate_binary_op(lhs, rhs, ALU::not_equal)?,
+            Expression::Parenthesized(value) => self.visit_expression(value)?,
+            Expression::Tuple(values) => {
+                let mut computed_values = vec![];
+                for value in values {
+                    self.visit_expression(value)?;
+                    computed_values.push(self.read_last_result()?);
+                }
+                self.last_result = Some(Value::Tuple(computed_values));
+            }
+            Expression::Array(values) => {
+                let mut computed_values: Vec<Value> = vec![];
+                for value in values {
+                    self.visit_expression(value)?;
+                    let computed_value = self.read_last_result()?;
+                    if let Some(first) = computed_values.first() {
+                        if first.to_type() != computed_value.to_type() {
+                            let error = Box::new(InterpreterError::new(
+                                ErrorSeverity::HIGH,
+                                format!(
+                                    "Array elements have to be of the same type - got '{}' and '{}'.",
+                                    first.to_type(),
+                                    computed_value.to_type()
+                                ),
+                            ));
+                            return Err(self.append_position(error));
+                        }
+                    }
+                    computed_values.push(computed_value);
+                }
+                // literals can't be empty, so the first element tells the type
+                let element_type = computed_values.first().map_or(Type::Void, Value::to_type);
+                self.last_result = Some(Value::Array(element_type, Rc::new(RefCell::new(computed_values))));
+            }
+            Expression::Index { value, index } => {
+                self.visit_expression(value)?;
+                let array = self.read_last_result()?;
+                self.visit_expression(index)?;
+                let index = self.read_last_result()?;
+
+                // report the indexing, not the last visited operand
+                self.position = expression.position;
+                let (values, i) = self.locate_element(&array, &index)?;
+                let element = values.borrow()[i].clone();
+                self.last_result = Some(element);
+            }
             Expression::Literal(literal) => self.visit_literal(literal)?,
             Expression::Variable(variable) => self.visit_variable(variable)?,
             Expression::FunctionCall { identifier, arguments } => self.call_function(identifier, arguments)?,
@@ -136,11 +426,40 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
 
     fn visit_statement(&mut self, statement: &'a Node<Statement>) -> Result<(), Box<dyn IError>> {
         self.position = statement.position;
+        self.call_memo.clear();
         match &statement.value {
             Statement::FunctionCall { identifier, arguments } => self.call_function(identifier, arguments)?,
-            Statement::Declaration { var_type, identifier, value } => {
+            Statement::Declaration {
+                var_type,
+                identifier,
+                value,
+                is_const,
+            } => {
                 self.visit_type(&var_type)?;
 
+                // checked up front, so the void function is not run at all
+                if let Some(Node {
+                    value: Expression::FunctionCall { identifier: function, .. },
+                    ..
+                }) = value
+                {
+                    let is_void = self
+                        .program
+                        .functions
+                        .get(&function.value)
+                        .is_some_and(|declaration| declaration.value.return_type.value == Type::Void);
+                    if is_void {
+                        let error = Box::new(InterpreterError::new(
+                            ErrorSeverity::HIGH,
+                            format!(
+                                "Cannot assign result of void function '{}' to variable '{}'.",
+                                function.value, identifier.value
+                            ),
+                        ));
+                        return Err(self.append_position(error));
+                    }
+                }
+
                 let computed_value = match value {
                     Some(val) => {
                         self.visit_expression(&val)?;
@@ -149,31 +468,39 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
                                 ErrorSeverity::HIGH,
                                 format!("Cannot declare variable '{}' with no value.", identifier.value),
                             ));
-                            ErrorsManager::append_position(error, self.position)
+                            self.append_position(error)
                         })?
                     }
-                    None => Value::default_value(var_type.value).map_err(|err| Box::new(err) as Box<dyn IError>)?,
+                    None => Value::default_value(&var_type.value).map_err(|err| Box::new(err) as Box<dyn IError>)?,
                 };
 
-                match (var_type.value, &computed_value) {
-                    (Type::I64, Value::I64(_)) | (Type::F64, Value::F64(_)) | (Type::Str, Value::String(_)) | (Type::Bool, Value::Bool(_)) => {}
+                match (&var_type.value, &computed_value) {
+                    (Type::I64, Value::I64(_))
+                    | (Type::F64, Value::F64(_))
+                    | (Type::Str, Value::String(_))
+                    | (Type::Char, Value::Char(_))
+                    | (Type::Bool, Value::Bool(_)) => {}
+                    (declared_type @ Type::Array(_), computed_value) if computed_value.to_type() == *declared_type => {}
                     (declared_type, computed_type) => {
                         let error = Box::new(InterpreterError::new(
                             ErrorSeverity::HIGH,
                             format!(
-                                "Cannot assign value of type '{:?}' to variable '{}' of type '{:?}'.",
+                                "Cannot assign value of type '{}' to variable '{}' of type '{}'.",
                                 computed_type.to_type(),
                                 identifier.value,
                                 declared_type
                             ),
                         ));
-                        return Err(ErrorsManager::append_position(error, self.position));
+                        return Err(self.append_position(error));
                     }
                 }
 
-                self.stack
-                    .declare_variable(identifier.value.as_str(), Rc::new(RefCell::new(computed_value)))
-                    .map_err(|err| ErrorsManager::append_position(Box::new(err), self.position))?;
+                let value = Rc::new(RefCell::new(computed_value.deep_copy()));
+                match is_const {
+                    true => self.stack.declare_constant(identifier.value.as_str(), value),
+                    false => self.stack.declare_variable(identifier.value.as_str(), value),
+                }
+                .map_err(|err| self.append_position(Box::new(err)))?;
             }
             Statement::Assignment { identifier, value } => {
                 self.visit_expression(&value)?;
@@ -182,12 +509,69 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
                         ErrorSeverity::HIGH,
                         format!("Cannot assign no value to variable '{}'.", identifier.value),
                     ));
-                    ErrorsManager::append_position(error, self.position)
+                    self.append_position(error)
                 })?;
 
                 self.stack
-                    .assign_variable(identifier.value.as_str(), Rc::new(RefCell::new(value)))
-                    .map_err(|err| ErrorsManager::append_position(Box::new(err), self.position))?;
+                    .assign_variable(identifier.value.as_str(), Rc::new(RefCell::new(value.deep_copy())))
+                    .map_err(|err| self.append_position(Box::new(err)))?;
+            }
+            Statement::IndexAssignment { identifier, indices, value } => {
+                self.visit_expression(value)?;
+                let value = self.read_last_result().map_err(|_| {
+                    let error = Box::new(InterpreterError::new(
+                        ErrorSeverity::HIGH,
+                        format!("Cannot assign no value to variable '{}'.", identifier.value),
+                    ));
+                    self.append_position(error)
+                })?;
+                let mut computed_indices = vec![];
+                for index in indices {
+                    self.visit_expression(index)?;
+                    computed_indices.push(self.read_last_result()?);
+                }
+
+                self.position = statement.position;
+                if self.stack.is_constant(identifier.value.as_str()) {
+                    let error = Box::new(InterpreterError::new(
+                        ErrorSeverity::HIGH,
+                        format!("Cannot assign to constant '{}'.", identifier.value),
+                    ));
+                    return Err(self.append_position(error));
+                }
+                if self.stack.is_global(identifier.value.as_str()) {
+                    let error = Box::new(InterpreterError::new(
+                        ErrorSeverity::HIGH,
+                        format!("Cannot modify global variable '{}' inside a function.", identifier.value),
+                    ));
+                    return Err(self.append_position(error));
+                }
+                let mut element = match self.stack.get_variable(identifier.value.as_str()) {
+                    Ok(variable) => variable.borrow().clone(),
+                    Err(err) => return Err(self.append_position(Box::new(err))),
+                };
+
+                // the parser guarantees at least one index
+                let (last_index, outer_indices) = computed_indices.split_last().unwrap();
+                for index in outer_indices {
+                    let (values, i) = self.locate_element(&element, index)?;
+                    let inner = values.borrow()[i].clone();
+                    element = inner;
+                }
+                let (values, i) = self.locate_element(&element, last_index)?;
+                let element_type = values.borrow()[i].to_type();
+                if value.to_type() != element_type {
+                    let error = Box::new(InterpreterError::new(
+                        ErrorSeverity::HIGH,
+                        format!(
+                            "Cannot assign value of type '{}' to element of array of type '{}'.",
+                            value.to_type(),
+                            Type::Array(Box::new(element_type))
+                        ),
+                    ));
+                    return Err(self.append_position(error));
+                }
+                values.borrow_mut()[i] = value.deep_copy();
             }
             Statement::Conditional {
                 condition,
@@ -247,13 +631,16 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
                 }
                 self.stack.pop_scope();
             }
-            Statement::Switch { expressions, cases } => {
+            Statement::Switch { expressions, cases, default } => {
                 self.stack.push_scope();
                 for expr in expressions {
                     self.visit_switch_expression(&expr)?;
                 }
+                let mut any_matched = false;
                 for case in cases {
+                    self.is_falling_through = false;
                     self.visit_switch_case(&case)?;
+                    any_matched |= self.is_case_matched;
                     if self.is_returning {
                         break;
                     }
@@ -262,6 +649,16 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
                         self.is_breaking = false;
                         break;
                     }
+
+                    // only the first matching case runs, unless it ends with 'fallthrough'
+                    if self.is_case_matched && !self.is_falling_through {
+                        break;
+                    }
+                }
+                self.is_falling_through = false;
+                if let Some(default) = default.as_ref().filter(|_| !any_matched) {
+                    self.visit_block(default)?;
+                    self.is_breaking = false;
                 }
                 self.stack.pop_scope();
             }
@@ -278,6 +675,34 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
             Statement::Break => {
                 self.is_breaking = true;
             }
+            Statement::Fallthrough => {
+                self.is_falling_through = true;
+            }
+            Statement::Destructuring { targets, value } => {
+                self.visit_expression(value)?;
+                let computed_value = self.read_last_result()?;
+
+                let expected_type = Type::Tuple(targets.iter().map(|(var_type, _)| var_type.value.clone()).collect());
+                if computed_value.to_type() != expected_type {
+                    let error = Box::new(InterpreterError::new(
+                        ErrorSeverity::HIGH,
+                        format!(
+                            "Cannot destructure value of type '{}' into '{}'.",
+                            computed_value.to_type(),
+                            expected_type
+                        ),
+                    ));
+                    return Err(self.append_position(error));
+                }
+
+                if let Value::Tuple(values) = computed_value {
+                    for ((_, identifier), value) in targets.iter().zip(values) {
+                        self.stack
+                            .declare_variable(identifier.value.as_str(), Rc::new(RefCell::new(value.deep_copy())))
+                            .map_err(|err| self.append_position(Box::new(err)))?;
+                    }
+                }
+            }
         }
         Ok(())
     }
@@ -306,6 +731,7 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
     }
 
     fn visit_switch_case(&mut self, switch_case: &'a Node<SwitchCase>) -> Result<(), Box<dyn IError>> {
+        self.position = switch_case.position;
         self.visit_expression(&switch_case.value.condition)?;
         let computed_value = self.read_last_result()?;
         let boolean_value = computed_value
@@ -313,8 +739,10 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
             .map_err(|_| self.condition_error(computed_value, "switch case"))?;
 
         if boolean_value {
+            self.position = switch_case.position;
             self.visit_block(&switch_case.value.block)?;
         }
+        self.is_case_matched = boolean_value;
         Ok(())
     }
 
@@ -323,8 +751,8 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
             self.visit_expression(&switch_expression.value.expression)?;
             let computed_value = self.read_last_result()?;
             self.stack
-                .declare_variable(alias.value.as_str(), Rc::new(RefCell::new(computed_value)))
-                .map_err(|err| ErrorsManager::append_position(Box::new(err), self.position))?;
+                .declare_variable(alias.value.as_str(), Rc::new(RefCell::new(computed_value.deep_copy())))
+                .map_err(|err| self.append_position(Box::new(err)))?;
         }
         Ok(())
     }
@@ -339,6 +767,7 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
             Literal::F64(f64) => Value::F64(*f64),
             Literal::I64(i64) => Value::I64(*i64),
             Literal::String(str) => Value::String(str.to_string()),
+            Literal::Char(char) => Value::Char(*char),
             Literal::False => Value::Bool(false),
             Literal::True => Value::Bool(true),
         };
@@ -349,50 +778,110 @@ impl<'a> Visitor<'a> for Interpreter<'a> {
 
     fn visit_variable(&mut self, variable: &'a String) -> Result<(), Box<dyn IError>> {
         // read value of variable
-        let value = self
-            .stack
-            .get_variable(variable.as_str())
-            .map_err(|err| Box::new(err) as Box<dyn IError>)?;
-        self.last_result = Some(value.borrow().to_owned());
+        let value = match self.stack.get_variable(variable.as_str()) {
+            Ok(value) => value.borrow().to_owned(),
+            Err(err) => return Err(self.append_position(Box::new(err))),
+        };
+        self.last_result = Some(value);
         Ok(())
     }
 }
 
 impl<'a> Interpreter<'a> {
+    // value returned by an 'i64' entry point, the caller decides how to exit with it
+    pub fn exit_code(&self) -> Option<i64> {
+        self.exit_code
+    }
+
+    pub fn final_result(&self) -> Option<&Value> {
+        self.final_result.as_ref()
+    }
+
+    #[allow(dead_code)]
+    pub fn function_calls(&self) -> usize {
+        // number of executed user function bodies
+        self.function_calls
+    }
+
     #[allow(dead_code)]
-    pub fn stack(&mut self) -> Stack {
+    pub fn stack(&mut self) -> Stack<'a> {
         // only for accept tests
         self.stack.clone()
     }
 
+    // shared by reading and assigning array elements
+    fn locate_element(&self, array: &Value, index: &Value) -> Result<ElementLocation, Box<dyn IError>> {
+        let message = match (array, index) {
+            (Value::Array(_, values), Value::I64(i)) => match usize::try_from(*i) {
+                Ok(i) if i < values.borrow().len() => return Ok((values.clone(), i)),
+                _ => format!("Index {} out of bounds for array of length {}.", i, values.borrow().len()),
+            },
+            (Value::Array(..), index) => format!("Array index has to be of type 'i64' - got '{}'.", index.to_type()),
+            (value, _) => format!("Cannot index value of type '{}'.", value.to_type()),
+        };
+        Err(self.append_position(Box::new(InterpreterError::new(ErrorSeverity::HIGH, message))))
+    }
+
     fn condition_error(&self, value: Value, place: &'a str) -> Box<dyn IError> {
         let error = Box::new(InterpreterError::new(
             ErrorSeverity::HIGH,
             format!(
-                "Condition in '{}' has to evaluate to type '{:?}' - got '{:?}'.",
+                "Condition in '{}' has to evaluate to type '{}' - got '{}'.",
                 place,
                 Type::Bool,
                 value.to_type(),
             ),
         ));
-        ErrorsManager::append_position(error, self.position)
+        self.append_position(error)
     }
 
-    fn execute_std_function(std_function: &StdFunction, arguments: &Vec<Rc<RefCell<Value>>>) -> Result<Option<Value>, Box<dyn IError>> {
-        (std_function.execute)(arguments).map_err(|err| Box::new(err) as Box<dyn IError>)
+    fn execute_std_function(
+        std_function: &StdFunction,
+        arguments: &Vec<Rc<RefCell<Value>>>,
+        writer: &mut dyn Write,
+        reader: &mut dyn BufRead,
+    ) -> Result<Option<Value>, Box<dyn IError>> {
+        (std_function.execute)(arguments, writer, reader).map_err(|err| Box::new(err) as Box<dyn IError>)
     }
 
     fn call_function(&mut self, identifier: &Node<String>, arguments: &'a Vec<Box<Node<Argument>>>) -> Result<(), Box<dyn IError>> {
         let name = identifier.value.as_str();
+        if !self.program.functions.contains_key(name) && !self.program.std_functions.contains_key(name) {
+            self.position = identifier.position;
+            let error = Box::new(InterpreterError::new(ErrorSeverity::HIGH, format!("'{}' is not a function.", name)));
+            return Err(self.append_position(error));
+        }
 
         let mut args: Vec<Rc<RefCell<Value>>> = vec![];
         for arg in arguments {
             self.visit_expression(&arg.value.value)?;
             let value = self.read_last_result()?;
             match arg.value.passed_by {
-                PassedBy::Value => args.push(Rc::new(RefCell::new(value))),
+                // std functions like 'push' modify the array given to them
+                PassedBy::Value if self.program.std_functions.contains_key(name) => args.push(Rc::new(RefCell::new(value))),
+                PassedBy::Value => args.push(Rc::new(RefCell::new(value.deep_copy()))),
                 PassedBy::Reference => {
+                    if let Some(message) = arg.value.invalid_reference() {
+                        self.position = arg.position;
+                        let error = Box::new(InterpreterError::new(ErrorSeverity::HIGH, String::from(message)));
+                        return Err(self.append_position(error));
+                    }
                     if let Expression::Variable(var_name) = &arg.value.value.value {
+                        // a reference would allow the function to modify the constant
+                        if self.stack.is_constant(var_name.as_str()) {
+                            let error = Box::new(InterpreterError::new(
+                                ErrorSeverity::HIGH,
+                                format!("Cannot pass constant '{}' by reference.", var_name),
+                            ));
+                            return Err(self.append_position(error));
+                        }
+                        if self.stack.is_global(var_name.as_str()) {
+                            let error = Box::new(InterpreterError::new(
+                                ErrorSeverity::HIGH,
+                                format!("Cannot pass global variable '{}' by reference inside a function.", var_name),
+                            ));
+                            return Err(self.append_position(error));
+                        }
                         let var_ref = self
                             .stack
                             .get_variable(var_name.as_str())
@@ -405,16 +894,45 @@ impl<'a> Interpreter<'a> {
 
         self.last_arguments = args;
 
+        if let Some(argument) = arguments.iter().find(|argument| argument.value.name.is_some()) {
+            let binding = match self.program.functions.get(name) {
+                Some(function_declaration) => function_declaration.value.bind_arguments(arguments),
+                None => Err(format!("Std function '{}' doesn't accept named arguments.", name)),
+            };
+            match binding {
+                Ok(binding) => self.last_argument_binding = binding,
+                Err(message) => {
+                    self.position = argument.position;
+                    return Err(self.append_position(Box::new(InterpreterError::new(ErrorSeverity::HIGH, message))));
+                }
+            }
+        }
+
         if let Some(std_function) = self.program.std_functions.get(name) {
-            if let Some(return_value) =
-                Self::execute_std_function(std_function, &self.last_arguments).map_err(|err| ErrorsManager::append_position(err, self.position))?
+            if let Some(return_value) = Self::execute_std_function(std_function, &self.last_arguments, self.writer.as_mut(), self.reader.as_mut())
+                .map_err(|err| self.append_position(err))?
             {
                 self.last_result = Some(return_value);
             }
         }
 
         if let Some(function_declaration) = self.program.functions.get(name) {
-            self.execute_function(&(*function_declaration).value)?;
+            let is_pure = self.pure_functions.as_ref().is_some_and(|pure_functions| pure_functions.contains(name));
+            // only built for pure functions, formatting the arguments would slow down every other call
+            let memo_key = is_pure.then(|| format!("{}{:?}{:?}", name, self.last_argument_binding, self.last_arguments));
+
+            if let Some(memoized) = memo_key.as_ref().and_then(|memo_key| self.call_memo.get(memo_key)) {
+                self.last_result = memoized.clone();
+            } else {
+                // statements of the function body must not clear the memo of the calling statement
+                let call_memo = std::mem::take(&mut self.call_memo);
+                self.execute_function(&(*function_declaration).value)?;
+                self.call_memo = call_memo;
+
+                if let Some(memo_key) = memo_key {
+                    self.call_memo.insert(memo_key, self.last_result.clone());
+                }
+            }
         }
 
         if self.is_returning {
@@ -422,39 +940,94 @@ impl<'a> Interpreter<'a> {
         }
 
         self.last_arguments = vec![];
+        self.last_argument_binding = vec![];
 
         Ok(())
     }
 
     fn execute_function(&mut self, function_declaration: &'a FunctionDeclaration) -> Result<(), Box<dyn IError>> {
+        self.function_calls += 1;
         let name = function_declaration.identifier.value.as_str();
         let statements = &function_declaration.block.value.0;
-        self.stack.push_stack_frame().map_err(|err| Box::new(err) as Box<dyn IError>)?;
+        if self.stack.push_stack_frame().is_err() {
+            let error = Box::new(InterpreterError::new(
+                ErrorSeverity::HIGH,
+                format!("Maximum recursion depth ({}) exceeded in function '{}'.", self.stack.max_depth(), name),
+            ));
+            return Err(self.append_position(error));
+        }
+
+        // the semantic checker validates arity, but the interpreter can be run without it
+        let arguments = std::mem::take(&mut self.last_arguments);
+        let binding = std::mem::take(&mut self.last_argument_binding);
+        let parameters = &function_declaration.parameters;
+        let required = parameters.iter().filter(|p| p.value.default_value.is_none()).count();
+        if arguments.len() < required || arguments.len() > parameters.len() {
+            let error = Box::new(InterpreterError::new(
+                ErrorSeverity::HIGH,
+                format!(
+                    "Function '{}' called with wrong number of arguments (expected {}, got {}).",
+                    name,
+                    parameters.len(),
+                    arguments.len()
+                ),
+            ));
+            return Err(self.append_position(error));
+        }
+
+        let mut bound_arguments: Vec<Option<Rc<RefCell<Value>>>> = vec![None; parameters.len()];
+        for (idx, argument) in arguments.into_iter().enumerate() {
+            bound_arguments[binding.get(idx).copied().unwrap_or(idx)] = Some(argument);
+        }
 
-        // args
-        for idx in 0..self.last_arguments.len() {
-            let desired_type = function_declaration.parameters.get(idx).unwrap().value.parameter_type.value;
-            let param_name = &function_declaration.parameters.get(idx).unwrap().value.identifier.value;
-            let value = self.last_arguments.get(idx).unwrap();
+        for (parameter, argument) in parameters.iter().zip(bound_arguments) {
+            // missing args are filled with default values, which can use the preceding parameters
+            let value = match (argument, &parameter.value.default_value) {
+                (Some(argument), _) => argument,
+                (None, Some(default_value)) => {
+                    self.visit_expression(default_value)?;
+                    Rc::new(RefCell::new(self.read_last_result()?))
+                }
+                // only possible with named arguments, positional ones were counted above
+                (None, None) => {
+                    let error = Box::new(InterpreterError::new(
+                        ErrorSeverity::HIGH,
+                        format!(
+                            "Missing argument for parameter '{}' of function '{}'.",
+                            parameter.value.identifier.value, name
+                        ),
+                    ));
+                    return Err(self.append_position(error));
+                }
+            };
+            let desired_type = &parameter.value.parameter_type.value;
+            let param_name = &parameter.value.identifier.value;
             match (desired_type, &*value.borrow()) {
-                (Type::Bool, Value::Bool(_)) | (Type::F64, Value::F64(_)) | (Type::I64, Value::I64(_)) | (Type::Str, Value::String(_)) => {}
+                (Type::Bool, Value::Bool(_))
+                | (Type::F64, Value::F64(_))
+                | (Type::I64, Value::I64(_))
+                | (Type::Str, Value::String(_))
+                | (Type::Char, Value::Char(_)) => {}
+                (des @ Type::Array(_), got) if got.to_type() == *des => {}
                 (des, got) => {
                     let error = Box::new(InterpreterError::new(
                         ErrorSeverity::HIGH,
-                        format!("Function '{}' expected '{:?}', but got '{:?}'.", name, des, got.to_type()),
+                        format!("Function '{}' expected '{}', but got '{}'.", name, des, got.to_type()),
                     ));
-                    return Err(ErrorsManager::append_position(error, self.position));
+                    return Err(self.append_position(error));
                 }
             }
             self.stack
-                .declare_variable(param_name.as_str(), Rc::clone(value))
-                .map_err(|err| ErrorsManager::append_position(Box::new(err), self.position))?;
+                .declare_variable(param_name.as_str(), value)
+                .map_err(|err| self.append_position(Box::new(err)))?;
         }
 
         // execute
+        let mut returned = false;
         for statement in statements {
             if self.is_returning {
                 self.is_returning = false;
+                returned = true;
                 break;
             }
 
@@ -465,17 +1038,24 @@ impl<'a> Interpreter<'a> {
                     ErrorSeverity::HIGH,
                     String::from("Break called outside 'for' or 'switch'."),
                 ));
-                return Err(ErrorsManager::append_position(error, self.position));
+                return Err(self.append_position(error));
             }
         }
 
+        // results of calls used as statements are not returned
+        if !returned && !self.is_returning {
+            self.last_result = None;
+        }
+
         // check return type
-        match (&self.last_result, function_declaration.return_type.value) {
+        match (&self.last_result, &function_declaration.return_type.value) {
             (None, Type::Void)
             | (Some(Value::I64(_)), Type::I64)
             | (Some(Value::F64(_)), Type::F64)
             | (Some(Value::String(_)), Type::Str)
+            | (Some(Value::Char(_)), Type::Char)
             | (Some(Value::Bool(_)), Type::Bool) => {}
+            (Some(value @ (Value::Tuple(_) | Value::Array(..))), exp) if value.to_type() == *exp => {}
             (res, exp) => {
                 let res_type = match res {
                     None => Type::Void,
@@ -483,12 +1063,9 @@ impl<'a> Interpreter<'a> {
                 };
                 let error = Box::new(InterpreterError::new(
                     ErrorSeverity::HIGH,
-                    format!(
-                        "Bad return type from function '{}'. Expected '{:?}', but got '{:?}'.",
-                        name, exp, res_type
-                    ),
+                    format!("Bad return type from function '{}'. Expected '{}', but got '{}'.", name, exp, res_type),
                 ));
-                return Err(ErrorsManager::append_position(error, self.position));
+                return Err(self.append_position(error));
             }
         }
 
@@ -502,7 +1079,7 @@ impl<'a> Interpreter<'a> {
 mod tests {
     use std::collections::HashMap;
 
-    use crate::ast::FunctionDeclaration;
+    use crate::{ast::FunctionDeclaration, std_functions::get_std_functions};
 
     use super::*;
 
@@ -581,6 +1158,27 @@ mod tests {
         assert_eq!(interpreter.last_result, exp);
     }
 
+    #[test]
+    fn interpret_unary_plus() {
+        let ast = test_node!(Expression::UnaryPlus(Box::new(test_node!(Expression::Literal(Literal::I64(5))))));
+
+        let exp = Some(Value::I64(5));
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+
+        let _ = interpreter.visit_expression(&ast);
+        assert_eq!(interpreter.last_result, exp);
+
+        let ast = test_node!(Expression::UnaryPlus(Box::new(test_node!(Expression::Literal(Literal::String(
+            String::from("x")
+        ))))));
+        assert_eq!(
+            interpreter.visit_expression(&ast).err().unwrap().message(),
+            create_error_message(String::from("Cannot perform unary plus on type 'str'."))
+        );
+    }
+
     #[test]
     fn interpret_addition() {
         let ast = test_node!(Expression::Addition(
@@ -645,6 +1243,58 @@ mod tests {
         assert_eq!(interpreter.last_result, exp);
     }
 
+    #[test]
+    fn interpret_ternary() {
+        // only the chosen branch is evaluated - the other one would fail on division by zero
+        let division_by_zero = test_node!(Expression::Division(
+            Box::new(test_node!(Expression::Literal(Literal::I64(1)))),
+            Box::new(test_node!(Expression::Literal(Literal::I64(0))))
+        ));
+        let data = [
+            (Literal::True, test_node!(Expression::Literal(Literal::I64(1))), division_by_zero.clone()),
+            (
+                Literal::False,
+                division_by_zero,
+                test_node!(Expression::Literal(Literal::String(String::from("a")))),
+            ),
+        ];
+
+        let expected = [Value::I64(1), Value::String(String::from("a"))];
+
+        for (idx, (condition, if_true, if_false)) in data.into_iter().enumerate() {
+            let ast = test_node!(Expression::Ternary {
+                condition: Box::new(test_node!(Expression::Literal(condition))),
+                if_true: Box::new(if_true),
+                if_false: Box::new(if_false),
+            });
+
+            let program = setup_program();
+            let mut interpreter = create_interpreter(&program);
+
+            interpreter.visit_expression(&ast).unwrap();
+            assert_eq!(interpreter.last_result, Some(expected[idx].clone()));
+        }
+    }
+
+    #[test]
+    fn interpret_ternary_non_bool_condition() {
+        let ast = test_node!(Expression::Ternary {
+            condition: Box::new(test_node!(Expression::Literal(Literal::I64(1)))),
+            if_true: Box::new(test_node!(Expression::Literal(Literal::I64(1)))),
+            if_false: Box::new(test_node!(Expression::Literal(Literal::I64(2)))),
+        });
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+
+        assert!(interpreter
+            .visit_expression(&ast)
+            .err()
+            .unwrap()
+            .message()
+            .starts_with("Condition in 'ternary expression' has to evaluate to type 'bool' - got 'i64'."));
+    }
+
     #[test]
     fn interpret_concatenation() {
         let ast = test_node!(Expression::Concatenation(
@@ -806,6 +1456,7 @@ mod tests {
             var_type: test_node!(Type::I64),
             identifier: test_node!(String::from("x")),
             value: Some(test_node!(Expression::Literal(Literal::I64(5)))),
+            is_const: false,
         });
 
         let program = setup_program();
@@ -822,6 +1473,7 @@ mod tests {
             var_type: test_node!(Type::I64),
             identifier: test_node!(String::from("x")),
             value: None,
+            is_const: false,
         });
 
         let program = setup_program();
@@ -838,6 +1490,7 @@ mod tests {
             var_type: test_node!(Type::I64),
             identifier: test_node!(String::from("x")),
             value: Some(test_node!(Expression::Literal(Literal::False))),
+            is_const: false,
         });
 
         let program = setup_program();
@@ -855,6 +1508,7 @@ mod tests {
             var_type: test_node!(Type::I64),
             identifier: test_node!(String::from("x")),
             value: None,
+            is_const: false,
         });
 
         let program = setup_program();
@@ -871,21 +1525,37 @@ mod tests {
 
     #[test]
     fn declare_with_none_value_fails() {
-        // i64 x = print("hello world");
+        // i64 x = push(a, 1);
         let ast = test_node!(Statement::Declaration {
             var_type: test_node!(Type::I64),
             identifier: test_node!(String::from("x")),
             value: Some(test_node!(Expression::FunctionCall {
-                identifier: test_node!(String::from("print")),
-                arguments: vec![Box::new(test_node!(Argument {
-                    value: test_node!(Expression::Literal(Literal::String(String::from("hello world")))),
-                    passed_by: PassedBy::Value,
-                })),],
+                identifier: test_node!(String::from("push")),
+                arguments: vec![
+                    Box::new(test_node!(Argument {
+                        value: test_node!(Expression::Variable(String::from("a"))),
+                        passed_by: PassedBy::Value,
+                        name: None,
+                    })),
+                    Box::new(test_node!(Argument {
+                        value: test_node!(Expression::Literal(Literal::I64(1))),
+                        passed_by: PassedBy::Value,
+                        name: None,
+                    })),
+                ],
             })),
+            is_const: false,
         });
 
-        let program = setup_program();
+        let program = Program {
+            statements: vec![],
+            functions: HashMap::new(),
+            std_functions: get_std_functions(),
+        };
         let mut interpreter = create_interpreter(&program);
+        let _ = interpreter
+            .stack
+            .declare_variable("a", Rc::new(RefCell::new(Value::Array(Type::I64, Rc::new(RefCell::new(vec![]))))));
         assert_eq!(
             interpreter.visit_statement(&ast).err().unwrap().message(),
             create_error_message(String::from("Cannot declare variable 'x' with no value."))
@@ -893,31 +1563,680 @@ mod tests {
     }
 
     #[test]
-    fn declare_with_bad_type_fails() {
-        // i64 x = true;
-        let ast = test_node!(Statement::Declaration {
-            var_type: test_node!(Type::I64),
-            identifier: test_node!(String::from("x")),
-            value: Some(test_node!(Expression::Literal(Literal::True))),
-        });
+    fn calling_non_function_fails() {
+        // i64 x = 1;
+        // x(1);
+        // undefined();
+        let call = |name: &str| {
+            test_node!(Statement::FunctionCall {
+                identifier: test_node!(String::from(name)),
+                arguments: vec![Box::new(test_node!(Argument {
+                    value: test_node!(Expression::Literal(Literal::I64(1))),
+                    passed_by: PassedBy::Value,
+                    name: None,
+                }))],
+            })
+        };
+
+        let calls = [("x", call("x")), ("undefined", call("undefined"))];
 
         let program = setup_program();
         let mut interpreter = create_interpreter(&program);
-        assert_eq!(
-            interpreter.visit_statement(&ast).err().unwrap().message(),
-            create_error_message(String::from("Cannot assign value of type 'bool' to variable 'x' of type 'i64'."))
-        );
+        let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(1))));
+
+        for (name, ast) in &calls {
+            assert_eq!(
+                interpreter.visit_statement(ast).err().unwrap().message(),
+                create_error_message(format!("'{}' is not a function.", name))
+            );
+        }
     }
 
     #[test]
-    fn assigns_to_variable() {
-        // i64 x = 0;
-        // x = 5;
-        let ast = test_node!(Statement::Assignment {
-            identifier: test_node!(String::from("x")),
-            value: test_node!(Expression::Literal(Literal::I64(1))),
-        });
-
+    fn declare_with_void_function_result_fails() {
+        // fn do_stuff(): void { print("side effect"); }
+        // i64 x = do_stuff();
+        let mut functions: HashMap<String, Rc<Node<FunctionDeclaration>>> = HashMap::new();
+        functions.insert(
+            String::from("do_stuff"),
+            Rc::new(test_node!(FunctionDeclaration {
+                identifier: test_node!(String::from("do_stuff")),
+                parameters: vec![],
+                return_type: test_node!(Type::Void),
+                block: test_node!(Block(vec![test_node!(Statement::FunctionCall {
+                    identifier: test_node!(String::from("print")),
+                    arguments: vec![Box::new(test_node!(Argument {
+                        value: test_node!(Expression::Literal(Literal::String(String::from("side effect")))),
+                        passed_by: PassedBy::Value,
+                        name: None,
+                    }))],
+                })])),
+            })),
+        );
+        let ast = test_node!(Statement::Declaration {
+            var_type: test_node!(Type::I64),
+            identifier: test_node!(String::from("x")),
+            value: Some(test_node!(Expression::FunctionCall {
+                identifier: test_node!(String::from("do_stuff")),
+                arguments: vec![],
+            })),
+            is_const: false,
+        });
+
+        let program = Program {
+            statements: vec![],
+            functions,
+            std_functions: get_std_functions(),
+        };
+        let mut interpreter = create_interpreter(&program);
+        assert_eq!(
+            interpreter.visit_statement(&ast).err().unwrap().message(),
+            create_error_message(String::from("Cannot assign result of void function 'do_stuff' to variable 'x'."))
+        );
+        assert_eq!(interpreter.function_calls(), 0);
+    }
+
+    #[test]
+    fn function_cannot_read_caller_locals() {
+        // fn reads_caller(): i64 { return y; }
+        // fn caller(): void { i64 y = 1; i64 x = reads_caller(); }
+        let mut functions: HashMap<String, Rc<Node<FunctionDeclaration>>> = HashMap::new();
+        functions.insert(
+            String::from("reads_caller"),
+            Rc::new(test_node!(FunctionDeclaration {
+                identifier: test_node!(String::from("reads_caller")),
+                parameters: vec![],
+                return_type: test_node!(Type::I64),
+                block: test_node!(Block(vec![test_node!(Statement::Return(Some(test_node!(Expression::Variable(
+                    String::from("y")
+                ))))),])),
+            })),
+        );
+        let ast = test_node!(Statement::Declaration {
+            var_type: test_node!(Type::I64),
+            identifier: test_node!(String::from("x")),
+            value: Some(test_node!(Expression::FunctionCall {
+                identifier: test_node!(String::from("reads_caller")),
+                arguments: vec![],
+            })),
+            is_const: false,
+        });
+
+        let program = Program {
+            statements: vec![],
+            functions,
+            std_functions: HashMap::new(),
+        };
+        let mut interpreter = create_interpreter(&program);
+        let _ = interpreter.stack.push_stack_frame();
+        let _ = interpreter.stack.declare_variable("y", Rc::new(RefCell::new(Value::I64(1))));
+
+        assert_eq!(
+            interpreter.visit_statement(&ast).err().unwrap().message(),
+            create_error_message(String::from("Variable 'y' not declared in this scope."))
+        );
+    }
+
+    #[test]
+    fn print_returns_printed_text() {
+        // str x = print("hello", 5);
+        let ast = test_node!(Statement::Declaration {
+            var_type: test_node!(Type::Str),
+            identifier: test_node!(String::from("x")),
+            value: Some(test_node!(Expression::FunctionCall {
+                identifier: test_node!(String::from("print")),
+                arguments: vec![
+                    Box::new(test_node!(Argument {
+                        value: test_node!(Expression::Literal(Literal::String(String::from("hello")))),
+                        passed_by: PassedBy::Value,
+                        name: None,
+                    })),
+                    Box::new(test_node!(Argument {
+                        value: test_node!(Expression::Literal(Literal::I64(5))),
+                        passed_by: PassedBy::Value,
+                        name: None,
+                    })),
+                ],
+            })),
+            is_const: false,
+        });
+
+        let program = Program {
+            statements: vec![],
+            functions: HashMap::new(),
+            std_functions: get_std_functions(),
+        };
+        let mut interpreter = create_interpreter(&program);
+
+        assert!(interpreter.visit_statement(&ast).is_ok());
+        assert_eq!(
+            interpreter.stack.get_variable("x").unwrap().clone(),
+            Rc::new(RefCell::new(Value::String(String::from("hello 5"))))
+        );
+    }
+
+    fn call_std_function(name: &str, arguments: Vec<Expression>) -> Result<Option<Value>, Box<dyn IError>> {
+        let ast = test_node!(Expression::FunctionCall {
+            identifier: test_node!(String::from(name)),
+            arguments: arguments
+                .into_iter()
+                .map(|argument| {
+                    Box::new(test_node!(Argument {
+                        value: test_node!(argument),
+                        passed_by: PassedBy::Value,
+                        name: None,
+                    }))
+                })
+                .collect(),
+        });
+
+        let program = Program {
+            statements: vec![],
+            functions: HashMap::new(),
+            std_functions: get_std_functions(),
+        };
+        let mut interpreter = create_interpreter(&program);
+        interpreter.visit_expression(&ast)?;
+        Ok(interpreter.last_result)
+    }
+
+    #[test]
+    fn modulo() {
+        let arguments = vec![Expression::Literal(Literal::I64(7)), Expression::Literal(Literal::I64(3))];
+        assert_eq!(call_std_function("mod", arguments).unwrap(), Some(Value::I64(1)));
+
+        let data = [
+            (
+                vec![Expression::Literal(Literal::I64(7)), Expression::Literal(Literal::I64(0))],
+                "Division by zero.",
+            ),
+            (
+                vec![
+                    Expression::Literal(Literal::I64(i64::MIN)),
+                    Expression::ArithmeticNegation(Box::new(test_node!(Expression::Literal(Literal::I64(1))))),
+                ],
+                "Overflow occurred when performing modulo operation on i64s: -9223372036854775808 % -1.",
+            ),
+        ];
+
+        for (arguments, message) in data {
+            assert_eq!(
+                call_std_function("mod", arguments).err().unwrap().message(),
+                create_error_message(String::from(message))
+            );
+        }
+    }
+
+    #[test]
+    fn abs() {
+        // abs(-5), abs(-2.5), abs(5)
+        let data = [
+            Expression::ArithmeticNegation(Box::new(test_node!(Expression::Literal(Literal::I64(5))))),
+            Expression::ArithmeticNegation(Box::new(test_node!(Expression::Literal(Literal::F64(2.5))))),
+            Expression::Literal(Literal::I64(5)),
+        ];
+
+        let expected = [Value::I64(5), Value::F64(2.5), Value::I64(5)];
+
+        for (argument, exp) in data.into_iter().zip(expected) {
+            assert_eq!(call_std_function("abs", vec![argument]).unwrap(), Some(exp));
+        }
+    }
+
+    #[test]
+    fn abs_fail() {
+        let data = [
+            (
+                Expression::Literal(Literal::I64(i64::MIN)),
+                "Overflow occurred when calling 'abs' on '-9223372036854775808'.",
+            ),
+            (
+                Expression::Literal(Literal::String(String::from("a"))),
+                "Cannot call 'abs' on type 'str'.",
+            ),
+            (Expression::Literal(Literal::True), "Cannot call 'abs' on type 'bool'."),
+        ];
+
+        for (argument, message) in data {
+            assert_eq!(
+                call_std_function("abs", vec![argument]).err().unwrap().message(),
+                create_error_message(String::from(message))
+            );
+        }
+    }
+
+    #[test]
+    fn pow() {
+        // pow(2, 10), pow(2.0, 0.5), pow(5, 0)
+        let data = [
+            (Literal::I64(2), Literal::I64(10)),
+            (Literal::F64(2.0), Literal::F64(0.5)),
+            (Literal::I64(5), Literal::I64(0)),
+        ];
+        let expected = [Value::I64(1024), Value::F64(2.0_f64.sqrt()), Value::I64(1)];
+
+        for ((base, exp), expected) in data.into_iter().zip(expected) {
+            let arguments = vec![Expression::Literal(base), Expression::Literal(exp)];
+            assert_eq!(call_std_function("pow", arguments).unwrap(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn pow_fail() {
+        let data = [
+            (Literal::I64(2), Literal::I64(63)),
+            (Literal::I64(2), Literal::I64(-1)),
+            (Literal::F64(10.0), Literal::F64(400.0)),
+            (Literal::I64(2), Literal::F64(2.0)),
+        ];
+        let expected = [
+            "Overflow occurred when calling 'pow' on i64s.",
+            "Cannot raise i64 to negative power '-1'.",
+            "Invalid result when calling 'pow' on f64s.",
+            "Cannot perform 'pow' between values of type 'i64' and 'f64'.",
+        ];
+
+        for ((base, exp), message) in data.into_iter().zip(expected) {
+            let arguments = vec![Expression::Literal(base), Expression::Literal(exp)];
+            assert_eq!(
+                call_std_function("pow", arguments).err().unwrap().message(),
+                create_error_message(String::from(message))
+            );
+        }
+    }
+
+    #[test]
+    fn float_functions() {
+        let data = [
+            ("sqrt", 6.25),
+            ("floor", 2.7),
+            ("ceil", 2.2),
+            ("round", 2.5),
+            ("round", 2.4),
+            ("floor", -2.5),
+        ];
+        let expected = [2.5, 2.0, 3.0, 3.0, 2.0, -3.0];
+
+        for ((name, argument), exp) in data.into_iter().zip(expected) {
+            let arguments = vec![Expression::Literal(Literal::F64(argument))];
+            assert_eq!(call_std_function(name, arguments).unwrap(), Some(Value::F64(exp)));
+        }
+    }
+
+    #[test]
+    fn float_functions_fail() {
+        // sqrt(-1.0)
+        assert_eq!(
+            call_std_function("sqrt", vec![Expression::Literal(Literal::F64(-1.0))])
+                .err()
+                .unwrap()
+                .message(),
+            create_error_message(String::from("Cannot calculate 'sqrt' of negative number '-1'."))
+        );
+
+        for name in ["sqrt", "floor", "ceil", "round"] {
+            // floor(2)
+            assert_eq!(
+                call_std_function(name, vec![Expression::Literal(Literal::I64(2))])
+                    .err()
+                    .unwrap()
+                    .message(),
+                create_error_message(format!("Cannot call '{}' on type 'i64'.", name))
+            );
+        }
+    }
+
+    #[test]
+    fn len() {
+        // len("héllo"), len("")
+        let data = [Literal::String(String::from("héllo")), Literal::String(String::new())];
+        let expected = [Value::I64(5), Value::I64(0)];
+
+        for (argument, exp) in data.into_iter().zip(expected) {
+            assert_eq!(call_std_function("len", vec![Expression::Literal(argument)]).unwrap(), Some(exp));
+        }
+    }
+
+    #[test]
+    fn len_fail() {
+        // len(5)
+        assert_eq!(
+            call_std_function("len", vec![Expression::Literal(Literal::I64(5))])
+                .err()
+                .unwrap()
+                .message(),
+            create_error_message(String::from("Cannot call 'len' on type 'i64'."))
+        );
+    }
+
+    #[test]
+    fn len_array() {
+        // len([1, 2])
+        let array = Expression::Array(vec![
+            test_node!(Expression::Literal(Literal::I64(1))),
+            test_node!(Expression::Literal(Literal::I64(2))),
+        ]);
+        assert_eq!(call_std_function("len", vec![array]).unwrap(), Some(Value::I64(2)));
+    }
+
+    fn call_array_function<'a>(interpreter: &mut Interpreter<'a>, ast: &'a Node<Expression>) -> Result<Option<Value>, Box<dyn IError>> {
+        interpreter.last_result = None;
+        interpreter.visit_expression(ast)?;
+        Ok(interpreter.last_result.clone())
+    }
+
+    #[test]
+    fn push_and_pop() {
+        // push(a, 3), pop(a), pop(a)
+        let array_argument = || {
+            Box::new(test_node!(Argument {
+                value: test_node!(Expression::Variable(String::from("a"))),
+                passed_by: PassedBy::Value,
+                name: None,
+            }))
+        };
+        let push = test_node!(Expression::FunctionCall {
+            identifier: test_node!(String::from("push")),
+            arguments: vec![
+                array_argument(),
+                Box::new(test_node!(Argument {
+                    value: test_node!(Expression::Literal(Literal::I64(3))),
+                    passed_by: PassedBy::Value,
+                    name: None,
+                })),
+            ],
+        });
+        let pop = test_node!(Expression::FunctionCall {
+            identifier: test_node!(String::from("pop")),
+            arguments: vec![array_argument()],
+        });
+
+        let program = Program {
+            statements: vec![],
+            functions: HashMap::new(),
+            std_functions: get_std_functions(),
+        };
+        let mut interpreter = create_interpreter(&program);
+        let values = Rc::new(RefCell::new(vec![Value::I64(1), Value::I64(2)]));
+        let _ = interpreter
+            .stack
+            .declare_variable("a", Rc::new(RefCell::new(Value::Array(Type::I64, values.clone()))));
+
+        assert_eq!(call_array_function(&mut interpreter, &push).unwrap(), None);
+        assert_eq!(*values.borrow(), vec![Value::I64(1), Value::I64(2), Value::I64(3)]);
+        assert_eq!(call_array_function(&mut interpreter, &pop).unwrap(), Some(Value::I64(3)));
+        assert_eq!(call_array_function(&mut interpreter, &pop).unwrap(), Some(Value::I64(2)));
+        assert_eq!(*values.borrow(), vec![Value::I64(1)]);
+    }
+
+    #[test]
+    fn push_and_pop_fail() {
+        // push([1], "a"), push(1, 1), pop(1)
+        let single_element = || Expression::Array(vec![test_node!(Expression::Literal(Literal::I64(1)))]);
+        let data = [
+            (
+                "push",
+                vec![single_element(), Expression::Literal(Literal::String(String::from("a")))],
+                "Cannot push value of type 'str' to array of type '[i64]'.",
+            ),
+            (
+                "push",
+                vec![Expression::Literal(Literal::I64(1)), Expression::Literal(Literal::I64(1))],
+                "Cannot call 'push' on type 'i64'.",
+            ),
+            ("pop", vec![Expression::Literal(Literal::I64(1))], "Cannot call 'pop' on type 'i64'."),
+        ];
+
+        for (name, arguments, message) in data {
+            assert_eq!(
+                call_std_function(name, arguments).err().unwrap().message(),
+                create_error_message(String::from(message))
+            );
+        }
+    }
+
+    #[test]
+    fn pop_empty_array_fails() {
+        let program = Program {
+            statements: vec![],
+            functions: HashMap::new(),
+            std_functions: get_std_functions(),
+        };
+        let mut interpreter = create_interpreter(&program);
+        let _ = interpreter
+            .stack
+            .declare_variable("a", Rc::new(RefCell::new(Value::Array(Type::I64, Rc::new(RefCell::new(vec![]))))));
+        let pop = test_node!(Expression::FunctionCall {
+            identifier: test_node!(String::from("pop")),
+            arguments: vec![Box::new(test_node!(Argument {
+                value: test_node!(Expression::Variable(String::from("a"))),
+                passed_by: PassedBy::Value,
+                name: None,
+            }))],
+        });
+
+        assert_eq!(
+            call_array_function(&mut interpreter, &pop).err().unwrap().message(),
+            create_error_message(String::from("Cannot pop from an empty array."))
+        );
+    }
+
+    #[test]
+    fn split() {
+        // split("a,b,c", ","), split("abc", "")
+        let arguments = |text: &str, delimiter: &str| {
+            vec![
+                Expression::Literal(Literal::String(String::from(text))),
+                Expression::Literal(Literal::String(String::from(delimiter))),
+            ]
+        };
+        let expected: Vec<Value> = ["a", "b", "c"].iter().map(|segment| Value::String(String::from(*segment))).collect();
+        assert_eq!(
+            call_std_function("split", arguments("a,b,c", ",")).unwrap(),
+            Some(Value::Array(Type::Str, Rc::new(RefCell::new(expected))))
+        );
+        assert_eq!(
+            call_std_function("split", arguments("abc", "")).err().unwrap().message(),
+            create_error_message(String::from("Cannot split string on an empty delimiter."))
+        );
+    }
+
+    #[test]
+    fn contains_and_index_of() {
+        let arguments = |haystack: &str, needle: &str| {
+            vec![
+                Expression::Literal(Literal::String(String::from(haystack))),
+                Expression::Literal(Literal::String(String::from(needle))),
+            ]
+        };
+        let data = [
+            ("contains", "hello", "ell", Value::Bool(true)),
+            ("contains", "hello", "x", Value::Bool(false)),
+            ("index_of", "héllo", "llo", Value::I64(2)),
+            ("index_of", "hello", "x", Value::I64(-1)),
+            ("index_of", "hello", "", Value::I64(0)),
+        ];
+
+        for (name, haystack, needle, expected) in data {
+            assert_eq!(call_std_function(name, arguments(haystack, needle)).unwrap(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn contains_and_index_of_fail() {
+        for name in ["contains", "index_of"] {
+            let arguments = vec![
+                Expression::Literal(Literal::String(String::from("a"))),
+                Expression::Literal(Literal::I64(1)),
+            ];
+            assert_eq!(
+                call_std_function(name, arguments).err().unwrap().message(),
+                create_error_message(format!(
+                    "Std function '{}' expected arguments of types 'str', 'str', but was given 'str', 'i64'.",
+                    name
+                ))
+            );
+        }
+    }
+
+    fn substring_arguments(text: &str, start: i64, length: i64) -> Vec<Expression> {
+        vec![
+            Expression::Literal(Literal::String(String::from(text))),
+            Expression::Literal(Literal::I64(start)),
+            Expression::Literal(Literal::I64(length)),
+        ]
+    }
+
+    #[test]
+    fn substring() {
+        // substring("hello", 1, 3), substring("żółw", 1, 2), substring("abc", 3, 0)
+        let data = [("hello", 1, 3), ("żółw", 1, 2), ("abc", 3, 0)];
+        let expected = ["ell", "ół", ""];
+
+        for ((text, start, length), exp) in data.into_iter().zip(expected) {
+            assert_eq!(
+                call_std_function("substring", substring_arguments(text, start, length)).unwrap(),
+                Some(Value::String(String::from(exp)))
+            );
+        }
+    }
+
+    #[test]
+    fn substring_out_of_range_fail() {
+        let data = [("hello", -1, 2), ("hello", 1, -2), ("hello", 3, 3), ("hello", 6, 0)];
+
+        for (text, start, length) in data {
+            assert_eq!(
+                call_std_function("substring", substring_arguments(text, start, length))
+                    .err()
+                    .unwrap()
+                    .message(),
+                create_error_message(format!(
+                    "Range starting at {} with length {} is out of bounds for string of length 5.",
+                    start, length
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn change_case() {
+        // to_upper("straße"), to_lower("ŻÓŁW"), to_upper("abc1")
+        let data = [("to_upper", "straße"), ("to_lower", "ŻÓŁW"), ("to_upper", "abc1")];
+        let expected = ["STRASSE", "żółw", "ABC1"];
+
+        for ((name, text), exp) in data.into_iter().zip(expected) {
+            let arguments = vec![Expression::Literal(Literal::String(String::from(text)))];
+            assert_eq!(call_std_function(name, arguments).unwrap(), Some(Value::String(String::from(exp))));
+        }
+    }
+
+    #[test]
+    fn change_case_fail() {
+        for name in ["to_upper", "to_lower"] {
+            // to_upper(true)
+            assert_eq!(
+                call_std_function(name, vec![Expression::Literal(Literal::True)]).err().unwrap().message(),
+                create_error_message(format!("Cannot call '{}' on type 'bool'.", name))
+            );
+        }
+    }
+
+    #[test]
+    fn min_max() {
+        // max(3, 7), min(2.5, 1.5), min(3, 7), max(2.5, 1.5)
+        let data = [
+            ("max", Literal::I64(3), Literal::I64(7)),
+            ("min", Literal::F64(2.5), Literal::F64(1.5)),
+            ("min", Literal::I64(3), Literal::I64(7)),
+            ("max", Literal::F64(2.5), Literal::F64(1.5)),
+        ];
+
+        let expected = [Value::I64(7), Value::F64(1.5), Value::I64(3), Value::F64(2.5)];
+
+        for ((name, a, b), exp) in data.into_iter().zip(expected) {
+            let arguments = vec![Expression::Literal(a), Expression::Literal(b)];
+            assert_eq!(call_std_function(name, arguments).unwrap(), Some(exp));
+        }
+    }
+
+    #[test]
+    fn min_max_mixed_types_fail() {
+        for name in ["min", "max"] {
+            // min(1, 2.0)
+            let arguments = vec![Expression::Literal(Literal::I64(1)), Expression::Literal(Literal::F64(2.0))];
+            assert_eq!(
+                call_std_function(name, arguments).err().unwrap().message(),
+                create_error_message(format!("Cannot perform '{}' between values of type 'i64' and 'f64'.", name))
+            );
+        }
+    }
+
+    #[test]
+    fn declare_with_bad_type_fails() {
+        // i64 x = true;
+        let ast = test_node!(Statement::Declaration {
+            var_type: test_node!(Type::I64),
+            identifier: test_node!(String::from("x")),
+            value: Some(test_node!(Expression::Literal(Literal::True))),
+            is_const: false,
+        });
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        assert_eq!(
+            interpreter.visit_statement(&ast).err().unwrap().message(),
+            create_error_message(String::from("Cannot assign value of type 'bool' to variable 'x' of type 'i64'."))
+        );
+    }
+
+    #[test]
+    fn declares_char_variable() {
+        // char c = 'a';
+        let ast = test_node!(Statement::Declaration {
+            var_type: test_node!(Type::Char),
+            identifier: test_node!(String::from("c")),
+            value: Some(test_node!(Expression::Literal(Literal::Char('a')))),
+            is_const: false,
+        });
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+
+        assert!(interpreter.visit_statement(&ast).is_ok());
+        assert_eq!(
+            interpreter.stack.get_variable("c").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Char('a')))
+        );
+    }
+
+    #[test]
+    fn declare_char_with_bad_type_fails() {
+        // char c = "a";
+        let ast = test_node!(Statement::Declaration {
+            var_type: test_node!(Type::Char),
+            identifier: test_node!(String::from("c")),
+            value: Some(test_node!(Expression::Literal(Literal::String(String::from("a"))))),
+            is_const: false,
+        });
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        assert_eq!(
+            interpreter.visit_statement(&ast).err().unwrap().message(),
+            create_error_message(String::from("Cannot assign value of type 'str' to variable 'c' of type 'char'."))
+        );
+    }
+
+    #[test]
+    fn assigns_to_variable() {
+        // i64 x = 0;
+        // x = 5;
+        let ast = test_node!(Statement::Assignment {
+            identifier: test_node!(String::from("x")),
+            value: test_node!(Expression::Literal(Literal::I64(1))),
+        });
+
         let program = setup_program();
         let mut interpreter = create_interpreter(&program);
         let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(0))));
@@ -947,22 +2266,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn assign_to_constant_fails() {
+        // const i64 x = 0;
+        // x = 1;
+        let declaration = test_node!(Statement::Declaration {
+            var_type: test_node!(Type::I64),
+            identifier: test_node!(String::from("x")),
+            value: Some(test_node!(Expression::Literal(Literal::I64(0)))),
+            is_const: true,
+        });
+        let assignment = test_node!(Statement::Assignment {
+            identifier: test_node!(String::from("x")),
+            value: test_node!(Expression::Literal(Literal::I64(1))),
+        });
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        assert!(interpreter.visit_statement(&declaration).is_ok());
+        assert_eq!(
+            interpreter.visit_statement(&assignment).err().unwrap().message(),
+            create_error_message(String::from("Cannot assign to constant 'x'."))
+        );
+        assert_eq!(interpreter.stack.get_variable("x").unwrap().clone(), Rc::new(RefCell::new(Value::I64(0))));
+    }
+
     #[test]
     fn assign_with_none_value_fails() {
-        // x = print("hello world");
+        // x = push(a, 1);
         let ast = test_node!(Statement::Assignment {
             identifier: test_node!(String::from("x")),
             value: test_node!(Expression::FunctionCall {
-                identifier: test_node!(String::from("print")),
-                arguments: vec![Box::new(test_node!(Argument {
-                    value: test_node!(Expression::Literal(Literal::String(String::from("hello world")))),
-                    passed_by: PassedBy::Value,
-                })),],
+                identifier: test_node!(String::from("push")),
+                arguments: vec![
+                    Box::new(test_node!(Argument {
+                        value: test_node!(Expression::Variable(String::from("a"))),
+                        passed_by: PassedBy::Value,
+                        name: None,
+                    })),
+                    Box::new(test_node!(Argument {
+                        value: test_node!(Expression::Literal(Literal::I64(1))),
+                        passed_by: PassedBy::Value,
+                        name: None,
+                    })),
+                ],
             }),
         });
 
-        let program = setup_program();
+        let program = Program {
+            statements: vec![],
+            functions: HashMap::new(),
+            std_functions: get_std_functions(),
+        };
         let mut interpreter = create_interpreter(&program);
+        let _ = interpreter
+            .stack
+            .declare_variable("a", Rc::new(RefCell::new(Value::Array(Type::I64, Rc::new(RefCell::new(vec![]))))));
         let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(0))));
 
         assert_eq!(
@@ -1048,6 +2407,7 @@ mod tests {
                 var_type: test_node!(Type::I64),
                 identifier: test_node!(String::from("i")),
                 value: Some(test_node!(Expression::Literal(Literal::I64(1)))),
+                is_const: false,
             }))),
             condition: test_node!(Expression::LessEqual(
                 Box::new(test_node!(Expression::Variable(String::from("i")))),
@@ -1182,10 +2542,12 @@ mod tests {
                 Box::new(test_node!(Argument {
                     value: test_node!(Expression::Literal(Literal::I64(3))),
                     passed_by: PassedBy::Value,
+                    name: None,
                 })),
                 Box::new(test_node!(Argument {
                     value: test_node!(Expression::Literal(Literal::I64(4))),
                     passed_by: PassedBy::Value,
+                    name: None,
                 })),
             ],
         });
@@ -1201,11 +2563,13 @@ mod tests {
                         passed_by: PassedBy::Value,
                         parameter_type: test_node!(Type::I64),
                         identifier: test_node!(String::from("a")),
+                        default_value: None,
                     }),
                     test_node!(Parameter {
                         passed_by: PassedBy::Value,
                         parameter_type: test_node!(Type::I64),
                         identifier: test_node!(String::from("b")),
+                        default_value: None,
                     }),
                 ],
                 return_type: test_node!(Type::I64),
@@ -1227,6 +2591,203 @@ mod tests {
         assert_eq!(interpreter.is_returning, false);
     }
 
+    fn named_arguments_program() -> Program {
+        // fn sub(i64 a, i64 b = 1): i64 { return a - b; }
+        let parameter = |name: &str, default_value: Option<Node<Expression>>| {
+            test_node!(Parameter {
+                passed_by: PassedBy::Value,
+                parameter_type: test_node!(Type::I64),
+                identifier: test_node!(String::from(name)),
+                default_value,
+            })
+        };
+        let mut functions: HashMap<String, Rc<Node<FunctionDeclaration>>> = HashMap::new();
+        functions.insert(
+            String::from("sub"),
+            Rc::new(test_node!(FunctionDeclaration {
+                identifier: test_node!(String::from("sub")),
+                parameters: vec![
+                    parameter("a", None),
+                    parameter("b", Some(test_node!(Expression::Literal(Literal::I64(1))))),
+                ],
+                return_type: test_node!(Type::I64),
+                block: test_node!(Block(vec![test_node!(Statement::Return(Some(test_node!(Expression::Subtraction(
+                    Box::new(test_node!(Expression::Variable(String::from("a")))),
+                    Box::new(test_node!(Expression::Variable(String::from("b")))),
+                )))))])),
+            })),
+        );
+        Program {
+            statements: vec![],
+            std_functions: HashMap::new(),
+            functions,
+        }
+    }
+
+    fn call_sub(arguments: &[(Option<&str>, i64)]) -> Node<Expression> {
+        test_node!(Expression::FunctionCall {
+            identifier: test_node!(String::from("sub")),
+            arguments: arguments
+                .iter()
+                .map(|(name, value)| {
+                    Box::new(test_node!(Argument {
+                        value: test_node!(Expression::Literal(Literal::I64(*value))),
+                        passed_by: PassedBy::Value,
+                        name: name.map(|name| test_node!(String::from(name))),
+                    }))
+                })
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn named_arguments() {
+        // sub(b = 3, a = 10), sub(a = 10), sub(10, b = 4)
+        let data = [
+            (vec![(Some("b"), 3), (Some("a"), 10)], 7),
+            (vec![(Some("a"), 10)], 9),
+            (vec![(None, 10), (Some("b"), 4)], 6),
+        ];
+        let asts: Vec<_> = data.iter().map(|(arguments, _)| call_sub(arguments)).collect();
+
+        let program = named_arguments_program();
+        for (ast, (_, expected)) in asts.iter().zip(data) {
+            let mut interpreter = Interpreter::new(&program);
+            interpreter.visit_expression(ast).unwrap();
+            assert_eq!(interpreter.last_result, Some(Value::I64(expected)));
+        }
+    }
+
+    #[test]
+    fn named_arguments_fail() {
+        // sub(1, c = 2), sub(1, a = 2), sub(b = 2)
+        let data = [
+            (vec![(None, 1), (Some("c"), 2)], "Function 'sub' has no parameter named 'c'."),
+            (
+                vec![(None, 1), (Some("a"), 2)],
+                "Parameter 'a' of function 'sub' is given more than once.",
+            ),
+            (vec![(Some("b"), 2)], "Missing argument for parameter 'a' of function 'sub'."),
+        ];
+        let asts: Vec<_> = data.iter().map(|(arguments, _)| call_sub(arguments)).collect();
+
+        let program = named_arguments_program();
+        for (ast, (_, expected)) in asts.iter().zip(data) {
+            let mut interpreter = Interpreter::new(&program);
+            assert_eq!(
+                interpreter.visit_expression(ast).err().unwrap().message(),
+                create_error_message(String::from(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn reference_to_non_variable_fails() {
+        // sub(&5), sub(&(5 + 1))
+        let data = [
+            (
+                Expression::Literal(Literal::I64(5)),
+                "Cannot pass a literal by reference - only variables can be passed by reference.",
+            ),
+            (
+                Expression::Parenthesized(Box::new(test_node!(Expression::Addition(
+                    Box::new(test_node!(Expression::Literal(Literal::I64(5)))),
+                    Box::new(test_node!(Expression::Literal(Literal::I64(1)))),
+                )))),
+                "Cannot pass an expression by reference - only variables can be passed by reference.",
+            ),
+        ];
+        let asts: Vec<_> = data
+            .iter()
+            .map(|(value, _)| {
+                test_node!(Expression::FunctionCall {
+                    identifier: test_node!(String::from("sub")),
+                    arguments: vec![Box::new(test_node!(Argument {
+                        value: test_node!(value.clone()),
+                        passed_by: PassedBy::Reference,
+                        name: None,
+                    }))],
+                })
+            })
+            .collect();
+
+        let program = named_arguments_program();
+        for (ast, (_, expected)) in asts.iter().zip(data) {
+            let mut interpreter = Interpreter::new(&program);
+            assert_eq!(
+                interpreter.visit_expression(ast).err().unwrap().message(),
+                create_error_message(String::from(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn function_call_in_expression_mutates_reference() {
+        // fn add_in_place(&i64 a): i64 { a = a + 1; return a * 10; }
+        // i64 a = 1;
+        // i64 x = add_in_place(&a);
+        let mut functions: HashMap<String, Rc<Node<FunctionDeclaration>>> = HashMap::new();
+        functions.insert(
+            String::from("add_in_place"),
+            Rc::new(test_node!(FunctionDeclaration {
+                identifier: test_node!(String::from("add_in_place")),
+                parameters: vec![test_node!(Parameter {
+                    passed_by: PassedBy::Reference,
+                    parameter_type: test_node!(Type::I64),
+                    identifier: test_node!(String::from("a")),
+                    default_value: None,
+                })],
+                return_type: test_node!(Type::I64),
+                block: test_node!(Block(vec![
+                    test_node!(Statement::Assignment {
+                        identifier: test_node!(String::from("a")),
+                        value: test_node!(Expression::Addition(
+                            Box::new(test_node!(Expression::Variable(String::from("a")))),
+                            Box::new(test_node!(Expression::Literal(Literal::I64(1)))),
+                        )),
+                    }),
+                    test_node!(Statement::Return(Some(test_node!(Expression::Multiplication(
+                        Box::new(test_node!(Expression::Variable(String::from("a")))),
+                        Box::new(test_node!(Expression::Literal(Literal::I64(10)))),
+                    ))))),
+                ])),
+            })),
+        );
+
+        let program = Program {
+            statements: vec![
+                test_node!(Statement::Declaration {
+                    var_type: test_node!(Type::I64),
+                    identifier: test_node!(String::from("a")),
+                    value: Some(test_node!(Expression::Literal(Literal::I64(1)))),
+                    is_const: false,
+                }),
+                test_node!(Statement::Declaration {
+                    var_type: test_node!(Type::I64),
+                    identifier: test_node!(String::from("x")),
+                    value: Some(test_node!(Expression::FunctionCall {
+                        identifier: test_node!(String::from("add_in_place")),
+                        arguments: vec![Box::new(test_node!(Argument {
+                            value: test_node!(Expression::Variable(String::from("a"))),
+                            passed_by: PassedBy::Reference,
+                            name: None,
+                        }))],
+                    })),
+                    is_const: false,
+                }),
+            ],
+            std_functions: HashMap::new(),
+            functions,
+        };
+        let mut interpreter = Interpreter::new(&program);
+        assert!(interpreter.interpret().is_ok());
+        assert_eq!(
+            interpreter.stack.get_variable("x").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(20)))
+        );
+        assert_eq!(interpreter.stack.get_variable("a").unwrap().clone(), Rc::new(RefCell::new(Value::I64(2))));
+    }
+
     fn create_test_switch_case() -> Node<Statement> {
         // switch (x) {
         //      (x < 15) {
@@ -1261,7 +2822,7 @@ mod tests {
             cases: vec![
                 test_node!(SwitchCase {
                     condition: create_condition(15),
-                    block: test_node!(Block(vec![create_assignment(15)])),
+                    block: test_node!(Block(vec![create_assignment(15), test_node!(Statement::Fallthrough)])),
                 }),
                 test_node!(SwitchCase {
                     condition: create_condition(10),
@@ -1272,9 +2833,70 @@ mod tests {
                     block: test_node!(Block(vec![create_assignment(5)])),
                 }),
             ],
+            default: None,
         })
     }
 
+    #[test]
+    fn switch_value() {
+        // switch (x: y) {
+        //     (y > 10) -> "big",
+        //     (y > 5) -> "medium",
+        //     (true) -> "small"
+        // }
+        let case = |threshold: Option<i64>, text: &str| {
+            let condition = match threshold {
+                Some(threshold) => Expression::Greater(
+                    Box::new(test_node!(Expression::Variable(String::from("y")))),
+                    Box::new(test_node!(Expression::Literal(Literal::I64(threshold)))),
+                ),
+                None => Expression::Literal(Literal::True),
+            };
+            test_node!(SwitchValueCase {
+                condition: test_node!(condition),
+                value: test_node!(Expression::Literal(Literal::String(String::from(text)))),
+            })
+        };
+        let ast = test_node!(Expression::Switch {
+            expressions: vec![test_node!(SwitchExpression {
+                expression: test_node!(Expression::Variable(String::from("x"))),
+                alias: Some(test_node!(String::from("y"))),
+            })],
+            cases: vec![case(Some(10), "big"), case(Some(5), "medium"), case(None, "small")],
+            default: None,
+        });
+
+        let program = setup_program();
+        for (x, expected) in [(12, "big"), (7, "medium"), (1, "small")] {
+            let mut interpreter = create_interpreter(&program);
+            let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(x))));
+            interpreter.visit_expression(&ast).unwrap();
+            assert_eq!(interpreter.last_result, Some(Value::String(String::from(expected))));
+            // the alias is not visible after the switch
+            assert!(interpreter.stack.get_variable("y").is_err());
+        }
+    }
+
+    #[test]
+    fn switch_value_without_match_fails() {
+        // switch () { (false) -> 1 }
+        let ast = test_node!(Expression::Switch {
+            expressions: vec![],
+            cases: vec![test_node!(SwitchValueCase {
+                condition: test_node!(Expression::Literal(Literal::False)),
+                value: test_node!(Expression::Literal(Literal::I64(1))),
+            })],
+            default: None,
+        });
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        assert_eq!(
+            interpreter.visit_expression(&ast).err().unwrap().message(),
+            create_error_message(String::from("None of the cases of switch expression matched."))
+        );
+    }
+
     #[test]
     fn switch_enters() {
         let program = setup_program();
@@ -1282,7 +2904,7 @@ mod tests {
         let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(12))));
         let _ = interpreter
             .stack
-            .declare_variable("result", Rc::new(RefCell::new(Value::default_value(Type::I64).unwrap())));
+            .declare_variable("result", Rc::new(RefCell::new(Value::default_value(&Type::I64).unwrap())));
 
         let switch_case = &create_test_switch_case();
         let _ = interpreter.visit_statement(switch_case);
@@ -1294,6 +2916,33 @@ mod tests {
         assert_eq!(interpreter.is_breaking, false);
     }
 
+    #[test]
+    fn switch_default_case() {
+        // the cases of the test switch match x < 15, x < 10 and x < 5
+        let mut switch = create_test_switch_case();
+        if let Statement::Switch { default, .. } = &mut switch.value {
+            *default = Some(test_node!(Block(vec![test_node!(Statement::Assignment {
+                identifier: test_node!(String::from("result")),
+                value: test_node!(Expression::Literal(Literal::I64(-1))),
+            })])));
+        }
+
+        let program = setup_program();
+        for (x, expected) in [(20, -1), (12, 15), (3, 10)] {
+            let mut interpreter = create_interpreter(&program);
+            let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(x))));
+            let _ = interpreter
+                .stack
+                .declare_variable("result", Rc::new(RefCell::new(Value::default_value(&Type::I64).unwrap())));
+
+            interpreter.visit_statement(&switch).unwrap();
+            assert_eq!(
+                interpreter.stack.get_variable("result").unwrap().clone(),
+                Rc::new(RefCell::new(Value::I64(expected)))
+            );
+        }
+    }
+
     #[test]
     fn switch_breaks() {
         let program = setup_program();
@@ -1301,7 +2950,7 @@ mod tests {
         let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(3))));
         let _ = interpreter
             .stack
-            .declare_variable("result", Rc::new(RefCell::new(Value::default_value(Type::I64).unwrap())));
+            .declare_variable("result", Rc::new(RefCell::new(Value::default_value(&Type::I64).unwrap())));
 
         let switch_case = &create_test_switch_case();
         let _ = interpreter.visit_statement(switch_case);
@@ -1320,7 +2969,7 @@ mod tests {
         let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(2137))));
         let _ = interpreter
             .stack
-            .declare_variable("result", Rc::new(RefCell::new(Value::default_value(Type::I64).unwrap())));
+            .declare_variable("result", Rc::new(RefCell::new(Value::default_value(&Type::I64).unwrap())));
 
         let switch_case = &create_test_switch_case();
         let _ = interpreter.visit_statement(switch_case);
@@ -1332,6 +2981,34 @@ mod tests {
         assert_eq!(interpreter.is_breaking, false);
     }
 
+    #[test]
+    fn switch_without_expressions_runs_first_matching_case() {
+        let data = [(3, 15), (12, 15), (20, 0)];
+
+        for (x, expected) in data {
+            let program = setup_program();
+            let mut interpreter = create_interpreter(&program);
+            let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(x))));
+            let _ = interpreter
+                .stack
+                .declare_variable("result", Rc::new(RefCell::new(Value::default_value(&Type::I64).unwrap())));
+
+            let mut switch_case = create_test_switch_case();
+            if let Statement::Switch { expressions, cases, .. } = &mut switch_case.value {
+                expressions.clear();
+                // without 'fallthrough' in the first case
+                cases[0].value.block.value.0.pop();
+            }
+            let _ = interpreter.visit_statement(&switch_case);
+
+            assert_eq!(
+                interpreter.stack.get_variable("result").unwrap().clone(),
+                Rc::new(RefCell::new(Value::I64(expected)))
+            );
+            assert!(!interpreter.is_breaking);
+        }
+    }
+
     #[test]
     fn switch_bad_condition_type() {
         // switch () {
@@ -1346,6 +3023,7 @@ mod tests {
                 condition: test_node!(Expression::Literal(Literal::I64(1))),
                 block: test_node!(Block(vec![])),
             }),],
+            default: None,
         });
 
         assert_eq!(
@@ -1354,6 +3032,76 @@ mod tests {
         )
     }
 
+    #[test]
+    fn switch_error_points_at_executed_case() {
+        // switch () {
+        //      (false) -> {}
+        //      (true) -> {
+        //          i64 x = 1 + true;
+        //      }
+        // }
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+
+        fn at<T>(value: T, line: u32, column: u32) -> Node<T> {
+            Node {
+                value,
+                position: Position::new(line, column, 0),
+            }
+        }
+        let ast = at(
+            Statement::Switch {
+                expressions: vec![],
+                cases: vec![
+                    at(
+                        SwitchCase {
+                            condition: at(Expression::Literal(Literal::False), 2, 6),
+                            block: at(Block(vec![]), 2, 16),
+                        },
+                        2,
+                        5,
+                    ),
+                    at(
+                        SwitchCase {
+                            condition: at(Expression::Literal(Literal::True), 3, 6),
+                            block: at(
+                                Block(vec![at(
+                                    Statement::Declaration {
+                                        var_type: at(Type::I64, 4, 9),
+                                        identifier: at(String::from("x"), 4, 13),
+                                        value: Some(at(
+                                            Expression::Addition(
+                                                Box::new(at(Expression::Literal(Literal::I64(1)), 4, 17)),
+                                                Box::new(at(Expression::Literal(Literal::True), 4, 21)),
+                                            ),
+                                            4,
+                                            19,
+                                        )),
+                                        is_const: false,
+                                    },
+                                    4,
+                                    9,
+                                )]),
+                                3,
+                                15,
+                            ),
+                        },
+                        3,
+                        5,
+                    ),
+                ],
+                default: None,
+            },
+            1,
+            1,
+        );
+
+        assert_eq!(
+            interpreter.visit_statement(&ast).err().unwrap().message(),
+            "Cannot perform addition between values of type 'i64' and 'bool'.\nAt line: 4, column: 19."
+        )
+    }
+
     #[test]
     fn break_called_outside_for_or_switch() {
         let program = Program {
@@ -1421,6 +3169,7 @@ mod tests {
                 passed_by: PassedBy::Value,
                 parameter_type: test_node!(Type::I64),
                 identifier: test_node!(String::from("x")),
+                default_value: None,
             })],
             return_type: test_node!(Type::Void),
             block: test_node!(Block(vec![])),
@@ -1434,6 +3183,36 @@ mod tests {
         )
     }
 
+    #[test]
+    fn wrong_number_of_arguments() {
+        let program = setup_program();
+
+        let ast = FunctionDeclaration {
+            identifier: test_node!(String::from("fun")),
+            parameters: vec![test_node!(Parameter {
+                passed_by: PassedBy::Value,
+                parameter_type: test_node!(Type::I64),
+                identifier: test_node!(String::from("x")),
+                default_value: None,
+            })],
+            return_type: test_node!(Type::Void),
+            block: test_node!(Block(vec![])),
+        };
+
+        for (arguments, given) in [(vec![], 0), (vec![Value::I64(1), Value::I64(2)], 2)] {
+            let mut interpreter = create_interpreter(&program);
+            interpreter.last_arguments = arguments.into_iter().map(|value| Rc::new(RefCell::new(value))).collect();
+
+            assert_eq!(
+                interpreter.execute_function(&ast).err().unwrap().message(),
+                create_error_message(format!(
+                    "Function 'fun' called with wrong number of arguments (expected 1, got {}).",
+                    given
+                ))
+            );
+        }
+    }
+
     #[test]
     fn bad_return_type() {
         let program = setup_program();
@@ -1453,4 +3232,214 @@ mod tests {
             create_error_message(String::from("Bad return type from function 'fun'. Expected 'void', but got 'i64'."))
         )
     }
+
+    #[test]
+    fn array_literal() {
+        // [1, 2]
+        let ast = test_node!(Expression::Array(vec![
+            test_node!(Expression::Literal(Literal::I64(1))),
+            test_node!(Expression::Literal(Literal::I64(2))),
+        ]));
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        assert!(interpreter.visit_expression(&ast).is_ok());
+        assert_eq!(
+            interpreter.last_result,
+            Some(Value::Array(Type::I64, Rc::new(RefCell::new(vec![Value::I64(1), Value::I64(2)]))))
+        );
+    }
+
+    #[test]
+    fn array_literal_mixed_types_fails() {
+        // [1, "a"]
+        let ast = test_node!(Expression::Array(vec![
+            test_node!(Expression::Literal(Literal::I64(1))),
+            test_node!(Expression::Literal(Literal::String(String::from("a")))),
+        ]));
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        assert_eq!(
+            interpreter.visit_expression(&ast).err().unwrap().message(),
+            create_error_message(String::from("Array elements have to be of the same type - got 'i64' and 'str'."))
+        );
+    }
+
+    #[test]
+    fn index_array() {
+        // a[0], a[1]
+        let asts: Vec<_> = [0, 1]
+            .into_iter()
+            .map(|index| {
+                test_node!(Expression::Index {
+                    value: Box::new(test_node!(Expression::Variable(String::from("a")))),
+                    index: Box::new(test_node!(Expression::Literal(Literal::I64(index)))),
+                })
+            })
+            .collect();
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        let array = Value::Array(Type::I64, Rc::new(RefCell::new(vec![Value::I64(1), Value::I64(2)])));
+        let _ = interpreter.stack.declare_variable("a", Rc::new(RefCell::new(array)));
+
+        for (ast, expected) in asts.iter().zip([1, 2]) {
+            assert!(interpreter.visit_expression(ast).is_ok());
+            assert_eq!(interpreter.last_result, Some(Value::I64(expected)));
+        }
+    }
+
+    #[test]
+    fn index_array_fail() {
+        // a[2], a[-1], a[true], x[0]
+        let data = [
+            ("a", Literal::I64(2), "Index 2 out of bounds for array of length 2."),
+            ("a", Literal::I64(-1), "Index -1 out of bounds for array of length 2."),
+            ("a", Literal::True, "Array index has to be of type 'i64' - got 'bool'."),
+            ("x", Literal::I64(0), "Cannot index value of type 'i64'."),
+        ];
+        let asts: Vec<_> = data
+            .iter()
+            .map(|(name, index, _)| {
+                test_node!(Expression::Index {
+                    value: Box::new(test_node!(Expression::Variable(String::from(*name)))),
+                    index: Box::new(test_node!(Expression::Literal(index.clone()))),
+                })
+            })
+            .collect();
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        let array = Value::Array(Type::I64, Rc::new(RefCell::new(vec![Value::I64(1), Value::I64(2)])));
+        let _ = interpreter.stack.declare_variable("a", Rc::new(RefCell::new(array)));
+        let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(1))));
+
+        for (ast, (_, _, expected)) in asts.iter().zip(data) {
+            assert_eq!(
+                interpreter.visit_expression(ast).err().unwrap().message(),
+                create_error_message(String::from(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn index_assignment() {
+        // a[1] = 5;
+        let ast = test_node!(Statement::IndexAssignment {
+            identifier: test_node!(String::from("a")),
+            indices: vec![test_node!(Expression::Literal(Literal::I64(1)))],
+            value: test_node!(Expression::Literal(Literal::I64(5))),
+        });
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        let values = Rc::new(RefCell::new(vec![Value::I64(1), Value::I64(2)]));
+        let _ = interpreter
+            .stack
+            .declare_variable("a", Rc::new(RefCell::new(Value::Array(Type::I64, values.clone()))));
+
+        assert!(interpreter.visit_statement(&ast).is_ok());
+        // updated in place
+        assert_eq!(*values.borrow(), vec![Value::I64(1), Value::I64(5)]);
+    }
+
+    #[test]
+    fn index_assignment_fail() {
+        // a[0] = "x", a[2] = 1, x[0] = 1
+        let data = [
+            (
+                "a",
+                Literal::I64(0),
+                Literal::String(String::from("x")),
+                "Cannot assign value of type 'str' to element of array of type '[i64]'.",
+            ),
+            ("a", Literal::I64(2), Literal::I64(1), "Index 2 out of bounds for array of length 2."),
+            ("x", Literal::I64(0), Literal::I64(1), "Cannot index value of type 'i64'."),
+        ];
+        let asts: Vec<_> = data
+            .iter()
+            .map(|(name, index, value, _)| {
+                test_node!(Statement::IndexAssignment {
+                    identifier: test_node!(String::from(*name)),
+                    indices: vec![test_node!(Expression::Literal(index.clone()))],
+                    value: test_node!(Expression::Literal(value.clone())),
+                })
+            })
+            .collect();
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        let array = Value::Array(Type::I64, Rc::new(RefCell::new(vec![Value::I64(1), Value::I64(2)])));
+        let _ = interpreter.stack.declare_variable("a", Rc::new(RefCell::new(array)));
+        let _ = interpreter.stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(1))));
+
+        for (ast, (_, _, _, expected)) in asts.iter().zip(data) {
+            assert_eq!(
+                interpreter.visit_statement(ast).err().unwrap().message(),
+                create_error_message(String::from(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn destructuring() {
+        // (i64 x, str y) = (1, "a");
+        let ast = test_node!(Statement::Destructuring {
+            targets: vec![
+                (test_node!(Type::I64), test_node!(String::from("x"))),
+                (test_node!(Type::Str), test_node!(String::from("y"))),
+            ],
+            value: test_node!(Expression::Tuple(vec![
+                test_node!(Expression::Literal(Literal::I64(1))),
+                test_node!(Expression::Literal(Literal::String(String::from("a")))),
+            ])),
+        });
+
+        let program = setup_program();
+        let mut interpreter = create_interpreter(&program);
+        assert!(interpreter.visit_statement(&ast).is_ok());
+        assert_eq!(interpreter.stack.get_variable("x").unwrap().clone(), Rc::new(RefCell::new(Value::I64(1))));
+        assert_eq!(
+            interpreter.stack.get_variable("y").unwrap().clone(),
+            Rc::new(RefCell::new(Value::String(String::from("a"))))
+        );
+    }
+
+    #[test]
+    fn destructuring_type_mismatch_fails() {
+        let targets = vec![
+            (test_node!(Type::I64), test_node!(String::from("x"))),
+            (test_node!(Type::I64), test_node!(String::from("y"))),
+        ];
+        let data = [
+            (
+                // (i64 x, i64 y) = (1, "a");
+                Expression::Tuple(vec![
+                    test_node!(Expression::Literal(Literal::I64(1))),
+                    test_node!(Expression::Literal(Literal::String(String::from("a")))),
+                ]),
+                "Cannot destructure value of type '(i64, str)' into '(i64, i64)'.",
+            ),
+            (
+                // (i64 x, i64 y) = 1;
+                Expression::Literal(Literal::I64(1)),
+                "Cannot destructure value of type 'i64' into '(i64, i64)'.",
+            ),
+        ];
+
+        let program = setup_program();
+        for (value, expected) in data {
+            let ast = test_node!(Statement::Destructuring {
+                targets: targets.clone(),
+                value: test_node!(value),
+            });
+
+            let mut interpreter = create_interpreter(&program);
+            assert_eq!(
+                interpreter.visit_statement(&ast).err().unwrap().message(),
+                create_error_message(String::from(expected))
+            );
+        }
+    }
 }
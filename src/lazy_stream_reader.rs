@@ -5,6 +5,11 @@ use std::io::BufRead;
 pub const STX: char = '\u{2}';
 pub const ETX: char = '\u{3}';
 
+// max number of characters shown on each side of the error in code snippets
+const SNIPPET_RADIUS: usize = 40;
+const TRUNCATION_MARKER: &str = "...";
+pub const DEFAULT_TAB_WIDTH: u32 = 4;
+
 pub trait ILazyStreamReader {
     fn current(&self) -> &char;
     fn next(&mut self) -> Result<&char, Box<dyn Error>>;
@@ -36,6 +41,9 @@ pub struct LazyStreamReader<R: BufRead> {
     current_char: char,
     newline: Option<Vec<u8>>,
     current_position: Position,
+    // bytes taken by the current char in the source, invalid UTF-8 is read as one replacement char per byte
+    current_char_bytes: usize,
+    tab_width: u32,
 }
 
 impl<R: BufRead> ILazyStreamReader for LazyStreamReader<R> {
@@ -63,9 +71,16 @@ impl<R: BufRead> LazyStreamReader<R> {
             current_char: STX,
             newline: None,
             current_position: Position::new(0, 0, 0),
+            current_char_bytes: 0,
+            tab_width: DEFAULT_TAB_WIDTH,
         }
     }
 
+    pub fn with_tab_width(mut self, tab_width: u32) -> Self {
+        self.tab_width = tab_width.max(1);
+        self
+    }
+
     fn read_char(&mut self) -> Result<char, Box<dyn Error>> {
         let new_char = match self.try_handle_newline()? {
             None => self.process_char()?,
@@ -76,28 +91,20 @@ impl<R: BufRead> LazyStreamReader<R> {
     }
 
     fn try_handle_newline(&mut self) -> Result<Option<char>, Box<dyn Error>> {
-        let buffer = self.src.fill_buf()?;
+        let first_char = match self.src.fill_buf()?.first() {
+            Some(&first_char) if first_char == b'\r' || first_char == b'\n' => first_char,
+            _ => return Ok(None),
+        };
+        self.src.consume(1);
 
-        if let Some(&first_char) = buffer.get(0) {
-            if let Some(&second_char) = buffer.get(1) {
-                if first_char == b'\r' {
-                    let mut newline_sequence = vec![first_char];
-                    self.src.consume(1);
-                    if second_char == b'\n' {
-                        newline_sequence.push(second_char);
-                        self.src.consume(1);
-                    }
-                    self.newline = Some(newline_sequence.clone());
-                    return Ok(Some('\n'));
-                } else if first_char == b'\n' {
-                    self.src.consume(1);
-                    self.newline = Some(vec![first_char]);
-                    return Ok(Some('\n'));
-                }
-            }
+        let mut newline_sequence = vec![first_char];
+        // the newline can be the last byte of the input, or '\r\n' can be split between two reads of the buffer
+        if first_char == b'\r' && self.src.fill_buf()?.first() == Some(&b'\n') {
+            newline_sequence.push(b'\n');
+            self.src.consume(1);
         }
-
-        Ok(None)
+        self.newline = Some(newline_sequence);
+        Ok(Some('\n'))
     }
 
     fn process_char(&mut self) -> Result<char, Box<dyn Error>> {
@@ -108,10 +115,27 @@ impl<R: BufRead> LazyStreamReader<R> {
         }
 
         let first_byte = *buffer.get(0).unwrap();
-        let char = first_byte as char;
-
+        let width = utf8_char_width(first_byte);
         self.src.consume(1);
 
+        let mut bytes = vec![first_byte];
+        while bytes.len() < width {
+            // a multibyte char can be split between two reads of the buffer
+            let buffer = self.src.fill_buf()?;
+            match buffer.first() {
+                Some(&byte) if byte & 0b1100_0000 == 0b1000_0000 => {
+                    bytes.push(byte);
+                    self.src.consume(1);
+                }
+                _ => break,
+            }
+        }
+
+        self.current_char_bytes = bytes.len();
+        let char = std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|decoded| decoded.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
         Ok(char)
     }
 
@@ -127,8 +151,15 @@ impl<R: BufRead> LazyStreamReader<R> {
                 self.current_position.column = 1;
                 self.current_line = String::new();
             }
+            '\t' => {
+                self.current_position.offset += 1;
+                // tabs move to the next tab stop, columns start from 1
+                let tab_width = self.tab_width;
+                self.current_position.column = (self.current_position.column - 1) / tab_width * tab_width + tab_width + 1;
+                self.current_line.push('\t');
+            }
             char => {
-                self.current_position.offset += self.current_char.len_utf8();
+                self.current_position.offset += self.current_char_bytes;
                 self.current_position.column += 1;
                 self.current_line.push(char);
             }
@@ -139,9 +170,51 @@ impl<R: BufRead> LazyStreamReader<R> {
         let mut buffer = String::new();
         let _ = self.src.read_line(&mut buffer);
 
-        let spaces = " ".repeat((self.position().column - 1) as usize);
-        let caret_string = format!("{}^", spaces);
+        let line = format!("{}{}{}", self.current_line, self.current_char, buffer.trim_end_matches(['\r', '\n']));
+        code_snippet_with_tab_width(&line, self.position().column, self.tab_width)
+    }
+}
+
+fn utf8_char_width(first_byte: u8) -> usize {
+    match first_byte {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    }
+}
 
-        format!("\nAt line:\n{}{}{}{}", self.current_line, self.current_char, buffer, caret_string)
+// columns count tabs up to the next tab stop, so the snippet has to as well for the caret to line up
+fn expand_tabs(line: &str, tab_width: u32) -> Vec<char> {
+    let tab_width = tab_width as usize;
+    let mut chars = vec![];
+    for char in line.chars() {
+        match char {
+            '\t' => chars.resize((chars.len() / tab_width + 1) * tab_width, ' '),
+            char => chars.push(char),
+        }
     }
+    chars
+}
+
+pub fn code_snippet(line: &str, column: u32) -> String {
+    code_snippet_with_tab_width(line, column, DEFAULT_TAB_WIDTH)
+}
+
+fn code_snippet_with_tab_width(line: &str, column: u32, tab_width: u32) -> String {
+    let chars = expand_tabs(line, tab_width);
+    let caret_index = (column.max(1) - 1) as usize;
+
+    // long lines are cut to a window around the error
+    let start = caret_index.saturating_sub(SNIPPET_RADIUS);
+    let end = chars.len().min(caret_index + SNIPPET_RADIUS + 1);
+    let prefix = if start > 0 { TRUNCATION_MARKER } else { "" };
+    let suffix = if end < chars.len() { TRUNCATION_MARKER } else { "" };
+    let window: String = chars[start.min(end)..end].iter().collect();
+
+    let spaces = " ".repeat(prefix.len() + caret_index - start);
+    let caret_string = format!("{}^", spaces);
+
+    format!("\nAt line:\n{}{}{}\n{}", prefix, window, suffix, caret_string)
 }
@@ -9,6 +9,9 @@ use crate::tokens::{Token, TokenCategory, TokenValue};
 pub struct LexerOptions {
     pub max_comment_length: u32,
     pub max_identifier_length: u32,
+    pub max_string_length: u32,
+    // unclosed strings are an error instead of a warning
+    pub strict_strings: bool,
 }
 
 pub trait ILexer {
@@ -56,6 +59,7 @@ impl<T: BufRead> Lexer<T> {
             Self::try_generating_operator,
             Self::try_generating_comment,
             Self::try_generating_string,
+            Self::try_generating_char,
             Self::try_generating_number,
             Self::try_creating_identifier_or_keyword,
         ];
@@ -70,6 +74,16 @@ impl<T: BufRead> Lexer<T> {
         Err(self.create_lexer_error(String::from("Unexpected token")))
     }
 
+    pub fn for_each_token<F: FnMut(&Token)>(&mut self, mut on_token: F) -> Result<(), Box<dyn IError>> {
+        loop {
+            let token = self.generate_token()?;
+            on_token(&token);
+            if token.category == TokenCategory::ETX {
+                return Ok(());
+            }
+        }
+    }
+
     fn skip_whitespaces(&mut self) {
         while self.src.current().is_whitespace() {
             let _ = self.src.next();
@@ -119,16 +133,20 @@ impl<T: BufRead> Lexer<T> {
     fn try_generating_operator(&mut self) -> Result<Option<Token>, Box<dyn IError>> {
         let current_char = self.src.current();
         let token = match current_char {
-            '+' => Some(self.single_char(TokenCategory::Plus)),
-            '*' => Some(self.single_char(TokenCategory::Multiply)),
-            '/' => Some(self.single_char(TokenCategory::Divide)),
-            '-' => Some(self.extend_to_next('>', TokenCategory::Minus, TokenCategory::Arrow)),
-            '<' => Some(self.extend_to_next('=', TokenCategory::Less, TokenCategory::LessOrEqual)),
-            '>' => Some(self.extend_to_next('=', TokenCategory::Greater, TokenCategory::GreaterOrEqual)),
+            '+' => Some(self.extend_to_next('=', TokenCategory::Plus, TokenCategory::PlusAssign)),
+            '*' => Some(self.extend_to_next('=', TokenCategory::Multiply, TokenCategory::MultiplyAssign)),
+            '/' => Some(self.extend_to_next('=', TokenCategory::Divide, TokenCategory::DivideAssign)),
+            '^' => Some(self.single_char(TokenCategory::BitXor)),
+            '-' => Some(self.extend_to_one_of(&[('>', TokenCategory::Arrow), ('=', TokenCategory::MinusAssign)], TokenCategory::Minus)),
+            '<' => Some(self.extend_to_one_of(&[('=', TokenCategory::LessOrEqual), ('<', TokenCategory::ShiftLeft)], TokenCategory::Less)),
+            '>' => Some(self.extend_to_one_of(
+                &[('=', TokenCategory::GreaterOrEqual), ('>', TokenCategory::ShiftRight)],
+                TokenCategory::Greater,
+            )),
             '!' => Some(self.extend_to_next('=', TokenCategory::Negate, TokenCategory::NotEqual)),
             '=' => Some(self.extend_to_next('=', TokenCategory::Assign, TokenCategory::Equal)),
             '&' => Some(self.extend_to_next('&', TokenCategory::Reference, TokenCategory::And)),
-            '|' => Some(self.extend_to_next_or_warning('|', TokenCategory::Or)),
+            '|' => Some(self.extend_to_next('|', TokenCategory::BitOr, TokenCategory::Or)),
             _ => None,
         };
         Ok(token)
@@ -160,21 +178,20 @@ impl<T: BufRead> Lexer<T> {
         };
     }
 
-    fn extend_to_next_or_warning(&mut self, char_to_search: char, found: TokenCategory) -> Token {
-        let next_char = self.src.next().unwrap();
-        if *next_char == char_to_search {
-            let _ = self.src.next();
-        } else {
-            (self.on_warning)(Box::new(LexerError::new(
-                ErrorSeverity::LOW,
-                self.prepare_warning_message(format!("Expected '{}'", char_to_search)),
-            )));
-        }
-        return Token {
-            category: found,
+    fn extend_to_one_of(&mut self, candidates: &[(char, TokenCategory)], not_found: TokenCategory) -> Token {
+        let next_char = *self.src.next().unwrap();
+        let category = match candidates.iter().find(|(char, _)| *char == next_char) {
+            Some((_, found)) => {
+                let _ = self.src.next();
+                found.clone()
+            }
+            None => not_found,
+        };
+        Token {
+            category,
             value: TokenValue::Null,
             position: self.position,
-        };
+        }
     }
 
     fn try_generating_string(&mut self) -> Result<Option<Token>, Box<dyn IError>> {
@@ -185,9 +202,16 @@ impl<T: BufRead> Lexer<T> {
         let mut created_string = String::new();
         current_char = self.src.next().unwrap().clone();
         while current_char != '"' {
+            if (created_string.len() as u32) >= self.options.max_string_length && current_char != ETX && current_char != '\n' {
+                return Err(self.create_lexer_error(format!("String literal too long (max {}).", self.options.max_string_length)));
+            }
             // escaping
             if current_char == '\\' {
                 let next_char = self.src.next().unwrap().clone();
+                if next_char == 'u' {
+                    current_char = self.read_unicode_escape(&mut created_string);
+                    continue;
+                }
                 match ESCAPES.get(&next_char) {
                     Some(char) => {
                         created_string.push(*char);
@@ -210,10 +234,7 @@ impl<T: BufRead> Lexer<T> {
                 return Err(self.create_lexer_error(String::from("Unexpected newline in string")));
             }
             if current_char == ETX {
-                (self.on_warning)(Box::new(LexerError::new(
-                    ErrorSeverity::LOW,
-                    self.prepare_warning_message(String::from("String not closed")),
-                )));
+                self.report_unclosed_string()?;
                 return Ok(Some(Token {
                     category: TokenCategory::StringValue,
                     value: TokenValue::String(created_string),
@@ -232,6 +253,108 @@ impl<T: BufRead> Lexer<T> {
         }))
     }
 
+    // '\u{1F600}' - returns the first char after the escape
+    fn read_unicode_escape(&mut self, created_string: &mut String) -> char {
+        let mut escape = String::from("\\u");
+        let mut current_char = *self.src.next().unwrap();
+        let mut digits = String::new();
+        let mut is_closed = false;
+        if current_char == '{' {
+            escape.push(current_char);
+            current_char = *self.src.next().unwrap();
+            while current_char.is_ascii_hexdigit() {
+                digits.push(current_char);
+                current_char = *self.src.next().unwrap();
+            }
+            escape.push_str(&digits);
+            if current_char == '}' {
+                escape.push(current_char);
+                current_char = *self.src.next().unwrap();
+                is_closed = true;
+            }
+        }
+
+        // at most 6 digits, so 'from_str_radix' can't overflow
+        let codepoint = match is_closed && (1..=6).contains(&digits.len()) {
+            true => u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32),
+            false => None,
+        };
+        match codepoint {
+            Some(char) => created_string.push(char),
+            None => {
+                (self.on_warning)(Box::new(LexerError::new(
+                    ErrorSeverity::LOW,
+                    self.prepare_warning_message(format!("Invalid unicode escape detected '{}'", escape)),
+                )));
+                created_string.push_str(&escape);
+            }
+        }
+        current_char
+    }
+
+    // r"C:\path" - copied verbatim, so it can't contain '"'
+    fn read_raw_string(&mut self) -> Result<Token, Box<dyn IError>> {
+        let mut created_string = String::new();
+        let mut current_char = *self.src.next().unwrap();
+        while current_char != '"' {
+            if (created_string.len() as u32) >= self.options.max_string_length && current_char != ETX && current_char != '\n' {
+                return Err(self.create_lexer_error(format!("String literal too long (max {}).", self.options.max_string_length)));
+            }
+            if current_char == '\n' {
+                return Err(self.create_lexer_error(String::from("Unexpected newline in string")));
+            }
+            if current_char == ETX {
+                self.report_unclosed_string()?;
+                break;
+            }
+            created_string.push(current_char);
+            current_char = *self.src.next().unwrap();
+        }
+        // consume closing "
+        if current_char == '"' {
+            let _ = self.src.next();
+        }
+        Ok(Token {
+            category: TokenCategory::StringValue,
+            value: TokenValue::String(created_string),
+            position: self.position,
+        })
+    }
+
+    fn try_generating_char(&mut self) -> Result<Option<Token>, Box<dyn IError>> {
+        if *self.src.current() != '\'' {
+            return Ok(None);
+        }
+
+        let mut current_char = *self.src.next().unwrap();
+        let created_char = match current_char {
+            '\'' => return Err(self.create_lexer_error(String::from("Empty char literal"))),
+            '\n' => return Err(self.create_lexer_error(String::from("Unexpected newline in char"))),
+            ETX => return Err(self.create_lexer_error(String::from("Char not closed"))),
+            '\\' => {
+                let next_char = *self.src.next().unwrap();
+                match ESCAPES.get(&next_char) {
+                    Some(char) => *char,
+                    None => return Err(self.create_lexer_error(format!("Invalid escape symbol detected '\\{}'", next_char))),
+                }
+            }
+            char => char,
+        };
+
+        current_char = *self.src.next().unwrap();
+        if current_char != '\'' {
+            return Err(self.create_lexer_error(String::from("Char literal has to contain exactly one character")));
+        }
+        // consume closing '
+        let _ = self.src.next();
+
+        Ok(Some(Token {
+            category: TokenCategory::CharValue,
+            value: TokenValue::Char(created_char),
+            position: self.position,
+        }))
+    }
+
     fn try_generating_number(&mut self) -> Result<Option<Token>, Box<dyn IError>> {
         let mut current_char = self.src.current().clone();
         if !current_char.is_ascii_digit() {
@@ -242,10 +365,13 @@ impl<T: BufRead> Lexer<T> {
         if current_char != '0' {
             (decimal, _) = self.parse_integer()?;
         } else {
-            let next_char = self.src.next().unwrap();
-            if next_char.is_ascii_digit() {
+            let next_char = *self.src.next().unwrap();
+            if next_char.is_ascii_digit() || next_char == '_' {
                 return Err(self.create_lexer_error(String::from("Cannot prefix number with 0's.")));
             }
+            if next_char == 'x' || next_char == 'X' {
+                return self.parse_hexadecimal().map(Some);
+            }
         }
 
         current_char = self.src.current().clone();
@@ -267,21 +393,65 @@ impl<T: BufRead> Lexer<T> {
         }))
     }
 
+    fn parse_hexadecimal(&mut self) -> Result<Token, Box<dyn IError>> {
+        // 0x prefix is followed by hexadecimal digits, e.g. 0xFF
+        let _ = self.src.next();
+        if !self.src.current().is_ascii_hexdigit() {
+            return Err(self.create_lexer_error(String::from("Hexadecimal literal has to contain at least one digit.")));
+        }
+
+        let (value, _) = self.parse_digits(16)?;
+        let current_char = *self.src.current();
+        if current_char == '.' || current_char == 'p' || current_char == 'P' {
+            return Err(self.create_lexer_error(String::from("Hexadecimal float literals are not supported.")));
+        }
+
+        Ok(Token {
+            category: TokenCategory::I64Value,
+            value: TokenValue::I64(value),
+            position: self.position,
+        })
+    }
+
     fn parse_integer(&mut self) -> Result<(i64, i64), Box<dyn IError>> {
-        let mut current_char = self.src.current();
+        self.parse_digits(10)
+    }
+
+    fn parse_digits(&mut self, radix: u32) -> Result<(i64, i64), Box<dyn IError>> {
+        // digits may be separated by single underscores, e.g. 1_000_000
+        let mut current_char = *self.src.current();
+        if current_char == '_' {
+            return Err(self.create_lexer_error(String::from("Underscore in number has to be placed between digits.")));
+        }
+
         let mut length = 0;
         let mut total: i64 = 0;
-        while current_char.is_ascii_digit() {
-            let digit = *current_char as i64 - '0' as i64;
+        let mut after_underscore = false;
+        while current_char.is_digit(radix) || current_char == '_' {
+            if current_char == '_' {
+                if after_underscore {
+                    return Err(self.create_lexer_error(String::from("Underscore in number has to be placed between digits.")));
+                }
+                after_underscore = true;
+                current_char = *self.src.next().unwrap();
+                continue;
+            }
+
+            let digit = current_char.to_digit(radix).unwrap() as i64;
             total = total
-                .checked_mul(10)
+                .checked_mul(radix as i64)
                 .ok_or_else(|| self.create_lexer_error(String::from("Overflow occurred while parsing integer")))?;
 
             total = total
                 .checked_add(digit)
                 .ok_or_else(|| self.create_lexer_error(String::from("Overflow occurred while parsing integer")))?;
             length += 1;
-            current_char = self.src.next().unwrap();
+            after_underscore = false;
+            current_char = *self.src.next().unwrap();
+        }
+
+        if after_underscore {
+            return Err(self.create_lexer_error(String::from("Underscore in number has to be placed between digits.")));
         }
         Ok((total, length))
     }
@@ -308,6 +478,10 @@ impl<T: BufRead> Lexer<T> {
             created_string.push(current_char);
             current_char = self.src.next().unwrap().clone();
         }
+        // 'r' directly followed by a string is a raw string, not an identifier
+        if created_string == "r" && current_char == '"' {
+            return self.read_raw_string().map(Some);
+        }
         match KEYWORDS.get(created_string.as_str()) {
             Some(category) => Ok(Some(Token {
                 category: category.clone(),
@@ -329,6 +503,15 @@ impl<T: BufRead> Lexer<T> {
         Box::new(LexerError::new(ErrorSeverity::HIGH, message))
     }
 
+    fn report_unclosed_string(&mut self) -> Result<(), Box<dyn IError>> {
+        let text = String::from("String not closed");
+        if self.options.strict_strings {
+            return Err(self.create_lexer_error(text));
+        }
+        (self.on_warning)(Box::new(LexerError::new(ErrorSeverity::LOW, self.prepare_warning_message(text))));
+        Ok(())
+    }
+
     fn prepare_warning_message(&self, text: String) -> String {
         let position = self.src.position();
         format!("\nWarning:\n{}\nAt {:?}\n", text, position)
@@ -344,6 +527,7 @@ static SIGNS: phf::Map<char, TokenCategory> = phf_map! {
     '}'     => TokenCategory::BraceClose,
     ';'     => TokenCategory::Semicolon,
     ':'     => TokenCategory::Colon,
+    '?'     => TokenCategory::QuestionMark,
     ','     => TokenCategory::Comma,
     '\u{2}' => TokenCategory::STX,
     '\u{3}' => TokenCategory::ETX,
@@ -359,13 +543,17 @@ static KEYWORDS: phf::Map<&'static str, TokenCategory> = phf_map! {
     "i64" => TokenCategory::I64,
     "f64" => TokenCategory::F64,
     "str" => TokenCategory::String,
+    "char" => TokenCategory::Char,
     "void" => TokenCategory::Void,
     "bool" => TokenCategory::Bool,
     "true" => TokenCategory::True,
     "false" => TokenCategory::False,
     "as" => TokenCategory::As,
     "switch" => TokenCategory::Switch,
-    "break" => TokenCategory::Break
+    "default" => TokenCategory::Default,
+    "break" => TokenCategory::Break,
+    "fallthrough" => TokenCategory::Fallthrough,
+    "const" => TokenCategory::Const
 };
 
 static ESCAPES: phf::Map<char, char> = phf_map! {
@@ -373,5 +561,6 @@ static ESCAPES: phf::Map<char, char> = phf_map! {
     'r'  => '\r',
     't'  => '\t',
     '"'  => '"',
+    '\'' => '\'',
     '\\' => '\\',
 };
@@ -0,0 +1,312 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader, ErrorKind, Read, Write},
+    rc::Rc,
+};
+
+use crate::{
+    ast::{FunctionDeclaration, Node, Program},
+    errors::{ErrorSeverity, FileError, IError, SemanticCheckerError},
+    interpreter::{Environment, Interpreter},
+    lazy_stream_reader::{LazyStreamReader, DEFAULT_TAB_WIDTH},
+    lexer::{Lexer, LexerOptions},
+    parser::{IParser, Parser, ParserOptions},
+    semantic_checker::{SemanticChecker, SemanticCheckerOptions},
+    stack::Stack,
+    value::Value,
+};
+
+pub mod alu;
+pub mod ast;
+pub mod ast_printer;
+pub mod errors;
+pub mod interpreter;
+pub mod lazy_stream_reader;
+pub mod lexer;
+pub mod parser;
+pub mod purity;
+pub mod scope_manager;
+pub mod semantic_checker;
+pub mod stack;
+pub mod std_functions;
+pub mod tokens;
+pub mod value;
+pub mod visitor;
+
+mod tests;
+
+pub struct RunOptions {
+    pub tab_width: u32,
+    pub disable_std_functions: bool,
+    pub echo_result: bool,
+    pub memoize_calls: bool,
+    pub entry_point: Option<String>,
+    // stop at the first syntax error, instead of reporting all of them
+    pub strict_parsing: bool,
+    // an unclosed string stops the program, instead of being reported as a warning
+    pub strict_strings: bool,
+    // all warnings of the semantic analyzer are off by default
+    pub warnings: SemanticCheckerOptions,
+    // output and warnings are collected into 'RunResult', otherwise they go to stdout / stderr right away
+    pub capture: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            tab_width: DEFAULT_TAB_WIDTH,
+            disable_std_functions: false,
+            echo_result: false,
+            memoize_calls: false,
+            entry_point: None,
+            strict_parsing: false,
+            strict_strings: false,
+            warnings: SemanticCheckerOptions::default(),
+            capture: true,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RunResult {
+    pub output: String,
+    pub warnings: Vec<String>,
+    // value left by the final top-level statement
+    pub last_result: Option<Value>,
+    // returned by the entry point, if there is one
+    pub exit_code: Option<i64>,
+}
+
+#[derive(Clone, Default)]
+struct CapturedOutput(Rc<RefCell<Vec<u8>>>);
+
+impl Write for CapturedOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+thread_local! {
+    // the lexer reports warnings through a function pointer, so they are collected here
+    static LEXER_WARNINGS: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+}
+
+// flags come after the program name and the path to the file
+pub fn flag_given(args: &[String], flag: &str) -> bool {
+    args.iter().skip(2).any(|arg| arg == flag)
+}
+
+// comma separated names given to '--warn', e.g. 'unused,shadowing'
+pub fn parse_warnings(value: &str) -> Result<SemanticCheckerOptions, String> {
+    let mut options = SemanticCheckerOptions::default();
+    for name in value.split(',') {
+        match name {
+            "mixed-operators" => options.warn_on_mixed_logical_operators = true,
+            "ignored-results" => options.warn_on_ignored_return_values = true,
+            "unused" => options.warn_on_unused_variables = true,
+            "unreachable" => options.warn_on_unreachable_code = true,
+            "shadowing" => options.warn_on_shadowing = true,
+            "all" => {
+                options = SemanticCheckerOptions {
+                    warn_on_mixed_logical_operators: true,
+                    warn_on_ignored_return_values: true,
+                    warn_on_unused_variables: true,
+                    warn_on_unreachable_code: true,
+                    warn_on_shadowing: true,
+                }
+            }
+            _ => return Err(format!("Unknown warning '{}'.", name)),
+        }
+    }
+    Ok(options)
+}
+
+pub fn open_error_message(path: &str, error: &io::Error) -> String {
+    match error.kind() {
+        ErrorKind::NotFound => format!("File '{}' not found.", path),
+        ErrorKind::PermissionDenied => format!("Permission denied reading '{}'.", path),
+        ErrorKind::IsADirectory => format!("'{}' is a directory.", path),
+        _ => format!("Cannot open file '{}': {}.", path, error),
+    }
+}
+
+pub fn open_file(path: &str) -> Result<File, String> {
+    let file = File::open(path).map_err(|err| open_error_message(path, &err))?;
+    // opening a directory succeeds on some platforms, reading it fails later
+    match file.metadata() {
+        Ok(metadata) if metadata.is_dir() => Err(open_error_message(path, &io::Error::from(ErrorKind::IsADirectory))),
+        _ => Ok(file),
+    }
+}
+
+// runtime errors show snippets of the source, so the whole file is read up front
+pub fn read_source(path: &str) -> Result<String, Box<dyn IError>> {
+    let mut source = String::new();
+    open_file(path)
+        .and_then(|mut file| file.read_to_string(&mut source).map_err(|err| open_error_message(path, &err)))
+        .map_err(|message| Box::new(FileError::new(ErrorSeverity::HIGH, message)) as Box<dyn IError>)?;
+    Ok(source)
+}
+
+fn on_warning(warning: Box<dyn IError>) {
+    eprintln!("{}", warning.message());
+}
+
+fn collect_warning(warning: Box<dyn IError>) {
+    LEXER_WARNINGS.with(|warnings| warnings.borrow_mut().push(warning.message()));
+}
+
+pub fn create_lexer(source: &str, tab_width: u32, strict_strings: bool) -> Lexer<BufReader<&[u8]>> {
+    create_lexer_with_warnings(source, tab_width, strict_strings, on_warning)
+}
+
+fn create_lexer_with_warnings(source: &str, tab_width: u32, strict_strings: bool, on_warning: fn(Box<dyn IError>)) -> Lexer<BufReader<&[u8]>> {
+    let reader = LazyStreamReader::new(BufReader::new(source.as_bytes())).with_tab_width(tab_width);
+    let lexer_options = LexerOptions {
+        max_comment_length: 100,
+        max_identifier_length: 20,
+        max_string_length: 1000,
+        strict_strings,
+    };
+    Lexer::new(reader, lexer_options, on_warning)
+}
+
+pub fn parse_source(source: &str, options: &RunOptions) -> Result<Program, Box<dyn IError>> {
+    let parser_options = ParserOptions {
+        disable_std_functions: options.disable_std_functions,
+        recover_from_errors: !options.strict_parsing,
+        ..Default::default()
+    };
+    let on_warning = match options.capture {
+        true => collect_warning,
+        false => on_warning,
+    };
+    Parser::with_options(
+        create_lexer_with_warnings(source, options.tab_width, options.strict_strings, on_warning),
+        parser_options,
+    )
+    .parse()
+}
+
+// semantic warnings are returned, so the caller decides where to show them
+pub fn check_program(program: &Program, options: &SemanticCheckerOptions) -> Result<Vec<String>, Box<dyn IError>> {
+    let mut semantic_checker = SemanticChecker::with_options(program, options.clone())?;
+    semantic_checker.check();
+
+    // all errors are reported at once, each of them already ends with a newline
+    match semantic_checker.errors.is_empty() {
+        true => Ok(semantic_checker.warnings.iter().map(|warning| warning.message()).collect()),
+        false => {
+            let messages: Vec<String> = semantic_checker.errors.iter().map(|error| error.message()).collect();
+            Err(Box::new(SemanticCheckerError::new(ErrorSeverity::HIGH, messages.join("\n"))))
+        }
+    }
+}
+
+pub fn run_source(source: &str) -> Result<RunResult, Box<dyn IError>> {
+    run_source_with_options(source, &RunOptions::default())
+}
+
+pub fn run_file(path: &str) -> Result<RunResult, Box<dyn IError>> {
+    run_source(&read_source(path)?)
+}
+
+pub fn run_source_with_options(source: &str, options: &RunOptions) -> Result<RunResult, Box<dyn IError>> {
+    LEXER_WARNINGS.with(|warnings| warnings.borrow_mut().clear());
+    let program = parse_source(source, options)?;
+    let mut warnings = LEXER_WARNINGS.with(|warnings| warnings.take());
+    warnings.extend(check_program(&program, &options.warnings)?);
+    if !options.capture {
+        for warning in warnings.drain(..) {
+            eprintln!("{}", warning);
+        }
+    }
+
+    let output = CapturedOutput::default();
+    let mut interpreter = Interpreter::new(&program)
+        .with_source(source)
+        .with_result_echo(options.echo_result)
+        .with_call_memoization(options.memoize_calls)
+        .with_entry_point(options.entry_point.as_deref());
+    if options.capture {
+        interpreter = interpreter.with_writer(Box::new(output.clone()));
+    }
+    interpreter.interpret()?;
+
+    let captured = String::from_utf8_lossy(&output.0.borrow()).into_owned();
+    Ok(RunResult {
+        output: captured,
+        warnings,
+        last_result: interpreter.final_result().cloned(),
+        exit_code: interpreter.exit_code(),
+    })
+}
+
+// reads statements line by line, the variables and functions declared on a line stay visible on the next ones
+// the semantic checker is skipped, as it only sees a single line - errors are caught by the interpreter instead
+pub fn run_repl(mut reader: Box<dyn BufRead>, mut writer: Box<dyn Write>, options: &RunOptions) {
+    // the stack borrows the names from the program, so they are copied out after every line and the program can be dropped
+    let mut globals: Vec<(String, Rc<RefCell<Value>>, bool)> = vec![];
+    let mut functions: HashMap<String, Rc<Node<FunctionDeclaration>>> = HashMap::new();
+
+    loop {
+        let _ = write!(writer, "> ");
+        let _ = writer.flush();
+        let mut line = String::new();
+        // the same reader serves the lines and the 'input' calls, so they don't compete for stdin
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) if line.trim().is_empty() => continue,
+            Ok(_) => {}
+        }
+
+        let mut program = match parse_source(&line, options) {
+            Ok(program) => program,
+            Err(err) => {
+                let _ = writeln!(writer, "{}", err.message());
+                continue;
+            }
+        };
+        // functions declared on this line replace the earlier ones with the same name
+        for (name, function) in &functions {
+            program.functions.entry(name.clone()).or_insert_with(|| Rc::clone(function));
+        }
+        functions = program.functions.clone();
+
+        let mut stack = Stack::new();
+        for (name, value, is_const) in &globals {
+            let _ = match is_const {
+                true => stack.declare_constant(name, Rc::clone(value)),
+                false => stack.declare_variable(name, Rc::clone(value)),
+            };
+        }
+        let mut interpreter = Interpreter::new(&program)
+            .with_source(&line)
+            .with_result_echo(options.echo_result)
+            .with_environment(Environment { stack, writer, reader });
+        let result = interpreter.interpret();
+        let Environment {
+            stack,
+            writer: line_writer,
+            reader: line_reader,
+        } = interpreter.into_environment();
+        (writer, reader) = (line_writer, line_reader);
+
+        if let Err(err) = result {
+            let _ = writeln!(writer, "{}", err.message());
+        }
+        // a failed line may leave the frames of its calls behind, only the root scope is kept
+        globals = stack
+            .global_variables()
+            .into_iter()
+            .map(|(name, value, is_const)| (name.to_owned(), value, is_const))
+            .collect();
+    }
+    let _ = writeln!(writer);
+}
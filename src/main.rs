@@ -1,40 +1,28 @@
-use std::{env::args, fs::File, io::BufReader, time::Instant};
-
-use errors::IError;
-use lexer::Lexer;
-mod lazy_stream_reader;
-use lazy_stream_reader::LazyStreamReader;
-
-use crate::{
-    interpreter::Interpreter,
-    lexer::LexerOptions,
-    parser::{IParser, Parser},
-    semantic_checker::SemanticChecker,
+use std::{
+    env::args,
+    io::{self, BufReader},
+    process,
+    time::Instant,
 };
 
-mod alu;
-mod ast;
-mod errors;
-mod interpreter;
-mod lexer;
-mod parser;
-mod scope_manager;
-mod semantic_checker;
-mod stack;
-mod std_functions;
-mod tokens;
-mod value;
-mod visitor;
-
-mod tests;
+use tkom::{
+    ast_printer::AstPrinter, create_lexer, flag_given, lazy_stream_reader::DEFAULT_TAB_WIDTH, parse_source, parse_warnings, read_source, run_repl,
+    run_source_with_options, RunOptions,
+};
 
 fn parse_filename() -> Option<String> {
     let args: Vec<String> = args().collect();
     args.get(1).cloned()
 }
 
-fn on_warning(warning: Box<dyn IError>) {
-    eprintln!("{}", warning.message());
+fn has_flag(flag: &str) -> bool {
+    flag_given(&args().collect::<Vec<String>>(), flag)
+}
+
+// options given as `--name=value`
+fn option_value(name: &str) -> Option<String> {
+    let prefix = format!("{}=", name);
+    args().skip(2).find_map(|arg| arg.strip_prefix(prefix.as_str()).map(String::from))
 }
 
 fn main() {
@@ -43,45 +31,77 @@ fn main() {
         None => return eprintln!("Path to file not given."),
     };
 
-    let file = match File::open(path.as_str()) {
-        Ok(f) => f,
-        Err(_) => return eprintln!("File '{}' not found.", path),
-    };
+    // no file is read in interactive mode, the flag takes the place of the path
+    if path == "--repl" {
+        let options = RunOptions {
+            disable_std_functions: has_flag("--no-std"),
+            echo_result: has_flag("--echo"),
+            strict_parsing: has_flag("--strict-parsing"),
+            strict_strings: has_flag("--strict-strings"),
+            capture: false,
+            ..Default::default()
+        };
+        return run_repl(Box::new(BufReader::new(io::stdin())), Box::new(io::stdout()), &options);
+    }
 
-    let code = BufReader::new(file);
-    let reader = LazyStreamReader::new(code);
+    let source = match read_source(path.as_str()) {
+        Ok(source) => source,
+        Err(err) => return eprintln!("{}", err.message()),
+    };
 
-    let lexer_options = LexerOptions {
-        max_comment_length: 100,
-        max_identifier_length: 20,
+    let tab_width = match option_value("--tab-width").map(|value| value.parse::<u32>()) {
+        None => DEFAULT_TAB_WIDTH,
+        Some(Ok(width)) if width > 0 => width,
+        Some(_) => return eprintln!("Tab width has to be a positive integer."),
     };
 
-    let lexer = Lexer::new(reader, lexer_options, on_warning);
-    let mut parser = Parser::new(lexer);
+    if has_flag("--tokens") {
+        let mut lexer = create_lexer(&source, tab_width, has_flag("--strict-strings"));
+        if let Err(err) = lexer.for_each_token(|token| println!("{:?}\t{:?}\t{:?}", token.category, token.value, token.position)) {
+            eprintln!("{}", err.message());
+        }
+        return;
+    }
 
-    let start = Instant::now();
-    let program = match parser.parse() {
-        Ok(p) => p,
-        Err(err) => return eprintln!("{}", err.message()),
+    let warnings = match option_value("--warn").map(|value| parse_warnings(&value)) {
+        None => Default::default(),
+        Some(Ok(warnings)) => warnings,
+        Some(Err(message)) => return eprintln!("{}", message),
     };
 
-    let mut semantic_checker = match SemanticChecker::new(&program) {
-        Ok(checker) => checker,
-        Err(err) => return eprintln!("{}", err.message()),
+    let options = RunOptions {
+        tab_width,
+        disable_std_functions: has_flag("--no-std"),
+        echo_result: has_flag("--echo"),
+        memoize_calls: has_flag("--memoize"),
+        entry_point: option_value("--entry"),
+        strict_parsing: has_flag("--strict-parsing"),
+        strict_strings: has_flag("--strict-strings"),
+        warnings,
+        capture: false,
     };
-    semantic_checker.check();
 
-    if semantic_checker.errors.len() > 0 {
-        for error in &semantic_checker.errors {
-            eprintln!("{}", error.message());
-        }
-        return;
+    if has_flag("--dump-ast") {
+        return match parse_source(&source, &options) {
+            Ok(program) => print!("{}", AstPrinter::new(&program).print()),
+            Err(err) => eprintln!("{}", err.message()),
+        };
     }
 
-    let mut interpreter = Interpreter::new(&program);
-    if let Err(err) = interpreter.interpret() {
-        eprintln!("{}", err.message());
+    let start = Instant::now();
+    let exit_code = match run_source_with_options(&source, &options) {
+        Ok(result) => result.exit_code,
+        Err(err) => {
+            eprintln!("{}", err.message());
+            None
+        }
     };
 
-    println!("\nExecution time: {:?}", Instant::now() - start);
+    if has_flag("--time") {
+        eprintln!("\nExecution time: {:?}", Instant::now() - start);
+    }
+
+    if let Some(code) = exit_code {
+        process::exit(code as i32);
+    }
 }
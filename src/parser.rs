@@ -2,11 +2,12 @@ use std::{collections::HashMap, rc::Rc};
 
 use crate::{
     ast::{
-        Argument, Block, Expression, FunctionDeclaration, Literal, Node, Parameter, PassedBy, Program, Statement, SwitchCase, SwitchExpression, Type,
+        Argument, Block, Expression, FunctionDeclaration, Literal, Node, Parameter, PassedBy, Program, Statement, SwitchCase, SwitchExpression,
+        SwitchValueCase, Type,
     },
     errors::{ErrorSeverity, IError, ParserError},
     lexer::ILexer,
-    std_functions::get_std_functions,
+    std_functions::{get_std_functions, StdFunction},
     tokens::{Token, TokenCategory, TokenValue},
 };
 
@@ -28,47 +29,69 @@ macro_rules! try_consume {
     };
 }
 
+#[derive(Default)]
+pub struct ParserOptions {
+    pub allow_missing_final_semicolon: bool,
+    pub disable_std_functions: bool,
+    // skip to the next statement after an error and report all of them, instead of stopping at the first one
+    pub recover_from_errors: bool,
+}
+
 pub struct Parser<L: ILexer> {
     lexer: L,
+    options: ParserOptions,
+    // errors found so far, only collected when recovering from errors
+    pub errors: Vec<Box<dyn IError>>,
 }
 
 pub trait IParser<L: ILexer> {
+    #[allow(dead_code)]
     fn new(lexer: L) -> Parser<L>;
     fn parse(&mut self) -> Result<Program, Box<dyn IError>>;
 }
 
 impl<L: ILexer> IParser<L> for Parser<L> {
     fn new(lexer: L) -> Parser<L> {
-        Parser { lexer }
+        Parser::with_options(lexer, ParserOptions::default())
     }
 
     fn parse(&mut self) -> Result<Program, Box<dyn IError>> {
-        // program = { function_declaration | assign_or_call | if_statement | for_statement | switch_statement | declaration, ";" };
+        // program = { function_declaration | assign_or_call | if_statement | for_statement | switch_statement | declaration, ";" | destructuring };
         let _ = self.next_token()?; // initialize
         let _ = self.next_token()?; // skip STX
 
         let mut statements: Vec<Node<Statement>> = vec![];
         let mut functions: HashMap<String, Rc<Node<FunctionDeclaration>>> = HashMap::new();
-        let std_functions = get_std_functions();
+        let std_functions = match self.options.disable_std_functions {
+            true => HashMap::new(),
+            false => get_std_functions(),
+        };
 
         loop {
-            if let Some(statement) = self.parse_program_statement()? {
-                statements.push(statement);
-            } else if let Some(function_declaration) = self.parse_function_declaration()? {
-                let function_name = function_declaration.value.identifier.value.clone();
-                if functions.contains_key(&function_name) || std_functions.contains_key(&function_name) {
-                    return Err(Box::new(ParserError::new(
-                        ErrorSeverity::HIGH,
-                        format!("Redeclaration of function '{}'.\nAt: {:?}.", function_name, function_declaration.position),
-                    )));
+            match self.parse_program_item(&mut statements, &mut functions, &std_functions) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(err) if self.options.recover_from_errors => {
+                    self.errors.push(err);
+                    // an error of the lexer while skipping ends parsing
+                    if let Err(err) = self.synchronize() {
+                        self.errors.push(err);
+                        break;
+                    }
+                    // there is no block to close at the top level
+                    if let Err(err) = self.consume_if_matches(TokenCategory::BraceClose) {
+                        self.errors.push(err);
+                        break;
+                    }
                 }
-                functions.insert(function_name, Rc::new(function_declaration));
-            } else {
-                break;
+                Err(err) => return Err(err),
             }
         }
 
-        self.consume_must_be(TokenCategory::ETX)?;
+        if !self.errors.is_empty() {
+            let messages: Vec<String> = self.errors.iter().map(|error| error.message()).collect();
+            return Err(Box::new(ParserError::new(ErrorSeverity::HIGH, messages.join("\n\n"))));
+        }
 
         let program = Program {
             statements,
@@ -80,6 +103,74 @@ impl<L: ILexer> IParser<L> for Parser<L> {
 }
 
 impl<L: ILexer> Parser<L> {
+    pub fn with_options(lexer: L, options: ParserOptions) -> Parser<L> {
+        Parser {
+            lexer,
+            options,
+            errors: vec![],
+        }
+    }
+
+    // returns false after the end of the program is reached
+    fn parse_program_item(
+        &mut self,
+        statements: &mut Vec<Node<Statement>>,
+        functions: &mut HashMap<String, Rc<Node<FunctionDeclaration>>>,
+        std_functions: &HashMap<String, StdFunction>,
+    ) -> Result<bool, Box<dyn IError>> {
+        if let Some(statement) = self.parse_program_statement()? {
+            statements.push(statement);
+            return Ok(true);
+        }
+        if let Some(function_declaration) = self.parse_function_declaration()? {
+            let function_name = function_declaration.value.identifier.value.clone();
+            if functions.contains_key(&function_name) || std_functions.contains_key(&function_name) {
+                let error = Box::new(ParserError::new(
+                    ErrorSeverity::HIGH,
+                    format!("Redeclaration of function '{}'.\nAt: {:?}.", function_name, function_declaration.position),
+                ));
+                // the declaration itself is well-formed, so there is nothing to skip
+                match self.options.recover_from_errors {
+                    true => self.errors.push(error),
+                    false => return Err(error),
+                }
+                return Ok(true);
+            }
+            functions.insert(function_name, Rc::new(function_declaration));
+            return Ok(true);
+        }
+        self.consume_must_be(TokenCategory::ETX)?;
+        Ok(false)
+    }
+
+    // skips tokens up to the end of the broken statement - past its ';' or its block's '}', blocks nested in it are skipped whole
+    // stops before a '}' closing the enclosing block, so the block can still end there
+    fn synchronize(&mut self) -> Result<(), Box<dyn IError>> {
+        let mut depth = 0;
+        loop {
+            match self.current_token().category {
+                TokenCategory::ETX => return Ok(()),
+                TokenCategory::BraceClose if depth == 0 => return Ok(()),
+                TokenCategory::BraceClose if depth == 1 => {
+                    let _ = self.next_token()?;
+                    return Ok(());
+                }
+                TokenCategory::Semicolon if depth == 0 => {
+                    let _ = self.next_token()?;
+                    return Ok(());
+                }
+                category => {
+                    match category {
+                        TokenCategory::BraceOpen => depth += 1,
+                        TokenCategory::BraceClose => depth -= 1,
+                        _ => {}
+                    }
+                    let _ = self.next_token()?;
+                }
+            }
+        }
+    }
+
     fn next_token(&mut self) -> Result<Option<Token>, Box<dyn IError>> {
         // returns next token (skips comments)
         let mut current_token = self.lexer.next()?;
@@ -104,6 +195,7 @@ impl<L: ILexer> Parser<L> {
             TokenValue::F64(f64) => f64.to_string(),
             TokenValue::I64(i64) => i64.to_string(),
             TokenValue::String(str) => str,
+            TokenValue::Char(char) => char.to_string(),
             TokenValue::Null => format!("{:?}", current_token.category),
         };
         Err(self.create_parser_error(format!("Unexpected token - '{}'. Expected '{:?}'.", text, category)))
@@ -119,14 +211,24 @@ impl<L: ILexer> Parser<L> {
         Ok(None)
     }
 
+    fn consume_statement_end(&mut self) -> Result<(), Box<dyn IError>> {
+        // last statement in a block may omit its semicolon if enabled
+        if self.options.allow_missing_final_semicolon && self.current_token().category == TokenCategory::BraceClose {
+            return Ok(());
+        }
+        self.consume_must_be(TokenCategory::Semicolon)?;
+        Ok(())
+    }
+
     fn parse_program_statement(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
-        // program = { assign_or_call | if_statement | for_statement | switch_statement | declaration, ";" };
+        // program = { assign_or_call | if_statement | for_statement | switch_statement | declaration, ";" | destructuring };
         let generators = [
             Self::parse_assign_or_call,
             Self::parse_if_statement,
             Self::parse_for_statement,
             Self::parse_switch_statement,
             Self::parse_variable_declaration,
+            Self::parse_destructuring,
         ];
 
         for generator in &generators {
@@ -145,16 +247,22 @@ impl<L: ILexer> Parser<L> {
                 position: token.position,
             }),
             None => {
+                let expected = [Type::I64, Type::F64, Type::Bool, Type::Str, Type::Char, Type::Void]
+                    .iter()
+                    .map(|t| format!("'{}'", t))
+                    .collect::<Vec<String>>()
+                    .join(", ");
                 return Err(self.create_parser_error(format!(
-                    "Bad return type: {:?}. Expected one of: 'i64', 'f64', 'bool', 'str', 'void'.",
-                    self.current_token().category
-                )))
+                    "Bad return type: {:?}. Expected one of: {}.",
+                    self.current_token().category,
+                    expected
+                )));
             }
         }
     }
 
     fn parse_function_declaration(&mut self) -> Result<Option<Node<FunctionDeclaration>>, Box<dyn IError>> {
-        // function_declaration = “fn”, identifier, "(", parameters, ")", “:”, type | “void”, statement_block;
+        // function_declaration = “fn”, identifier, "(", parameters, ")", “:”, type | tuple_type | “void”, statement_block;
         let fn_token = try_consume_token!(self, TokenCategory::Fn);
 
         let identifier = self
@@ -167,7 +275,10 @@ impl<L: ILexer> Parser<L> {
         let _ = self.consume_must_be(TokenCategory::Colon)?;
         let return_type = match self.parse_type() {
             Ok(Some(t)) => t,
-            _ => self.void_type_or_error()?,
+            _ => match self.parse_tuple_type()? {
+                Some(t) => t,
+                None => self.void_type_or_error()?,
+            },
         };
         let block = self
             .parse_statement_block()?
@@ -198,6 +309,17 @@ impl<L: ILexer> Parser<L> {
             let parameter = self
                 .parse_parameter()?
                 .ok_or_else(|| self.create_parser_error(String::from("Couldn't create parameter while parsing parameters.")))?;
+
+            let previous_has_default = parameters.last().is_some_and(|p| p.value.default_value.is_some());
+            if previous_has_default && parameter.value.default_value.is_none() {
+                return Err(Box::new(ParserError::new(
+                    ErrorSeverity::HIGH,
+                    format!(
+                        "Parameter without default follows a parameter with a default.\nAt {:?}.",
+                        parameter.position
+                    ),
+                )));
+            }
             parameters.push(parameter);
         }
 
@@ -207,21 +329,27 @@ impl<L: ILexer> Parser<L> {
     fn parse_parameter(&mut self) -> Result<Option<Node<Parameter>>, Box<dyn IError>> {
         // parameter = [“&”], type, identifier, [ "=", expression ];
         let position = self.current_token().position;
-        let passed_by = match self.consume_if_matches(TokenCategory::Reference)? {
-            Some(_) => PassedBy::Reference,
-            None => PassedBy::Value,
-        };
+        let passed_by = self.parse_passed_by()?;
 
         let parameter_type = try_consume!(self, parse_type);
         let identifier = self
             .parse_identifier()?
             .ok_or_else(|| self.create_parser_error(String::from("Couldn't create identifier while parsing parameter.")))?;
 
+        let default_value = match self.consume_if_matches(TokenCategory::Assign)? {
+            Some(_) => Some(
+                self.parse_expression()?
+                    .ok_or_else(|| self.create_parser_error(String::from("Couldn't create default value while parsing parameter.")))?,
+            ),
+            None => None,
+        };
+
         let node = Node {
             value: Parameter {
                 passed_by,
                 parameter_type,
                 identifier,
+                default_value,
             },
             position,
         };
@@ -229,7 +357,7 @@ impl<L: ILexer> Parser<L> {
     }
 
     fn parse_for_statement(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
-        // for_statement = "for", "(", [ declaration ], “;”, expression, “;”, [ identifier, "=", expression ], ")", statement_block;
+        // for_statement = "for", "(", [ declaration ], “;”, expression, “;”, [ assign_or_call_clause ], ")", statement_block;
         let for_token = try_consume_token!(self, TokenCategory::For);
 
         let _ = self.consume_must_be(TokenCategory::ParenOpen)?;
@@ -248,24 +376,7 @@ impl<L: ILexer> Parser<L> {
             .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing for statement.")))?;
 
         self.consume_must_be(TokenCategory::Semicolon)?;
-        let mut assignment: Option<Box<Node<Statement>>> = None;
-        if self.current_token().category == TokenCategory::Identifier {
-            let identifier = self
-                .parse_identifier()?
-                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create identifier while parsing for statement.")))?;
-
-            let position = identifier.position;
-            let _ = self.consume_must_be(TokenCategory::Assign)?;
-            let expr = self
-                .parse_expression()?
-                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing for statement.")))?;
-
-            let assign = Box::new(Node {
-                value: Statement::Assignment { identifier, value: expr },
-                position,
-            });
-            assignment = Some(assign);
-        };
+        let assignment = self.parse_assign_or_call_clause()?.map(Box::new);
 
         self.consume_must_be(TokenCategory::ParenClose)?;
         let block = self
@@ -320,11 +431,19 @@ impl<L: ILexer> Parser<L> {
 
         let mut statements: Vec<Node<Statement>> = vec![];
         while self.consume_if_matches(TokenCategory::BraceClose)?.is_none() {
-            let statement = self
-                .parse_statement()?
-                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create statement while parsing statement block.")))?;
+            let statement = self.parse_statement().and_then(|statement| {
+                statement.ok_or_else(|| self.create_parser_error(String::from("Couldn't create statement while parsing statement block.")))
+            });
 
-            statements.push(statement);
+            match statement {
+                Ok(statement) => statements.push(statement),
+                // recovering within the block keeps the parser at the right nesting level, an unclosed block is left to the caller
+                Err(err) if self.options.recover_from_errors && self.current_token().category != TokenCategory::ETX => {
+                    self.errors.push(err);
+                    self.synchronize()?;
+                }
+                Err(err) => return Err(err),
+            }
         }
         Ok(Some(Node {
             value: Block(statements),
@@ -335,12 +454,12 @@ impl<L: ILexer> Parser<L> {
     fn parse_variable_declaration(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
         let decl = try_consume!(self, parse_declaration);
 
-        self.consume_must_be(TokenCategory::Semicolon)?;
+        self.consume_statement_end()?;
         Ok(Some(decl))
     }
 
     fn parse_statement(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
-        // statement = assign_or_call | if_statement | for_statement | switch_statement | declaration, ";" | return_statement | break_statement;
+        // statement = assign_or_call | if_statement | for_statement | switch_statement | declaration, ";" | return_statement | break_statement | fallthrough_statement | destructuring;
         let generators = [
             Self::parse_assign_or_call,
             Self::parse_if_statement,
@@ -348,7 +467,9 @@ impl<L: ILexer> Parser<L> {
             Self::parse_switch_statement,
             Self::parse_return_statement,
             Self::parse_break_statement,
+            Self::parse_fallthrough_statement,
             Self::parse_variable_declaration,
+            Self::parse_destructuring,
         ];
 
         for generator in &generators {
@@ -361,32 +482,87 @@ impl<L: ILexer> Parser<L> {
     }
 
     fn parse_assign_or_call(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
-        // assign_or_call = identifier, ("=", expression | "(", arguments, ")"), ";";
+        // assign_or_call = assign_or_call_clause, ";";
+        let node = try_consume!(self, parse_assign_or_call_clause);
+        self.consume_statement_end()?;
+        Ok(Some(node))
+    }
+
+    fn parse_assign_or_call_clause(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
+        // assign_or_call_clause = identifier, ({ "[", expression, "]" }, assign_operator, expression | "(", arguments, ")");
         let identifier = try_consume!(self, parse_identifier);
 
         let position = identifier.position;
 
-        if self.consume_if_matches(TokenCategory::Assign)?.is_some() {
+        // the assigned element, e.g. 'x[1][2]', is needed to desugar compound assignment
+        let mut target = Node {
+            value: Expression::Variable(identifier.value.clone()),
+            position,
+        };
+        let mut indices = vec![];
+        while let Some(token) = self.consume_if_matches(TokenCategory::BracketOpen)? {
+            let index = self
+                .parse_expression()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing index.")))?;
+            self.consume_must_be(TokenCategory::BracketClose)?;
+            indices.push(index.clone());
+            target = Node {
+                value: Expression::Index {
+                    value: Box::new(target),
+                    index: Box::new(index),
+                },
+                position: token.position,
+            };
+        }
+
+        let operator = self.current_token();
+        let is_assign_operator = matches!(
+            operator.category,
+            TokenCategory::Assign
+                | TokenCategory::PlusAssign
+                | TokenCategory::MinusAssign
+                | TokenCategory::MultiplyAssign
+                | TokenCategory::DivideAssign
+        );
+
+        if is_assign_operator {
+            let _ = self.next_token()?;
             let expr = self
                 .parse_expression()?
                 .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing assignment.")))?;
 
-            let node = Node {
-                value: Statement::Assignment { identifier, value: expr },
-                position,
+            // compound assignment 'x += y' is stored as 'x = x + y'
+            let variable = Box::new(target);
+            let expression_type = match operator.category {
+                TokenCategory::PlusAssign => Some(Expression::Addition(variable, Box::new(expr.clone()))),
+                TokenCategory::MinusAssign => Some(Expression::Subtraction(variable, Box::new(expr.clone()))),
+                TokenCategory::MultiplyAssign => Some(Expression::Multiplication(variable, Box::new(expr.clone()))),
+                TokenCategory::DivideAssign => Some(Expression::Division(variable, Box::new(expr.clone()))),
+                _ => None,
             };
-            self.consume_must_be(TokenCategory::Semicolon)?;
+            let value = match expression_type {
+                Some(expression_type) => Node {
+                    value: expression_type,
+                    position: operator.position,
+                },
+                None => expr,
+            };
+
+            let statement = match indices.is_empty() {
+                true => Statement::Assignment { identifier, value },
+                false => Statement::IndexAssignment { identifier, indices, value },
+            };
+            let node = Node { value: statement, position };
             return Ok(Some(node));
         }
 
-        if self.consume_if_matches(TokenCategory::ParenOpen)?.is_some() {
+        if indices.is_empty() && self.consume_if_matches(TokenCategory::ParenOpen)?.is_some() {
             let arguments = self.parse_arguments()?.into_iter().map(Box::new).collect();
             let node = Node {
                 value: Statement::FunctionCall { identifier, arguments },
                 position,
             };
             self.consume_must_be(TokenCategory::ParenClose)?;
-            self.consume_must_be(TokenCategory::Semicolon)?;
             return Ok(Some(node));
         }
 
@@ -394,10 +570,16 @@ impl<L: ILexer> Parser<L> {
     }
 
     fn parse_declaration(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
-        // declaration = type, identifier, [ "=", expression ];
-        let declaration_type = try_consume!(self, parse_type);
+        // declaration = [ "const" ], type, identifier, [ "=", expression ];
+        let const_token = self.consume_if_matches(TokenCategory::Const)?;
+        let declaration_type = match const_token {
+            Some(_) => self
+                .parse_type()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create type while parsing constant declaration.")))?,
+            None => try_consume!(self, parse_type),
+        };
 
-        let position = declaration_type.position;
+        let position = const_token.as_ref().map_or(declaration_type.position, |token| token.position);
         let identifier = self
             .parse_identifier()?
             .ok_or_else(|| self.create_parser_error(String::from("Couldn't create identifier while parsing variable declaration.")))?;
@@ -406,23 +588,64 @@ impl<L: ILexer> Parser<L> {
             Some(_) => self.parse_expression()?,
             None => None,
         };
+        if const_token.is_some() && value.is_none() {
+            return Err(self.create_parser_error(format!("Constant '{}' has to be initialized.", identifier.value)));
+        }
+
         let node = Node {
             value: Statement::Declaration {
                 var_type: declaration_type,
                 identifier,
                 value,
+                is_const: const_token.is_some(),
             },
             position,
         };
         Ok(Some(node))
     }
 
+    fn parse_destructuring(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
+        // destructuring = "(", type, identifier, ",", type, identifier, { ",", type, identifier }, ")", "=", expression, ";";
+        let token = try_consume_token!(self, TokenCategory::ParenOpen);
+
+        let mut targets = vec![];
+        loop {
+            let var_type = self
+                .parse_type()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create type while parsing destructuring.")))?;
+            let identifier = self
+                .parse_identifier()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create identifier while parsing destructuring.")))?;
+            targets.push((var_type, identifier));
+
+            if self.consume_if_matches(TokenCategory::Comma)?.is_none() {
+                break;
+            }
+        }
+        if targets.len() < 2 {
+            return Err(self.create_parser_error(String::from("Destructuring needs at least two variables.")));
+        }
+
+        self.consume_must_be(TokenCategory::ParenClose)?;
+        self.consume_must_be(TokenCategory::Assign)?;
+        let value = self
+            .parse_expression()?
+            .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing destructuring.")))?;
+        self.consume_statement_end()?;
+
+        let node = Node {
+            value: Statement::Destructuring { targets, value },
+            position: token.position,
+        };
+        Ok(Some(node))
+    }
+
     fn parse_return_statement(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
         // return_statement = "return", [ expression ], ";";
         let token = try_consume_token!(self, TokenCategory::Return);
 
         let returned_value = self.parse_expression()?;
-        self.consume_must_be(TokenCategory::Semicolon)?;
+        self.consume_statement_end()?;
         let node = Node {
             value: Statement::Return(returned_value),
             position: token.position,
@@ -434,7 +657,7 @@ impl<L: ILexer> Parser<L> {
         // break_statement = "break", ";";
         let token = try_consume_token!(self, TokenCategory::Break);
 
-        let _ = self.consume_must_be(TokenCategory::Semicolon)?;
+        self.consume_statement_end()?;
         let node = Node {
             value: Statement::Break,
             position: token.position,
@@ -442,6 +665,18 @@ impl<L: ILexer> Parser<L> {
         Ok(Some(node))
     }
 
+    fn parse_fallthrough_statement(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
+        // fallthrough_statement = "fallthrough", ";";
+        let token = try_consume_token!(self, TokenCategory::Fallthrough);
+
+        self.consume_statement_end()?;
+        let node = Node {
+            value: Statement::Fallthrough,
+            position: token.position,
+        };
+        Ok(Some(node))
+    }
+
     fn parse_arguments(&mut self) -> Result<Vec<Node<Argument>>, Box<dyn IError>> {
         // arguments = [ argument, {",", argument} ];
         let expression = match self.parse_argument()? {
@@ -455,31 +690,80 @@ impl<L: ILexer> Parser<L> {
                 .parse_argument()?
                 .ok_or_else(|| self.create_parser_error(String::from("Couldn't create argument while parsing arguments.")))?;
 
+            let follows_named = arguments.last().is_some_and(|a| a.value.name.is_some());
+            if follows_named && argument.value.name.is_none() {
+                return Err(self.create_parser_error(String::from("Positional argument cannot follow a named argument.")));
+            }
             arguments.push(argument);
         }
         Ok(arguments)
     }
 
     fn parse_argument(&mut self) -> Result<Option<Node<Argument>>, Box<dyn IError>> {
-        // argument = [“&”], expression;
-        let passed_by = match self.consume_if_matches(TokenCategory::Reference)? {
-            Some(_) => PassedBy::Reference,
-            None => PassedBy::Value,
-        };
+        // argument = [ identifier, "=" ], [“&”], expression;
+        let mut passed_by = self.parse_passed_by()?;
+        let mut expression = try_consume!(self, parse_expression);
+        let position = expression.position;
+
+        // the name can only be told apart from a variable once '=' is found
+        let mut name = None;
+        if let (PassedBy::Value, Expression::Variable(identifier)) = (&passed_by, &expression.value) {
+            if self.consume_if_matches(TokenCategory::Assign)?.is_some() {
+                name = Some(Node {
+                    value: identifier.clone(),
+                    position,
+                });
+                passed_by = self.parse_passed_by()?;
+                expression = self
+                    .parse_expression()?
+                    .ok_or_else(|| self.create_parser_error(format!("Couldn't create expression for named argument '{}'.", identifier)))?;
+            }
+        }
 
-        let expression = try_consume!(self, parse_expression);
         let argument = Argument {
-            value: expression.clone(),
+            value: expression,
             passed_by,
+            name,
+        };
+        Ok(Some(Node { value: argument, position }))
+    }
+
+    fn parse_passed_by(&mut self) -> Result<PassedBy, Box<dyn IError>> {
+        match self.consume_if_matches(TokenCategory::Reference)? {
+            Some(_) => Ok(PassedBy::Reference),
+            None => Ok(PassedBy::Value),
+        }
+    }
+
+    fn parse_expression(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
+        // expression = alternative_term, [ "?", expression, ":", expression ];
+        let condition = try_consume!(self, parse_alternative_term);
+
+        let question_mark = match self.consume_if_matches(TokenCategory::QuestionMark)? {
+            Some(token) => token,
+            None => return Ok(Some(condition)),
         };
+
+        let if_true = self
+            .parse_expression()?
+            .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression for true branch of ternary expression.")))?;
+        self.consume_must_be(TokenCategory::Colon)?;
+        let if_false = self
+            .parse_expression()?
+            .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression for false branch of ternary expression.")))?;
+
         Ok(Some(Node {
-            value: argument,
-            position: expression.position,
+            value: Expression::Ternary {
+                condition: Box::new(condition),
+                if_true: Box::new(if_true),
+                if_false: Box::new(if_false),
+            },
+            position: question_mark.position,
         }))
     }
 
-    fn parse_expression(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
-        // expression = concatenation_term { “||”, concatenation_term };
+    fn parse_alternative_term(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
+        // alternative_term = concatenation_term, { “||”, concatenation_term };
         let mut left_side = try_consume!(self, parse_concatenation_term);
 
         let mut current_token = self.current_token();
@@ -487,7 +771,7 @@ impl<L: ILexer> Parser<L> {
             let _ = self.next_token()?;
             let right_side = self
                 .parse_concatenation_term()?
-                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create concatenation term while parsing expression.")))?;
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create concatenation term while parsing alternative term.")))?;
 
             let expression_type = Expression::Alternative(Box::new(left_side.clone()), Box::new(right_side.clone()));
             left_side = Node {
@@ -521,8 +805,8 @@ impl<L: ILexer> Parser<L> {
     }
 
     fn parse_relation_term(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
-        // relation_term = additive_term, [ relation_operands, additive_term ];
-        let left_side = try_consume!(self, parse_additive_term);
+        // relation_term = bitwise_or_term, [ relation_operands, bitwise_or_term ];
+        let left_side = try_consume!(self, parse_bitwise_or_term);
 
         let operands = [
             TokenCategory::Equal,
@@ -540,8 +824,8 @@ impl<L: ILexer> Parser<L> {
 
         let _ = self.next_token()?;
         let right_side = self
-            .parse_additive_term()?
-            .ok_or_else(|| self.create_parser_error(String::from("Couldn't create additive term while parsing relation term.")))?;
+            .parse_bitwise_or_term()?
+            .ok_or_else(|| self.create_parser_error(String::from("Couldn't create bitwise or term while parsing relation term.")))?;
 
         let box_l = Box::new(left_side.clone());
         let box_r = Box::new(right_side);
@@ -553,16 +837,104 @@ impl<L: ILexer> Parser<L> {
             TokenCategory::GreaterOrEqual => Expression::GreaterEqual(box_l, box_r),
             TokenCategory::Less => Expression::Less(box_l, box_r),
             TokenCategory::LessOrEqual => Expression::LessEqual(box_l, box_r),
-            _ => return Err(self.create_parser_error(String::from("Couldn't create additive term while parsing relation term."))),
+            _ => return Err(self.create_parser_error(String::from("Couldn't create bitwise or term while parsing relation term."))),
         };
 
         let node = Node {
             value: expr,
-            position: left_side.position,
+            position: current_token.position,
         };
         Ok(Some(node))
     }
 
+    fn parse_bitwise_or_term(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
+        // bitwise_or_term = bitwise_xor_term, { "|", bitwise_xor_term };
+        let mut left_side = try_consume!(self, parse_bitwise_xor_term);
+
+        let mut current_token = self.current_token();
+        while current_token.category == TokenCategory::BitOr {
+            let _ = self.next_token()?;
+            let right_side = self
+                .parse_bitwise_xor_term()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create bitwise xor term while parsing bitwise or term.")))?;
+
+            let expression_type = Expression::BitwiseOr(Box::new(left_side), Box::new(right_side));
+            left_side = Node {
+                value: expression_type,
+                position: current_token.position,
+            };
+            current_token = self.current_token();
+        }
+        Ok(Some(left_side))
+    }
+
+    fn parse_bitwise_xor_term(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
+        // bitwise_xor_term = bitwise_and_term, { "^", bitwise_and_term };
+        let mut left_side = try_consume!(self, parse_bitwise_and_term);
+
+        let mut current_token = self.current_token();
+        while current_token.category == TokenCategory::BitXor {
+            let _ = self.next_token()?;
+            let right_side = self
+                .parse_bitwise_and_term()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create bitwise and term while parsing bitwise xor term.")))?;
+
+            let expression_type = Expression::BitwiseXor(Box::new(left_side), Box::new(right_side));
+            left_side = Node {
+                value: expression_type,
+                position: current_token.position,
+            };
+            current_token = self.current_token();
+        }
+        Ok(Some(left_side))
+    }
+
+    fn parse_bitwise_and_term(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
+        // bitwise_and_term = shift_term, { "&", shift_term };
+        // a single "&" is also used for passing by reference, but that one can only start an argument
+        let mut left_side = try_consume!(self, parse_shift_term);
+
+        let mut current_token = self.current_token();
+        while current_token.category == TokenCategory::Reference {
+            let _ = self.next_token()?;
+            let right_side = self
+                .parse_shift_term()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create shift term while parsing bitwise and term.")))?;
+
+            let expression_type = Expression::BitwiseAnd(Box::new(left_side), Box::new(right_side));
+            left_side = Node {
+                value: expression_type,
+                position: current_token.position,
+            };
+            current_token = self.current_token();
+        }
+        Ok(Some(left_side))
+    }
+
+    fn parse_shift_term(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
+        // shift_term = additive_term, { ("<<" | ">>"), additive_term };
+        let mut left_side = try_consume!(self, parse_additive_term);
+
+        let mut current_token = self.current_token();
+        while current_token.category == TokenCategory::ShiftLeft || current_token.category == TokenCategory::ShiftRight {
+            let _ = self.next_token()?;
+            let right_side = self
+                .parse_additive_term()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create additive term while parsing shift term.")))?;
+
+            let mut expression_type = Expression::ShiftLeft(Box::new(left_side.clone()), Box::new(right_side.clone()));
+            if current_token.category == TokenCategory::ShiftRight {
+                expression_type = Expression::ShiftRight(Box::new(left_side), Box::new(right_side))
+            }
+            left_side = Node {
+                value: expression_type,
+                position: current_token.position,
+            };
+            current_token = self.current_token();
+        }
+        Ok(Some(left_side))
+    }
+
     fn parse_additive_term(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
         // additive_term = multiplicative_term , { ("+" | "-"), multiplicative_term };
         let mut left_side = try_consume!(self, parse_multiplicative_term);
@@ -642,7 +1014,7 @@ impl<L: ILexer> Parser<L> {
     }
 
     fn parse_unary_term(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
-        // unary_term = [ ("-", "!") ], factor;
+        // unary_term = [ ("-" | "!" | "+") ], factor;
         if let Some(token) = self.consume_if_matches(TokenCategory::Negate)? {
             let factor = self.parse_unary_term_factor()?;
             return Ok(Some(Node {
@@ -659,13 +1031,41 @@ impl<L: ILexer> Parser<L> {
             }));
         }
 
+        if let Some(token) = self.consume_if_matches(TokenCategory::Plus)? {
+            let factor = self.parse_unary_term_factor()?;
+            return Ok(Some(Node {
+                value: Expression::UnaryPlus(Box::new(factor)),
+                position: token.position,
+            }));
+        }
+
         let factor = self.parse_factor()?;
         Ok(factor)
     }
 
     fn parse_factor(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
-        // factor = literal | ( "(", expression, ")" ) | identifier_or_call;
-        if let Ok(Some(literal)) = self.parse_literal() {
+        // factor = primary, { "[", expression, "]" };
+        let mut factor = try_consume!(self, parse_primary);
+
+        while let Some(token) = self.consume_if_matches(TokenCategory::BracketOpen)? {
+            let index = self
+                .parse_expression()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing index.")))?;
+            self.consume_must_be(TokenCategory::BracketClose)?;
+            factor = Node {
+                value: Expression::Index {
+                    value: Box::new(factor),
+                    index: Box::new(index),
+                },
+                position: token.position,
+            };
+        }
+        Ok(Some(factor))
+    }
+
+    fn parse_primary(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
+        // primary = literal | ( "(", expression, { ",", expression }, ")" ) | array_literal | switch_value | identifier_or_call;
+        if let Some(literal) = self.parse_literal()? {
             let node = Node {
                 value: Expression::Literal(literal.value),
                 position: literal.position,
@@ -673,17 +1073,63 @@ impl<L: ILexer> Parser<L> {
             return Ok(Some(node));
         }
 
-        if self.consume_if_matches(TokenCategory::ParenOpen)?.is_some() {
+        if let Some(token) = self.consume_if_matches(TokenCategory::ParenOpen)? {
             let expression = self
                 .parse_expression()?
                 .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing nested expression.")))?;
 
+            // a comma after the first expression makes it a tuple
+            let mut values = vec![];
+            while self.consume_if_matches(TokenCategory::Comma)?.is_some() {
+                let value = self
+                    .parse_expression()?
+                    .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing tuple.")))?;
+                values.push(value);
+            }
+
             self.consume_must_be(TokenCategory::ParenClose)?;
-            return Ok(Some(expression));
+            let value = match values.is_empty() {
+                true => Expression::Parenthesized(Box::new(expression)),
+                false => Expression::Tuple([vec![expression], values].concat()),
+            };
+            return Ok(Some(Node {
+                value,
+                position: token.position,
+            }));
+        }
+
+        if let Some(array) = self.parse_array_literal()? {
+            return Ok(Some(array));
+        }
+        if let Some(switch) = self.parse_switch_value()? {
+            return Ok(Some(switch));
         }
         self.parse_identifier_or_call()
     }
 
+    fn parse_array_literal(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
+        // array_literal = "[", expression, { ",", expression }, "]";
+        let token = try_consume_token!(self, TokenCategory::BracketOpen);
+
+        let mut values = vec![];
+        loop {
+            let value = self
+                .parse_expression()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing array literal.")))?;
+            values.push(value);
+
+            if self.consume_if_matches(TokenCategory::Comma)?.is_none() {
+                break;
+            }
+        }
+
+        self.consume_must_be(TokenCategory::BracketClose)?;
+        Ok(Some(Node {
+            value: Expression::Array(values),
+            position: token.position,
+        }))
+    }
+
     fn parse_identifier_or_call(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
         // identifier_or_call = identifier, [ "(", arguments, ")" ];
         let identifier = try_consume!(self, parse_identifier);
@@ -702,7 +1148,7 @@ impl<L: ILexer> Parser<L> {
     }
 
     fn parse_switch_statement(&mut self) -> Result<Option<Node<Statement>>, Box<dyn IError>> {
-        // switch_statement = "switch", "(", switch_expressions, ")", "{", {switch_case}, "}";
+        // switch_statement = "switch", "(", switch_expressions, ")", "{", {switch_case | default_case}, "}";
         let switch_token = try_consume_token!(self, TokenCategory::Switch);
 
         let _ = self.consume_must_be(TokenCategory::ParenOpen)?;
@@ -711,7 +1157,21 @@ impl<L: ILexer> Parser<L> {
         let _ = self.consume_must_be(TokenCategory::BraceOpen)?;
 
         let mut switch_cases: Vec<Node<SwitchCase>> = vec![];
+        let mut default = None;
         while self.current_token().category != TokenCategory::BraceClose {
+            // default_case = "default", "->", statement_block;
+            if self.consume_if_matches(TokenCategory::Default)?.is_some() {
+                if default.is_some() {
+                    return Err(self.create_parser_error(String::from("Switch can have only one default case.")));
+                }
+                let _ = self.consume_must_be(TokenCategory::Arrow)?;
+                let block = self
+                    .parse_statement_block()?
+                    .ok_or_else(|| self.create_parser_error(String::from("Couldn't create statement block while parsing default case.")))?;
+                default = Some(block);
+                continue;
+            }
+
             let switch_case = self
                 .parse_switch_case()?
                 .ok_or_else(|| self.create_parser_error(String::from("Couldn't create switch case while parsing switch statement.")))?;
@@ -724,6 +1184,7 @@ impl<L: ILexer> Parser<L> {
             value: Statement::Switch {
                 expressions: switch_expressions,
                 cases: switch_cases,
+                default,
             },
             position: switch_token.position,
         };
@@ -786,27 +1247,133 @@ impl<L: ILexer> Parser<L> {
         Ok(Some(node))
     }
 
-    fn parse_type(&mut self) -> Result<Option<Node<Type>>, Box<dyn IError>> {
-        let token = self.current_token();
+    fn parse_switch_value(&mut self) -> Result<Option<Node<Expression>>, Box<dyn IError>> {
+        // switch_value = "switch", "(", switch_expressions, ")", "{", switch_value_case | default_value_case, { ",", switch_value_case | default_value_case }, "}";
+        let switch_token = try_consume_token!(self, TokenCategory::Switch);
 
-        let result = match token.category {
-            TokenCategory::Bool => Type::Bool,
-            TokenCategory::String => Type::Str,
-            TokenCategory::I64 => Type::I64,
-            TokenCategory::F64 => Type::F64,
-            _ => return Ok(None),
-        };
+        let _ = self.consume_must_be(TokenCategory::ParenOpen)?;
+        let switch_expressions = self.parse_switch_expressions()?;
+        let _ = self.consume_must_be(TokenCategory::ParenClose)?;
+        let _ = self.consume_must_be(TokenCategory::BraceOpen)?;
 
-        let _ = self.next_token()?;
+        let mut switch_cases: Vec<Node<SwitchValueCase>> = vec![];
+        let mut default = None;
+        loop {
+            // default_value_case = "default", "->", expression;
+            if self.consume_if_matches(TokenCategory::Default)?.is_some() {
+                if default.is_some() {
+                    return Err(self.create_parser_error(String::from("Switch can have only one default case.")));
+                }
+                let _ = self.consume_must_be(TokenCategory::Arrow)?;
+                let value = self
+                    .parse_expression()?
+                    .ok_or_else(|| self.create_parser_error(String::from("Couldn't create value of default case.")))?;
+                default = Some(Box::new(value));
+            } else {
+                let switch_case = self
+                    .parse_switch_value_case()?
+                    .ok_or_else(|| self.create_parser_error(String::from("Couldn't create switch case while parsing switch expression.")))?;
+                switch_cases.push(switch_case);
+            }
 
-        Ok(Some(Node {
-            value: result,
-            position: token.position,
-        }))
+            if self.consume_if_matches(TokenCategory::Comma)?.is_none() {
+                break;
+            }
+        }
+        let _ = self.consume_must_be(TokenCategory::BraceClose)?;
+
+        let node = Node {
+            value: Expression::Switch {
+                expressions: switch_expressions,
+                cases: switch_cases,
+                default,
+            },
+            position: switch_token.position,
+        };
+        Ok(Some(node))
     }
 
-    fn parse_literal(&mut self) -> Result<Option<Node<Literal>>, Box<dyn IError>> {
-        let token = self.current_token();
+    fn parse_switch_value_case(&mut self) -> Result<Option<Node<SwitchValueCase>>, Box<dyn IError>> {
+        // switch_value_case = "(", expression, ")", "->", expression;
+        let paren_open_token = try_consume_token!(self, TokenCategory::ParenOpen);
+
+        let condition = self
+            .parse_expression()?
+            .ok_or_else(|| self.create_parser_error(String::from("Couldn't create expression while parsing switch case.")))?;
+
+        let _ = self.consume_must_be(TokenCategory::ParenClose)?;
+        let _ = self.consume_must_be(TokenCategory::Arrow)?;
+        let value = self
+            .parse_expression()?
+            .ok_or_else(|| self.create_parser_error(String::from("Couldn't create value of switch case.")))?;
+
+        let node = Node {
+            value: SwitchValueCase { condition, value },
+            position: paren_open_token.position,
+        };
+        Ok(Some(node))
+    }
+
+    fn parse_tuple_type(&mut self) -> Result<Option<Node<Type>>, Box<dyn IError>> {
+        // tuple_type = "(", type, ",", type, { ",", type }, ")";
+        let token = try_consume_token!(self, TokenCategory::ParenOpen);
+
+        let mut types = vec![];
+        loop {
+            let element_type = self
+                .parse_type()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create type while parsing tuple type.")))?;
+            types.push(element_type.value);
+
+            if self.consume_if_matches(TokenCategory::Comma)?.is_none() {
+                break;
+            }
+        }
+        if types.len() < 2 {
+            return Err(self.create_parser_error(String::from("Tuple type needs at least two types.")));
+        }
+
+        self.consume_must_be(TokenCategory::ParenClose)?;
+        Ok(Some(Node {
+            value: Type::Tuple(types),
+            position: token.position,
+        }))
+    }
+
+    fn parse_type(&mut self) -> Result<Option<Node<Type>>, Box<dyn IError>> {
+        // type = "i64" | "f64" | "bool" | "str" | "char" | "[", type, "]";
+        let token = self.current_token();
+
+        if self.consume_if_matches(TokenCategory::BracketOpen)?.is_some() {
+            let element_type = self
+                .parse_type()?
+                .ok_or_else(|| self.create_parser_error(String::from("Couldn't create element type while parsing array type.")))?;
+            self.consume_must_be(TokenCategory::BracketClose)?;
+            return Ok(Some(Node {
+                value: Type::Array(Box::new(element_type.value)),
+                position: token.position,
+            }));
+        }
+
+        let result = match token.category {
+            TokenCategory::Bool => Type::Bool,
+            TokenCategory::String => Type::Str,
+            TokenCategory::Char => Type::Char,
+            TokenCategory::I64 => Type::I64,
+            TokenCategory::F64 => Type::F64,
+            _ => return Ok(None),
+        };
+
+        let _ = self.next_token()?;
+
+        Ok(Some(Node {
+            value: result,
+            position: token.position,
+        }))
+    }
+
+    fn parse_literal(&mut self) -> Result<Option<Node<Literal>>, Box<dyn IError>> {
+        let token = self.current_token();
         let position = token.position;
 
         let literal = match (token.category, token.value) {
@@ -815,6 +1382,20 @@ impl<L: ILexer> Parser<L> {
             (TokenCategory::I64Value, TokenValue::I64(int)) => Literal::I64(int),
             (TokenCategory::F64Value, TokenValue::F64(float)) => Literal::F64(float),
             (TokenCategory::StringValue, TokenValue::String(string)) => Literal::String(string),
+            (TokenCategory::CharValue, TokenValue::Char(char)) => Literal::Char(char),
+            // literal tokens always have to carry a matching value, otherwise lexer is faulty
+            (category @ (TokenCategory::I64Value | TokenCategory::F64Value | TokenCategory::StringValue | TokenCategory::CharValue), value) => {
+                let expected = match category {
+                    TokenCategory::I64Value => Type::I64,
+                    TokenCategory::F64Value => Type::F64,
+                    TokenCategory::StringValue => Type::Str,
+                    _ => Type::Char,
+                };
+                return Err(self.create_parser_error(format!(
+                    "Wrong token value type for '{:?}' - given: '{:?}', expected: '{}'.",
+                    category, value, expected
+                )));
+            }
             _ => return Ok(None),
         };
 
@@ -988,6 +1569,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_statement_block_without_final_semicolon() {
+        let series = vec![
+            // { x = 5; return x }
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+            create_token(TokenCategory::Assign, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+            create_token(TokenCategory::Semicolon, TokenValue::Null),
+            create_token(TokenCategory::Return, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let expected = Block(vec![
+            test_node!(Statement::Assignment {
+                identifier: test_node!(String::from("x")),
+                value: test_node!(Expression::Literal(Literal::I64(5))),
+            }),
+            test_node!(Statement::Return(Some(test_node!(Expression::Variable(String::from("x")))))),
+        ]);
+
+        let options = ParserOptions {
+            allow_missing_final_semicolon: true,
+            ..Default::default()
+        };
+        let mut parser = Parser::with_options(LexerMock::new(series.clone()), options);
+        let node = parser.parse_statement_block().unwrap().unwrap();
+        assert_eq!(node.value, expected);
+
+        let mut parser = Parser::new(LexerMock::new(series));
+        assert!(parser.parse_statement_block().is_err());
+    }
+
+    #[test]
+    fn missing_semicolon_allowed_only_before_block_end() {
+        let series = vec![
+            // { x = 5 x = 5 }
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+            create_token(TokenCategory::Assign, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+            create_token(TokenCategory::Assign, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let options = ParserOptions {
+            allow_missing_final_semicolon: true,
+            ..Default::default()
+        };
+        let mut parser = Parser::with_options(LexerMock::new(series), options);
+        assert_eq!(
+            parser.parse_statement_block().err().unwrap().message(),
+            create_error_message(String::from("Unexpected token - 'x'. Expected ';'."))
+        );
+    }
+
     #[test]
     fn parse_statement_fail() {
         let series = vec![
@@ -1119,6 +1761,7 @@ mod tests {
                     condition: test_node!(Expression::Literal(Literal::True)),
                     block: test_node!(Block(vec![])),
                 })],
+                default: None,
             },
             Statement::Return(None),
             Statement::Break,
@@ -1126,6 +1769,7 @@ mod tests {
                 var_type: test_node!(Type::I64),
                 identifier: test_node!(String::from("a")),
                 value: Some(test_node!(Expression::Literal(Literal::I64(5)))),
+                is_const: false,
             },
         ];
 
@@ -1158,7 +1802,9 @@ mod tests {
 
         assert_eq!(
             parser.parse_function_declaration().err().unwrap().message(),
-            create_error_message(String::from("Bad return type: ,. Expected one of: 'i64', 'f64', 'bool', 'str', 'void'."))
+            create_error_message(String::from(
+                "Bad return type: ,. Expected one of: 'i64', 'f64', 'bool', 'str', 'char', 'void'."
+            ))
         );
     }
 
@@ -1189,6 +1835,22 @@ mod tests {
                 create_token(TokenCategory::BraceClose, TokenValue::Null),
                 create_token(TokenCategory::ETX, TokenValue::Null),
             ],
+            vec![
+                // fn add(): (i64, str) {}
+                create_token(TokenCategory::Fn, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("add"))),
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::Colon, TokenValue::Null),
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::I64, TokenValue::Null),
+                create_token(TokenCategory::Comma, TokenValue::Null),
+                create_token(TokenCategory::String, TokenValue::Null),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::BraceOpen, TokenValue::Null),
+                create_token(TokenCategory::BraceClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
         ];
 
         let expected = [
@@ -1204,6 +1866,12 @@ mod tests {
                 return_type: test_node!(Type::Void),
                 block: test_node!(Block(vec![])),
             },
+            FunctionDeclaration {
+                identifier: test_node!(String::from("add")),
+                parameters: vec![],
+                return_type: test_node!(Type::Tuple(vec![Type::I64, Type::Str])),
+                block: test_node!(Block(vec![])),
+            },
         ];
 
         for (idx, series) in token_series.iter().enumerate() {
@@ -1264,17 +1932,20 @@ mod tests {
                 passed_by: PassedBy::Value,
                 parameter_type: test_node!(Type::I64),
                 identifier: test_node!(String::from("x")),
+                default_value: None,
             })],
             vec![
                 test_node!(Parameter {
                     passed_by: PassedBy::Value,
                     parameter_type: test_node!(Type::I64),
                     identifier: test_node!(String::from("x")),
+                    default_value: None,
                 }),
                 test_node!(Parameter {
                     passed_by: PassedBy::Value,
                     parameter_type: test_node!(Type::I64),
                     identifier: test_node!(String::from("y")),
+                    default_value: None,
                 }),
             ],
         ];
@@ -1288,6 +1959,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_parameters_defaults_order() {
+        // i64 a = 1, i64 b
+        let series = vec![
+            create_token(TokenCategory::I64, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+            create_token(TokenCategory::Assign, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+            create_token(TokenCategory::Comma, TokenValue::Null),
+            create_token(TokenCategory::I64, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("b"))),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let mut parser = Parser::new(LexerMock::new(series));
+        assert_eq!(
+            parser.parse_parameters().err().unwrap().message(),
+            create_error_message(String::from("Parameter without default follows a parameter with a default."))
+        );
+
+        // i64 a, i64 b = 2
+        let series = vec![
+            create_token(TokenCategory::I64, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+            create_token(TokenCategory::Comma, TokenValue::Null),
+            create_token(TokenCategory::I64, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("b"))),
+            create_token(TokenCategory::Assign, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(2)),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let expected = vec![
+            test_node!(Parameter {
+                passed_by: PassedBy::Value,
+                parameter_type: test_node!(Type::I64),
+                identifier: test_node!(String::from("a")),
+                default_value: None,
+            }),
+            test_node!(Parameter {
+                passed_by: PassedBy::Value,
+                parameter_type: test_node!(Type::I64),
+                identifier: test_node!(String::from("b")),
+                default_value: Some(test_node!(Expression::Literal(Literal::I64(2)))),
+            }),
+        ];
+
+        let mut parser = Parser::new(LexerMock::new(series));
+        assert_eq!(parser.parse_parameters().unwrap(), expected);
+    }
+
     #[test]
     fn parse_parameter() {
         let token_series = [
@@ -1313,11 +2035,13 @@ mod tests {
                 passed_by: PassedBy::Reference,
                 parameter_type: test_node!(Type::I64),
                 identifier: test_node!(String::from("x")),
+                default_value: Some(test_node!(Expression::Literal(Literal::I64(0)))),
             },
             Parameter {
                 passed_by: PassedBy::Value,
                 parameter_type: test_node!(Type::I64),
                 identifier: test_node!(String::from("x")),
+                default_value: None,
             },
         ];
 
@@ -1420,6 +2144,40 @@ mod tests {
                 create_token(TokenCategory::BraceClose, TokenValue::Null),
                 create_token(TokenCategory::ETX, TokenValue::Null),
             ],
+            vec![
+                // for (;x < 5; step()) {}
+                create_token(TokenCategory::For, TokenValue::Null),
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::Semicolon, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::Less, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+                create_token(TokenCategory::Semicolon, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("step"))),
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::BraceOpen, TokenValue::Null),
+                create_token(TokenCategory::BraceClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // for (;x < 5; x += 1) {}
+                create_token(TokenCategory::For, TokenValue::Null),
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::Semicolon, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::Less, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+                create_token(TokenCategory::Semicolon, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::PlusAssign, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::BraceOpen, TokenValue::Null),
+                create_token(TokenCategory::BraceClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
         ];
 
         let expected = [
@@ -1428,6 +2186,7 @@ mod tests {
                     var_type: test_node!(Type::I64),
                     identifier: test_node!(String::from("x")),
                     value: Some(test_node!(Expression::Literal(Literal::I64(0)))),
+                    is_const: false,
                 }))),
                 condition: test_node!(Expression::Less(
                     Box::new(test_node!(Expression::Variable(String::from("x")))),
@@ -1451,6 +2210,33 @@ mod tests {
                 assignment: None,
                 block: test_node!(Block(vec![])),
             },
+            Statement::ForLoop {
+                declaration: None,
+                condition: test_node!(Expression::Less(
+                    Box::new(test_node!(Expression::Variable(String::from("x")))),
+                    Box::new(test_node!(Expression::Literal(Literal::I64(5)))),
+                )),
+                assignment: Some(Box::new(test_node!(Statement::FunctionCall {
+                    identifier: test_node!(String::from("step")),
+                    arguments: vec![],
+                }))),
+                block: test_node!(Block(vec![])),
+            },
+            Statement::ForLoop {
+                declaration: None,
+                condition: test_node!(Expression::Less(
+                    Box::new(test_node!(Expression::Variable(String::from("x")))),
+                    Box::new(test_node!(Expression::Literal(Literal::I64(5)))),
+                )),
+                assignment: Some(Box::new(test_node!(Statement::Assignment {
+                    identifier: test_node!(String::from("x")),
+                    value: test_node!(Expression::Addition(
+                        Box::new(test_node!(Expression::Variable(String::from("x")))),
+                        Box::new(test_node!(Expression::Literal(Literal::I64(1)))),
+                    )),
+                }))),
+                block: test_node!(Block(vec![])),
+            },
         ];
 
         for (idx, series) in token_series.iter().enumerate() {
@@ -1580,6 +2366,22 @@ mod tests {
                 create_token(TokenCategory::Comma, TokenValue::Null),
                 create_token(TokenCategory::ETX, TokenValue::Null),
             ],
+            vec![
+                // x[] = 5;
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // x[0](
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(0)),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
         ];
 
         let expected = [
@@ -1587,6 +2389,8 @@ mod tests {
             String::from("Unexpected token - 'ETX'. Expected ';'."),
             String::from("Unexpected token - 'ETX'. Expected ';'."),
             String::from("Couldn't create assignment or call."),
+            String::from("Couldn't create expression while parsing index."),
+            String::from("Couldn't create assignment or call."),
         ];
 
         for idx in 0..token_series.len() {
@@ -1619,6 +2423,28 @@ mod tests {
                 create_token(TokenCategory::Semicolon, TokenValue::Null),
                 create_token(TokenCategory::ETX, TokenValue::Null),
             ],
+            vec![
+                // x[1] = 5;
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::Assign, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+                create_token(TokenCategory::Semicolon, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // x[1] += 5;
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::PlusAssign, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+                create_token(TokenCategory::Semicolon, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
         ];
 
         let expected = [
@@ -1630,6 +2456,22 @@ mod tests {
                 identifier: test_node!(String::from("x")),
                 value: test_node!(Expression::Literal(Literal::I64(5))),
             },
+            Statement::IndexAssignment {
+                identifier: test_node!(String::from("x")),
+                indices: vec![test_node!(Expression::Literal(Literal::I64(1)))],
+                value: test_node!(Expression::Literal(Literal::I64(5))),
+            },
+            Statement::IndexAssignment {
+                identifier: test_node!(String::from("x")),
+                indices: vec![test_node!(Expression::Literal(Literal::I64(1)))],
+                value: test_node!(Expression::Addition(
+                    Box::new(test_node!(Expression::Index {
+                        value: Box::new(test_node!(Expression::Variable(String::from("x")))),
+                        index: Box::new(test_node!(Expression::Literal(Literal::I64(1)))),
+                    })),
+                    Box::new(test_node!(Expression::Literal(Literal::I64(5))))
+                )),
+            },
         ];
 
         for (idx, series) in token_series.iter().enumerate() {
@@ -1658,6 +2500,15 @@ mod tests {
                 create_token(TokenCategory::I64Value, TokenValue::I64(5)),
                 create_token(TokenCategory::ETX, TokenValue::Null),
             ],
+            vec![
+                // const i64 a = 5
+                create_token(TokenCategory::Const, TokenValue::Null),
+                create_token(TokenCategory::I64, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+                create_token(TokenCategory::Assign, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
         ];
 
         let expected = [
@@ -1665,11 +2516,19 @@ mod tests {
                 var_type: test_node!(Type::I64),
                 identifier: test_node!(String::from("a")),
                 value: None,
+                is_const: false,
+            },
+            Statement::Declaration {
+                var_type: test_node!(Type::I64),
+                identifier: test_node!(String::from("a")),
+                value: Some(test_node!(Expression::Literal(Literal::I64(5)))),
+                is_const: false,
             },
             Statement::Declaration {
                 var_type: test_node!(Type::I64),
                 identifier: test_node!(String::from("a")),
                 value: Some(test_node!(Expression::Literal(Literal::I64(5)))),
+                is_const: true,
             },
         ];
 
@@ -1682,6 +2541,120 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_const_declaration_fail() {
+        let token_series = [
+            vec![
+                // const a = 5
+                create_token(TokenCategory::Const, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+                create_token(TokenCategory::Assign, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // const i64 a
+                create_token(TokenCategory::Const, TokenValue::Null),
+                create_token(TokenCategory::I64, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+        ];
+
+        for series in token_series {
+            let mock_lexer = LexerMock::new(series);
+            let mut parser = Parser::new(mock_lexer);
+
+            assert!(parser.parse_declaration().is_err());
+        }
+    }
+
+    #[test]
+    fn parse_destructuring() {
+        let tokens = vec![
+            // (i64 a, str b) = f();
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::I64, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+            create_token(TokenCategory::Comma, TokenValue::Null),
+            create_token(TokenCategory::String, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("b"))),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::Assign, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("f"))),
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::Semicolon, TokenValue::Null),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let expected = Statement::Destructuring {
+            targets: vec![
+                (test_node!(Type::I64), test_node!(String::from("a"))),
+                (test_node!(Type::Str), test_node!(String::from("b"))),
+            ],
+            value: test_node!(Expression::FunctionCall {
+                identifier: test_node!(String::from("f")),
+                arguments: vec![],
+            }),
+        };
+
+        let mock_lexer = LexerMock::new(tokens);
+        let mut parser = Parser::new(mock_lexer);
+
+        let node = parser.parse_destructuring().unwrap().unwrap();
+        assert_eq!(node.value, expected);
+    }
+
+    #[test]
+    fn parse_destructuring_fail() {
+        let token_series = [
+            vec![
+                // (i64 a) = f();
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::I64, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::Assign, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("f"))),
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::Semicolon, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // (i64 a, b) = f();
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::I64, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+                create_token(TokenCategory::Comma, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("b"))),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::Assign, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("f"))),
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::Semicolon, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+        ];
+
+        let expected = [
+            "Destructuring needs at least two variables.",
+            "Couldn't create type while parsing destructuring.",
+        ];
+
+        for (idx, series) in token_series.iter().enumerate() {
+            let mock_lexer = LexerMock::new(series.to_vec());
+            let mut parser = Parser::new(mock_lexer);
+
+            assert_eq!(
+                parser.parse_destructuring().err().unwrap().message(),
+                create_error_message(String::from(expected[idx]))
+            );
+        }
+    }
+
     #[test]
     fn parse_return_statement_fail() {
         let token_series = [
@@ -1741,6 +2714,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_fallthrough_statement() {
+        let tokens = vec![
+            // fallthrough;
+            create_token(TokenCategory::Fallthrough, TokenValue::Null),
+            create_token(TokenCategory::Semicolon, TokenValue::Null),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let mock_lexer = LexerMock::new(tokens);
+        let mut parser = Parser::new(mock_lexer);
+
+        let node = parser.parse_statement().unwrap().unwrap();
+        assert_eq!(node.value, Statement::Fallthrough);
+    }
+
     #[test]
     fn parse_break_statement_fail() {
         let series = vec![
@@ -1792,6 +2781,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_arguments_fail() {
+        let token_series = [
+            vec![
+                // x = 1, 2
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::Assign, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+                create_token(TokenCategory::Comma, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(2)),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // x =
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::Assign, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+        ];
+
+        let expected = [
+            "Positional argument cannot follow a named argument.",
+            "Couldn't create expression for named argument 'x'.",
+        ];
+
+        for (series, message) in token_series.iter().zip(expected) {
+            let mock_lexer = LexerMock::new(series.to_vec());
+            let mut parser = Parser::new(mock_lexer);
+
+            assert_eq!(
+                parser.parse_arguments().err().unwrap().message(),
+                create_error_message(String::from(message))
+            );
+        }
+    }
+
     #[test]
     fn parse_arguments() {
         let token_series = [
@@ -1818,16 +2843,19 @@ mod tests {
             vec![],
             vec![test_node!(Argument {
                 value: test_node!(Expression::Literal(Literal::I64(1))),
-                passed_by: PassedBy::Value
+                passed_by: PassedBy::Value,
+                name: None,
             })],
             vec![
                 test_node!(Argument {
                     value: test_node!(Expression::Literal(Literal::I64(1))),
-                    passed_by: PassedBy::Reference
+                    passed_by: PassedBy::Reference,
+                    name: None,
                 }),
                 test_node!(Argument {
                     value: test_node!(Expression::Literal(Literal::I64(2))),
-                    passed_by: PassedBy::Value
+                    passed_by: PassedBy::Value,
+                    name: None,
                 }),
             ],
         ];
@@ -1855,16 +2883,43 @@ mod tests {
                 create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
                 create_token(TokenCategory::ETX, TokenValue::Null),
             ],
+            vec![
+                // x = 1
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::Assign, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // x = &y
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::Assign, TokenValue::Null),
+                create_token(TokenCategory::Reference, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("y"))),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
         ];
 
         let expected = [
             Argument {
                 value: test_node!(Expression::Literal(Literal::I64(1))),
                 passed_by: PassedBy::Value,
+                name: None,
             },
             Argument {
                 value: test_node!(Expression::Variable(String::from("x"))),
                 passed_by: PassedBy::Reference,
+                name: None,
+            },
+            Argument {
+                value: test_node!(Expression::Literal(Literal::I64(1))),
+                passed_by: PassedBy::Value,
+                name: Some(test_node!(String::from("x"))),
+            },
+            Argument {
+                value: test_node!(Expression::Variable(String::from("y"))),
+                passed_by: PassedBy::Reference,
+                name: Some(test_node!(String::from("x"))),
             },
         ];
 
@@ -1878,14 +2933,46 @@ mod tests {
     }
 
     #[test]
-    fn parse_expression() {
+    fn parse_expression() {
+        let tokens = vec![
+            // a || b || c
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+            create_token(TokenCategory::Or, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("b"))),
+            create_token(TokenCategory::Or, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("c"))),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let mock_lexer = LexerMock::new(tokens);
+        let mut parser = Parser::new(mock_lexer);
+
+        let node = parser.parse_expression().unwrap().unwrap();
+        assert_eq!(
+            node,
+            test_node!(Expression::Alternative(
+                Box::new(test_node!(Expression::Alternative(
+                    Box::new(test_node!(Expression::Variable(String::from("a")))),
+                    Box::new(test_node!(Expression::Variable(String::from("b")))),
+                ))),
+                Box::new(test_node!(Expression::Variable(String::from("c")))),
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_ternary_expression() {
         let tokens = vec![
-            // a || b || c
+            // a ? 1 : b ? 2 : 3
             create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
-            create_token(TokenCategory::Or, TokenValue::Null),
+            create_token(TokenCategory::QuestionMark, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+            create_token(TokenCategory::Colon, TokenValue::Null),
             create_token(TokenCategory::Identifier, TokenValue::String(String::from("b"))),
-            create_token(TokenCategory::Or, TokenValue::Null),
-            create_token(TokenCategory::Identifier, TokenValue::String(String::from("c"))),
+            create_token(TokenCategory::QuestionMark, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(2)),
+            create_token(TokenCategory::Colon, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(3)),
             create_token(TokenCategory::ETX, TokenValue::Null),
         ];
 
@@ -1895,16 +2982,35 @@ mod tests {
         let node = parser.parse_expression().unwrap().unwrap();
         assert_eq!(
             node,
-            test_node!(Expression::Alternative(
-                Box::new(test_node!(Expression::Alternative(
-                    Box::new(test_node!(Expression::Variable(String::from("a")))),
-                    Box::new(test_node!(Expression::Variable(String::from("b")))),
-                ))),
-                Box::new(test_node!(Expression::Variable(String::from("c")))),
-            ))
+            test_node!(Expression::Ternary {
+                condition: Box::new(test_node!(Expression::Variable(String::from("a")))),
+                if_true: Box::new(test_node!(Expression::Literal(Literal::I64(1)))),
+                if_false: Box::new(test_node!(Expression::Ternary {
+                    condition: Box::new(test_node!(Expression::Variable(String::from("b")))),
+                    if_true: Box::new(test_node!(Expression::Literal(Literal::I64(2)))),
+                    if_false: Box::new(test_node!(Expression::Literal(Literal::I64(3)))),
+                })),
+            })
         );
     }
 
+    #[test]
+    fn parse_ternary_expression_without_colon() {
+        let tokens = vec![
+            // a ? 1 2
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+            create_token(TokenCategory::QuestionMark, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+            create_token(TokenCategory::I64Value, TokenValue::I64(2)),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let mock_lexer = LexerMock::new(tokens);
+        let mut parser = Parser::new(mock_lexer);
+
+        assert!(parser.parse_expression().is_err());
+    }
+
     #[test]
     fn parse_concatenation_term() {
         let tokens = vec![
@@ -2022,6 +3128,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn binary_expressions_point_at_operator() {
+        let at = |category: TokenCategory, value: TokenValue, column: u32| Token {
+            category,
+            value,
+            position: Position {
+                line: 1,
+                column,
+                offset: column as usize - 1,
+            },
+        };
+        let operators = [TokenCategory::Less, TokenCategory::Plus, TokenCategory::Multiply, TokenCategory::And];
+
+        for operator in operators {
+            // 1 op 2
+            let tokens = vec![
+                at(TokenCategory::I64Value, TokenValue::I64(1), 1),
+                at(operator, TokenValue::Null, 3),
+                at(TokenCategory::I64Value, TokenValue::I64(2), 5),
+                at(TokenCategory::ETX, TokenValue::Null, 6),
+            ];
+            let mock_lexer = LexerMock::new(tokens);
+            let mut parser = Parser::new(mock_lexer);
+
+            let node = parser.parse_expression().unwrap().unwrap();
+            assert_eq!(node.position.column, 3);
+        }
+    }
+
     #[test]
     fn parse_additive_term() {
         // 5 + 2.0 - x
@@ -2050,6 +3185,44 @@ mod tests {
         )
     }
 
+    #[test]
+    fn parse_bitwise_or_term() {
+        // a | b ^ c & d << 1
+        let tokens = vec![
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("a"))),
+            create_token(TokenCategory::BitOr, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("b"))),
+            create_token(TokenCategory::BitXor, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("c"))),
+            create_token(TokenCategory::Reference, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("d"))),
+            create_token(TokenCategory::ShiftLeft, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let mock_lexer = LexerMock::new(tokens);
+        let mut parser = Parser::new(mock_lexer);
+
+        let node = parser.parse_bitwise_or_term().unwrap().unwrap();
+        assert_eq!(
+            node,
+            test_node!(Expression::BitwiseOr(
+                Box::new(test_node!(Expression::Variable(String::from("a")))),
+                Box::new(test_node!(Expression::BitwiseXor(
+                    Box::new(test_node!(Expression::Variable(String::from("b")))),
+                    Box::new(test_node!(Expression::BitwiseAnd(
+                        Box::new(test_node!(Expression::Variable(String::from("c")))),
+                        Box::new(test_node!(Expression::ShiftLeft(
+                            Box::new(test_node!(Expression::Variable(String::from("d")))),
+                            Box::new(test_node!(Expression::Literal(Literal::I64(1))))
+                        )))
+                    )))
+                )))
+            ))
+        )
+    }
+
     #[test]
     fn parse_multiplicative_term() {
         let tokens = vec![
@@ -2127,6 +3300,12 @@ mod tests {
                 create_token(TokenCategory::I64Value, TokenValue::I64(5)),
                 create_token(TokenCategory::ETX, TokenValue::Null),
             ],
+            vec![
+                // +5
+                create_token(TokenCategory::Plus, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
             vec![
                 // 5
                 create_token(TokenCategory::I64Value, TokenValue::I64(5)),
@@ -2137,6 +3316,7 @@ mod tests {
         let expected = [
             Expression::BooleanNegation(Box::new(test_node!(Expression::Literal(Literal::True)))),
             Expression::ArithmeticNegation(Box::new(test_node!(Expression::Literal(Literal::I64(5))))),
+            Expression::UnaryPlus(Box::new(test_node!(Expression::Literal(Literal::I64(5))))),
             Expression::Literal(Literal::I64(5)),
         ];
 
@@ -2171,15 +3351,59 @@ mod tests {
                 create_token(TokenCategory::Identifier, TokenValue::String(String::from("print"))),
                 create_token(TokenCategory::ETX, TokenValue::Null),
             ],
+            vec![
+                // (5, x)
+                create_token(TokenCategory::ParenOpen, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+                create_token(TokenCategory::Comma, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::ParenClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // [5, x]
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(5)),
+                create_token(TokenCategory::Comma, TokenValue::Null),
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // x[1][0]
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::I64Value, TokenValue::I64(0)),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
         ];
 
         let expected = [
-            Expression::Addition(
+            Expression::Parenthesized(Box::new(test_node!(Expression::Addition(
                 Box::new(test_node!(Expression::Literal(Literal::I64(5)))),
                 Box::new(test_node!(Expression::Literal(Literal::I64(2)))),
-            ),
+            )))),
             Expression::Literal(Literal::I64(5)),
             Expression::Variable(String::from("print")),
+            Expression::Tuple(vec![
+                test_node!(Expression::Literal(Literal::I64(5))),
+                test_node!(Expression::Variable(String::from("x"))),
+            ]),
+            Expression::Array(vec![
+                test_node!(Expression::Literal(Literal::I64(5))),
+                test_node!(Expression::Variable(String::from("x"))),
+            ]),
+            Expression::Index {
+                value: Box::new(test_node!(Expression::Index {
+                    value: Box::new(test_node!(Expression::Variable(String::from("x")))),
+                    index: Box::new(test_node!(Expression::Literal(Literal::I64(1)))),
+                })),
+                index: Box::new(test_node!(Expression::Literal(Literal::I64(0)))),
+            },
         ];
 
         for (idx, series) in token_series.iter().enumerate() {
@@ -2211,6 +3435,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_array_fail() {
+        let token_series = [
+            vec![
+                // []
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+            vec![
+                // x[]
+                create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
+        ];
+
+        let expected = [
+            "Couldn't create expression while parsing array literal.",
+            "Couldn't create expression while parsing index.",
+        ];
+
+        for (idx, series) in token_series.iter().enumerate() {
+            let mock_lexer = LexerMock::new(series.to_vec());
+            let mut parser = Parser::new(mock_lexer);
+
+            assert_eq!(
+                parser.parse_factor().err().unwrap().message(),
+                create_error_message(String::from(expected[idx]))
+            );
+        }
+    }
+
     #[test]
     fn parse_identifier_or_call_fail() {
         let token_series = [
@@ -2297,6 +3555,7 @@ mod tests {
                 arguments: vec![Box::new(test_node!(Argument {
                     value: test_node!(Expression::Literal(Literal::I64(5))),
                     passed_by: PassedBy::Value,
+                    name: None,
                 }))],
             },
             Expression::FunctionCall {
@@ -2305,10 +3564,12 @@ mod tests {
                     Box::new(test_node!(Argument {
                         value: test_node!(Expression::Literal(Literal::I64(5))),
                         passed_by: PassedBy::Reference,
+                        name: None,
                     })),
                     Box::new(test_node!(Argument {
                         value: test_node!(Expression::Variable(String::from("x"))),
                         passed_by: PassedBy::Value,
+                        name: None,
                     })),
                 ],
             },
@@ -2353,6 +3614,48 @@ mod tests {
                 condition: test_node!(Expression::Literal(Literal::True)),
                 block: test_node!(Block(vec![])),
             })],
+            default: None,
+        };
+
+        let mock_lexer = LexerMock::new(series);
+        let mut parser = Parser::new(mock_lexer);
+
+        let node = parser.parse_switch_statement().unwrap().unwrap();
+        assert_eq!(node.value, expected);
+    }
+
+    #[test]
+    fn parse_switch_default_case() {
+        let series = vec![
+            // switch() {
+            //      default -> {}
+            //      (true) -> {}
+            // }
+            create_token(TokenCategory::Switch, TokenValue::Null),
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::Default, TokenValue::Null),
+            create_token(TokenCategory::Arrow, TokenValue::Null),
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::True, TokenValue::Null),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::Arrow, TokenValue::Null),
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let expected = Statement::Switch {
+            expressions: vec![],
+            cases: vec![test_node!(SwitchCase {
+                condition: test_node!(Expression::Literal(Literal::True)),
+                block: test_node!(Block(vec![])),
+            })],
+            default: Some(test_node!(Block(vec![]))),
         };
 
         let mock_lexer = LexerMock::new(series);
@@ -2362,6 +3665,121 @@ mod tests {
         assert_eq!(node.value, expected);
     }
 
+    #[test]
+    fn parse_switch_duplicate_default_case() {
+        let series = vec![
+            // switch() {
+            //      default -> {}
+            //      default -> {}
+            // }
+            create_token(TokenCategory::Switch, TokenValue::Null),
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::Default, TokenValue::Null),
+            create_token(TokenCategory::Arrow, TokenValue::Null),
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::Default, TokenValue::Null),
+            create_token(TokenCategory::Arrow, TokenValue::Null),
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let mock_lexer = LexerMock::new(series);
+        let mut parser = Parser::new(mock_lexer);
+
+        assert!(parser
+            .parse_switch_statement()
+            .err()
+            .unwrap()
+            .message()
+            .starts_with("Switch can have only one default case."));
+    }
+
+    #[test]
+    fn parse_switch_value() {
+        let series = vec![
+            // switch(x) {
+            //      (true) -> 1,
+            //      (false) -> 2
+            // }
+            create_token(TokenCategory::Switch, TokenValue::Null),
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::Identifier, TokenValue::String(String::from("x"))),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::True, TokenValue::Null),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::Arrow, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(1)),
+            create_token(TokenCategory::Comma, TokenValue::Null),
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::False, TokenValue::Null),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::Arrow, TokenValue::Null),
+            create_token(TokenCategory::I64Value, TokenValue::I64(2)),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let expected = Expression::Switch {
+            expressions: vec![test_node!(SwitchExpression {
+                expression: test_node!(Expression::Variable(String::from("x"))),
+                alias: None,
+            })],
+            cases: vec![
+                test_node!(SwitchValueCase {
+                    condition: test_node!(Expression::Literal(Literal::True)),
+                    value: test_node!(Expression::Literal(Literal::I64(1))),
+                }),
+                test_node!(SwitchValueCase {
+                    condition: test_node!(Expression::Literal(Literal::False)),
+                    value: test_node!(Expression::Literal(Literal::I64(2))),
+                }),
+            ],
+            default: None,
+        };
+
+        let mock_lexer = LexerMock::new(series);
+        let mut parser = Parser::new(mock_lexer);
+
+        let node = parser.parse_expression().unwrap().unwrap();
+        assert_eq!(node.value, expected);
+    }
+
+    #[test]
+    fn parse_switch_value_fail() {
+        let series = vec![
+            // switch() { (true) -> {} }
+            create_token(TokenCategory::Switch, TokenValue::Null),
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::ParenOpen, TokenValue::Null),
+            create_token(TokenCategory::True, TokenValue::Null),
+            create_token(TokenCategory::ParenClose, TokenValue::Null),
+            create_token(TokenCategory::Arrow, TokenValue::Null),
+            create_token(TokenCategory::BraceOpen, TokenValue::Null),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::BraceClose, TokenValue::Null),
+            create_token(TokenCategory::ETX, TokenValue::Null),
+        ];
+
+        let mock_lexer = LexerMock::new(series);
+        let mut parser = Parser::new(mock_lexer);
+
+        assert!(parser
+            .parse_expression()
+            .err()
+            .unwrap()
+            .message()
+            .starts_with("Couldn't create value of switch case."));
+    }
+
     #[test]
     fn parse_switch_expressions_fail() {
         let series = vec![
@@ -2508,9 +3926,24 @@ mod tests {
                 create_token(TokenCategory::Bool, TokenValue::Null),
                 create_token(TokenCategory::ETX, TokenValue::Null),
             ],
+            vec![
+                // [[i64]]
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::BracketOpen, TokenValue::Null),
+                create_token(TokenCategory::I64, TokenValue::Null),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::BracketClose, TokenValue::Null),
+                create_token(TokenCategory::ETX, TokenValue::Null),
+            ],
         ];
 
-        let expected_types = [Type::I64, Type::F64, Type::Str, Type::Bool];
+        let expected_types = [
+            Type::I64,
+            Type::F64,
+            Type::Str,
+            Type::Bool,
+            Type::Array(Box::new(Type::Array(Box::new(Type::I64)))),
+        ];
 
         for (idx, series) in token_series.iter().enumerate() {
             let mock_lexer = LexerMock::new(series.to_vec());
@@ -2573,6 +4006,44 @@ mod tests {
         assert_eq!(literal.value, Literal::F64(5.0));
     }
 
+    #[test]
+    fn parse_literal_bad_value_type() {
+        let data = [
+            (
+                TokenCategory::I64Value,
+                TokenValue::F64(1.0),
+                "Wrong token value type for 'i64 value' - given: 'F64(1.0)', expected: 'i64'.",
+            ),
+            (
+                TokenCategory::F64Value,
+                TokenValue::I64(1),
+                "Wrong token value type for 'f64 value' - given: 'I64(1)', expected: 'f64'.",
+            ),
+            (
+                TokenCategory::StringValue,
+                TokenValue::Null,
+                "Wrong token value type for 'str value' - given: 'Null', expected: 'str'.",
+            ),
+            (
+                TokenCategory::CharValue,
+                TokenValue::String(String::from("a")),
+                "Wrong token value type for 'char value' - given: 'String(\"a\")', expected: 'char'.",
+            ),
+        ];
+
+        for (category, value, message) in data {
+            let tokens = vec![create_token(category, value), create_token(TokenCategory::ETX, TokenValue::Null)];
+
+            let mock_lexer = LexerMock::new(tokens);
+            let mut parser = Parser::new(mock_lexer);
+
+            assert_eq!(
+                parser.parse_literal().err().unwrap().message(),
+                create_error_message(String::from(message))
+            );
+        }
+    }
+
     #[test]
     fn parse_identifier() {
         let tokens = vec![
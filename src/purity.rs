@@ -0,0 +1,277 @@
+use std::collections::HashSet;
+
+use crate::ast::{Block, Expression, FunctionDeclaration, Node, PassedBy, Program, Statement};
+
+// Functions can only read global variables, so a function without reference parameters
+// can only cause side effects through input / output, shared arrays or by calling a function that does.
+// Globals may change between calls, so functions reading them are not pure either.
+pub fn find_pure_functions(program: &Program) -> HashSet<String> {
+    let globals = global_variables(program);
+    let mut pure_functions: HashSet<String> = program
+        .functions
+        .iter()
+        .filter(|(_, function)| function.value.parameters.iter().all(|p| p.value.passed_by == PassedBy::Value))
+        .filter(|(_, function)| !reads_globals(&function.value, &globals))
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    // recursive functions stay pure unless they call something impure
+    loop {
+        let impure: Vec<String> = pure_functions
+            .iter()
+            .filter(|name| {
+                let mut uses = Uses::default();
+                collect_uses(&program.functions[*name].value, &mut uses);
+                uses.functions.iter().any(|callee| !is_pure_call(program, &pure_functions, callee))
+            })
+            .cloned()
+            .collect();
+
+        if impure.is_empty() {
+            return pure_functions;
+        }
+        for name in impure {
+            pure_functions.remove(&name);
+        }
+    }
+}
+
+// variables declared by top-level statements, the only ones visible in functions
+fn global_variables(program: &Program) -> HashSet<&str> {
+    let mut globals = HashSet::new();
+    for statement in &program.statements {
+        match &statement.value {
+            Statement::Declaration { identifier, .. } => {
+                globals.insert(identifier.value.as_str());
+            }
+            Statement::Destructuring { targets, .. } => globals.extend(targets.iter().map(|(_, identifier)| identifier.value.as_str())),
+            _ => {}
+        }
+    }
+    globals
+}
+
+// a local variable with the name of a global one also counts, telling them apart needs the scopes
+fn reads_globals(function: &FunctionDeclaration, globals: &HashSet<&str>) -> bool {
+    let mut uses = Uses::default();
+    collect_uses(function, &mut uses);
+    uses.variables
+        .iter()
+        .any(|name| globals.contains(name) && !function.parameters.iter().any(|parameter| parameter.value.identifier.value == *name))
+}
+
+fn is_pure_call(program: &Program, pure_functions: &HashSet<String>, name: &str) -> bool {
+    match program.std_functions.get(name) {
+        Some(std_function) => std_function.is_pure,
+        None => pure_functions.contains(name),
+    }
+}
+
+// functions called and variables read or written by a function
+#[derive(Default)]
+struct Uses<'a> {
+    functions: Vec<&'a str>,
+    variables: Vec<&'a str>,
+}
+
+fn collect_uses<'a>(function: &'a FunctionDeclaration, uses: &mut Uses<'a>) {
+    for parameter in &function.parameters {
+        if let Some(default_value) = &parameter.value.default_value {
+            collect_in_expression(default_value, uses);
+        }
+    }
+    collect_in_block(&function.block, uses);
+}
+
+fn collect_in_block<'a>(block: &'a Node<Block>, uses: &mut Uses<'a>) {
+    for statement in &block.value.0 {
+        collect_in_statement(statement, uses);
+    }
+}
+
+fn collect_in_statement<'a>(statement: &'a Node<Statement>, uses: &mut Uses<'a>) {
+    match &statement.value {
+        Statement::FunctionCall { identifier, arguments } => {
+            uses.functions.push(&identifier.value);
+            for argument in arguments {
+                collect_in_expression(&argument.value.value, uses);
+            }
+        }
+        Statement::Declaration { value, .. } | Statement::Return(value) => {
+            if let Some(value) = value {
+                collect_in_expression(value, uses);
+            }
+        }
+        Statement::Assignment { identifier, value } => {
+            uses.variables.push(&identifier.value);
+            collect_in_expression(value, uses);
+        }
+        Statement::Destructuring { value, .. } => collect_in_expression(value, uses),
+        Statement::IndexAssignment { identifier, indices, value } => {
+            uses.variables.push(&identifier.value);
+            for index in indices {
+                collect_in_expression(index, uses);
+            }
+            collect_in_expression(value, uses);
+        }
+        Statement::Conditional {
+            condition,
+            if_block,
+            else_block,
+        } => {
+            collect_in_expression(condition, uses);
+            collect_in_block(if_block, uses);
+            if let Some(else_block) = else_block {
+                collect_in_block(else_block, uses);
+            }
+        }
+        Statement::ForLoop {
+            declaration,
+            condition,
+            assignment,
+            block,
+        } => {
+            if let Some(declaration) = declaration {
+                collect_in_statement(declaration, uses);
+            }
+            collect_in_expression(condition, uses);
+            if let Some(assignment) = assignment {
+                collect_in_statement(assignment, uses);
+            }
+            collect_in_block(block, uses);
+        }
+        Statement::Switch { expressions, cases, default } => {
+            for expression in expressions {
+                collect_in_expression(&expression.value.expression, uses);
+            }
+            for case in cases {
+                collect_in_expression(&case.value.condition, uses);
+                collect_in_block(&case.value.block, uses);
+            }
+            if let Some(default) = default {
+                collect_in_block(default, uses);
+            }
+        }
+        Statement::Break | Statement::Fallthrough => {}
+    }
+}
+
+fn collect_in_expression<'a>(expression: &'a Node<Expression>, uses: &mut Uses<'a>) {
+    match &expression.value {
+        Expression::Alternative(lhs, rhs)
+        | Expression::Concatenation(lhs, rhs)
+        | Expression::Greater(lhs, rhs)
+        | Expression::GreaterEqual(lhs, rhs)
+        | Expression::Less(lhs, rhs)
+        | Expression::LessEqual(lhs, rhs)
+        | Expression::Equal(lhs, rhs)
+        | Expression::NotEqual(lhs, rhs)
+        | Expression::Addition(lhs, rhs)
+        | Expression::Subtraction(lhs, rhs)
+        | Expression::Multiplication(lhs, rhs)
+        | Expression::Division(lhs, rhs)
+        | Expression::BitwiseOr(lhs, rhs)
+        | Expression::BitwiseXor(lhs, rhs)
+        | Expression::BitwiseAnd(lhs, rhs)
+        | Expression::ShiftLeft(lhs, rhs)
+        | Expression::ShiftRight(lhs, rhs)
+        | Expression::Index { value: lhs, index: rhs } => {
+            collect_in_expression(lhs, uses);
+            collect_in_expression(rhs, uses);
+        }
+        Expression::Ternary {
+            condition,
+            if_true,
+            if_false,
+        } => {
+            collect_in_expression(condition, uses);
+            collect_in_expression(if_true, uses);
+            collect_in_expression(if_false, uses);
+        }
+        Expression::Parenthesized(value)
+        | Expression::BooleanNegation(value)
+        | Expression::ArithmeticNegation(value)
+        | Expression::UnaryPlus(value)
+        | Expression::Casting { value, .. } => collect_in_expression(value, uses),
+        Expression::FunctionCall { identifier, arguments } => {
+            uses.functions.push(&identifier.value);
+            for argument in arguments {
+                collect_in_expression(&argument.value.value, uses);
+            }
+        }
+        Expression::Tuple(values) | Expression::Array(values) => {
+            for value in values {
+                collect_in_expression(value, uses);
+            }
+        }
+        Expression::Switch { expressions, cases, default } => {
+            for expression in expressions {
+                collect_in_expression(&expression.value.expression, uses);
+            }
+            for case in cases {
+                collect_in_expression(&case.value.condition, uses);
+                collect_in_expression(&case.value.value, uses);
+            }
+            if let Some(default) = default {
+                collect_in_expression(default, uses);
+            }
+        }
+        Expression::Variable(name) => uses.variables.push(name),
+        Expression::Literal(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use crate::{
+        ast::Type,
+        errors::IError,
+        lazy_stream_reader::LazyStreamReader,
+        lexer::{Lexer, LexerOptions},
+        parser::{IParser, Parser},
+    };
+
+    use super::*;
+
+    fn on_warning(_err: Box<dyn IError>) {}
+
+    fn parse(text: &str) -> Program {
+        let options = LexerOptions {
+            max_comment_length: 100,
+            max_identifier_length: 100,
+            max_string_length: 1000,
+            strict_strings: false,
+        };
+        let reader = LazyStreamReader::new(BufReader::new(text.as_bytes()));
+        let lexer = Lexer::new(reader, options, on_warning);
+        let mut parser = Parser::new(lexer);
+        parser.parse().unwrap()
+    }
+
+    #[test]
+    fn finds_pure_functions() {
+        let code = r#"
+            fn square(i64 x): i64 { return x * x; }
+            fn fact(i64 n): i64 { if (n <= 1) { return 1; } return n * fact(n - 1); }
+            fn uses_std(i64 x): i64 { return abs(square(x)); }
+            fn logs(i64 x): i64 { println(x); return x; }
+            fn calls_logging(i64 x): i64 { return logs(x) + 1; }
+            fn by_reference(&i64 x): void { x = 1; }
+            fn calls_by_reference(): i64 { i64 y = 0; by_reference(&y); return y; }
+            fn reads_in_default(i64 x = input_int()): i64 { return x; }
+            fn pushes([i64] values): i64 { push(values, 1); return len(values); }
+            fn reads_global(i64 x): i64 { return x + offset; }
+            fn shadows_global(i64 offset): i64 { return offset; }
+            fn calls_host(i64 x): i64 { return host(x); }
+            i64 offset = 1;
+        "#;
+        let mut program = parse(code);
+        program.register_std_function("host", vec![Type::I64], |params| Ok(Some(params[0].borrow().clone())));
+
+        let mut pure: Vec<String> = find_pure_functions(&program).into_iter().collect();
+        pure.sort();
+        assert_eq!(pure, vec!["fact", "shadows_global", "square", "uses_std"]);
+    }
+}
@@ -1,33 +1,50 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use crate::{
     errors::{ErrorSeverity, ScopeManagerError},
     value::Value,
 };
 
+const DEFAULT_SCOPE_CAPACITY: usize = 8;
+
 #[derive(Debug, Clone)]
 pub struct ScopeManager<'a> {
     // always has at least 1 scope
     scopes: Vec<Scope<'a>>,
+    // popped scopes are cleared and kept here, so their allocations can be reused
+    free_scopes: Vec<Scope<'a>>,
 }
 
 impl<'a> ScopeManager<'a> {
     pub fn new() -> Self {
-        let root_scope = Scope::new();
-        ScopeManager { scopes: vec![root_scope] }
+        Self::with_capacity(DEFAULT_SCOPE_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut scopes = Vec::with_capacity(capacity.max(1));
+        scopes.push(Scope::new());
+        ScopeManager { scopes, free_scopes: vec![] }
     }
 
     pub fn push_scope(&mut self) {
-        let new_scope = Scope::new();
+        let new_scope = self.free_scopes.pop().unwrap_or_else(Scope::new);
         self.scopes.push(new_scope);
     }
 
     pub fn pop_scope(&mut self) {
-        self.scopes.pop();
+        if let Some(mut scope) = self.scopes.pop() {
+            scope.variables.clear();
+            scope.constants.clear();
+            self.free_scopes.push(scope);
+        }
     }
 
     pub fn get_variable(&self, searched: &'a str) -> Result<&Rc<RefCell<Value>>, ScopeManagerError> {
-        for scope in &self.scopes {
+        for scope in self.scopes.iter().rev() {
             if let Some(var) = scope.get_variable(searched) {
                 return Ok(var);
             }
@@ -39,8 +56,26 @@ impl<'a> ScopeManager<'a> {
         ))
     }
 
+    // variables declared in the root scope, without the ones from nested blocks
+    pub fn get_global_variable(&self, searched: &'a str) -> Option<&Rc<RefCell<Value>>> {
+        self.scopes.first().and_then(|scope| scope.get_variable(searched))
+    }
+
+    // name, value and whether it's a constant of every variable in the root scope
+    pub fn global_variables(&self) -> Vec<(&'a str, Rc<RefCell<Value>>, bool)> {
+        match self.scopes.first() {
+            Some(scope) => scope
+                .variables
+                .iter()
+                .map(|(name, value)| (*name, Rc::clone(value), scope.constants.contains(name)))
+                .collect(),
+            None => vec![],
+        }
+    }
+
     pub fn assign_variable(&mut self, name: &'a str, value: Rc<RefCell<Value>>) -> Result<(), ScopeManagerError> {
-        for scope in &mut self.scopes {
+        // the innermost binding wins, so a shadowing variable is assigned instead of the outer one
+        for scope in self.scopes.iter_mut().rev() {
             if let Some(_) = scope.get_variable(name) {
                 return scope.assign_variable(name, value);
             }
@@ -53,16 +88,9 @@ impl<'a> ScopeManager<'a> {
     }
 
     pub fn declare_variable(&mut self, name: &'a str, value: Rc<RefCell<Value>>) -> Result<(), ScopeManagerError> {
-        if self.get_variable(name).is_ok() {
-            return Err(ScopeManagerError::new(
-                ErrorSeverity::HIGH,
-                format!("Cannot redeclare variable '{}'.", name),
-            ));
-        }
-
+        // only the innermost scope is checked, so nested blocks can shadow outer variables
         if let Some(last_scope) = self.scopes.last_mut() {
-            let _ = last_scope.declare_variable(name, value);
-            Ok(())
+            last_scope.declare_variable(name, value)
         } else {
             Err(ScopeManagerError::new(
                 ErrorSeverity::HIGH,
@@ -71,20 +99,47 @@ impl<'a> ScopeManager<'a> {
         }
     }
 
-    #[allow(dead_code)]
+    pub fn declare_constant(&mut self, name: &'a str, value: Rc<RefCell<Value>>) -> Result<(), ScopeManagerError> {
+        self.declare_variable(name, value)?;
+        if let Some(last_scope) = self.scopes.last_mut() {
+            last_scope.constants.insert(name);
+        }
+        Ok(())
+    }
+
+    pub fn is_constant(&self, name: &'a str) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.get_variable(name).is_some())
+            .is_some_and(|scope| scope.constants.contains(name))
+    }
+
+    // never empty, there is always the root scope
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> u32 {
         self.scopes.len() as u32
     }
 }
 
+impl<'a> Default for ScopeManager<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Scope<'a> {
     variables: HashMap<&'a str, Rc<RefCell<Value>>>,
+    constants: HashSet<&'a str>,
 }
 
 impl<'a> Scope<'a> {
     fn new() -> Self {
-        Scope { variables: HashMap::new() }
+        Scope {
+            variables: HashMap::new(),
+            constants: HashSet::new(),
+        }
     }
 
     fn get_variable(&self, searched: &'a str) -> Option<&Rc<RefCell<Value>>> {
@@ -92,32 +147,33 @@ impl<'a> Scope<'a> {
     }
 
     fn assign_variable(&mut self, name: &'a str, value: Rc<RefCell<Value>>) -> Result<(), ScopeManagerError> {
+        if self.constants.contains(name) {
+            return Err(ScopeManagerError::new(
+                ErrorSeverity::HIGH,
+                format!("Cannot assign to constant '{}'.", name),
+            ));
+        }
+
         let current_value_option = self.get_variable(name);
         match current_value_option {
             None => Err(ScopeManagerError::new(ErrorSeverity::HIGH, format!("Variable '{}' not declared.", name))),
             Some(prev_val) => {
                 let mut prev_val_borrow = prev_val.borrow_mut();
                 let new_val_borrow = value.borrow();
-                match (&*prev_val_borrow, &*new_val_borrow) {
-                    (Value::I64(_), Value::I64(_))
-                    | (Value::F64(_), Value::F64(_))
-                    | (Value::String(_), Value::String(_))
-                    | (Value::Bool(_), Value::Bool(_)) => {
-                        *prev_val_borrow = new_val_borrow.clone();
-                        drop(prev_val_borrow);
-                        drop(new_val_borrow);
-                        Ok(())
-                    }
-                    (a, b) => Err(ScopeManagerError::new(
+                // arrays also need matching element types, so types are compared instead of variants
+                let (previous_type, new_type) = (prev_val_borrow.to_type(), new_val_borrow.to_type());
+                if previous_type != new_type {
+                    return Err(ScopeManagerError::new(
                         ErrorSeverity::HIGH,
                         format!(
-                            "Cannot assign '{:?}' to variable '{}' which was previously declared as '{:?}'.",
-                            b.to_type(),
-                            name,
-                            a.to_type()
+                            "Cannot assign '{}' to variable '{}' which was previously declared as '{}'.",
+                            new_type, name, previous_type
                         ),
-                    )),
+                    ));
                 }
+                // written through the shared cell, so the caller sees it when passed by reference
+                *prev_val_borrow = new_val_borrow.clone();
+                Ok(())
             }
         }
     }
@@ -190,6 +246,57 @@ mod tests {
         assert_eq!(manager.scopes.len(), 1);
     }
 
+    #[test]
+    fn constants() {
+        let mut manager = ScopeManager::new();
+        let _ = manager.declare_constant("x", Rc::new(RefCell::new(Value::I64(1))));
+        let _ = manager.declare_variable("y", Rc::new(RefCell::new(Value::I64(2))));
+        assert!(manager.is_constant("x"));
+        assert!(!manager.is_constant("y"));
+
+        assert_eq!(
+            manager
+                .assign_variable("x", Rc::new(RefCell::new(Value::I64(3))))
+                .err()
+                .unwrap()
+                .message(),
+            String::from("Cannot assign to constant 'x'.")
+        );
+        assert_eq!(manager.get_variable("x").unwrap().clone(), Rc::new(RefCell::new(Value::I64(1))));
+
+        // constness is dropped together with the scope
+        manager.push_scope();
+        let _ = manager.declare_constant("z", Rc::new(RefCell::new(Value::I64(3))));
+        manager.pop_scope();
+        manager.push_scope();
+        let _ = manager.declare_variable("z", Rc::new(RefCell::new(Value::I64(4))));
+        assert!(!manager.is_constant("z"));
+        assert!(manager.assign_variable("z", Rc::new(RefCell::new(Value::I64(5)))).is_ok());
+    }
+
+    #[test]
+    fn reuses_popped_scopes() {
+        let mut manager = ScopeManager::with_capacity(4);
+        assert!(manager.scopes.capacity() >= 4);
+
+        manager.push_scope();
+        let _ = manager.declare_variable("x", Rc::new(RefCell::new(Value::I64(1))));
+        manager.pop_scope();
+        assert_eq!(manager.free_scopes.len(), 1);
+
+        // the reused scope keeps its allocation, but none of its variables
+        manager.push_scope();
+        assert!(manager.free_scopes.is_empty());
+        assert!(manager.scopes.last().unwrap().variables.capacity() > 0);
+        assert_eq!(
+            manager.get_variable("x").err().unwrap().message(),
+            String::from("Variable 'x' not declared in this scope.")
+        );
+
+        let _ = manager.declare_variable("x", Rc::new(RefCell::new(Value::Bool(true))));
+        assert_eq!(manager.get_variable("x").unwrap().clone(), Rc::new(RefCell::new(Value::Bool(true))));
+    }
+
     #[test]
     fn manages_variables() {
         // i64 x = 1;
@@ -229,6 +336,48 @@ mod tests {
         manager.pop_scope();
     }
 
+    #[test]
+    fn assign_writes_through_shared_value() {
+        // a reference parameter shares the caller's cell, so assigning must not replace it
+        let shared = Rc::new(RefCell::new(Value::I64(1)));
+        let mut manager = ScopeManager::new();
+        let _ = manager.declare_variable("x", Rc::clone(&shared));
+
+        let _ = manager.assign_variable("x", Rc::new(RefCell::new(Value::I64(5))));
+        assert!(Rc::ptr_eq(manager.get_variable("x").unwrap(), &shared));
+        assert_eq!(*shared.borrow(), Value::I64(5));
+    }
+
+    #[test]
+    fn assign_targets_innermost_shadowing_variable() {
+        // i64 x = 1;
+        // {i64 x = 2; x = 3;}
+        let mut manager = ScopeManager::new();
+        let _ = manager.declare_variable("x", Rc::new(RefCell::new(Value::I64(1))));
+
+        manager.push_scope();
+        assert!(manager.declare_variable("x", Rc::new(RefCell::new(Value::I64(2)))).is_ok());
+        assert!(manager.assign_variable("x", Rc::new(RefCell::new(Value::I64(3)))).is_ok());
+
+        assert_eq!(*manager.scopes[1].get_variable("x").unwrap().borrow(), Value::I64(3));
+        assert_eq!(*manager.scopes[0].get_variable("x").unwrap().borrow(), Value::I64(1));
+    }
+
+    #[test]
+    fn reads_innermost_shadowing_variable() {
+        // i64 x = 1;
+        // {i64 x = 2; x;}
+        let mut manager = ScopeManager::new();
+        let _ = manager.declare_variable("x", Rc::new(RefCell::new(Value::I64(1))));
+
+        manager.push_scope();
+        let _ = manager.declare_variable("x", Rc::new(RefCell::new(Value::I64(2))));
+        assert_eq!(*manager.get_variable("x").unwrap().borrow(), Value::I64(2));
+
+        manager.pop_scope();
+        assert_eq!(*manager.get_variable("x").unwrap().borrow(), Value::I64(1));
+    }
+
     #[test]
     fn bad_assign_type() {
         let mut manager = ScopeManager::new();
@@ -1,6 +1,9 @@
 use crate::{
-    ast::{Argument, Block, Expression, Literal, Node, Parameter, PassedBy, Program, Statement, SwitchCase, SwitchExpression, Type},
+    ast::{
+        Argument, Block, Expression, FunctionDeclaration, Literal, Node, Parameter, PassedBy, Program, Statement, SwitchCase, SwitchExpression, Type,
+    },
     errors::{ErrorSeverity, IError, SemanticCheckerError},
+    lazy_stream_reader::Position,
     visitor::Visitor,
 };
 
@@ -9,16 +12,61 @@ enum FunctionCallType {
     Expression(Node<Expression>),
 }
 
+#[derive(Clone, Default)]
+pub struct SemanticCheckerOptions {
+    pub warn_on_mixed_logical_operators: bool,
+    pub warn_on_ignored_return_values: bool,
+    pub warn_on_unused_variables: bool,
+    pub warn_on_unreachable_code: bool,
+    pub warn_on_shadowing: bool,
+}
+
+struct DeclaredVariable {
+    name: String,
+    var_type: Option<Type>,
+    position: Position,
+    is_read: bool,
+    is_const: bool,
+}
+
+const FIRE_AND_FORGET_FUNCTIONS: [&str; 4] = ["print", "println", "input", "read_line"];
+
 pub struct SemanticChecker<'a> {
     program: &'a Program,
+    options: SemanticCheckerOptions,
+    scopes: Vec<Vec<DeclaredVariable>>,
+    position: Position,
+    // function whose body is being checked, none for top-level statements
+    current_function: Option<&'a FunctionDeclaration>,
+    // the last statement of the switch case being checked, if it is 'fallthrough'
+    allowed_fallthrough: Option<&'a Node<Statement>>,
     pub errors: Vec<SemanticCheckerError>,
+    pub warnings: Vec<SemanticCheckerError>,
 }
 
 impl<'a> SemanticChecker<'a> {
     #![allow(unused_must_use)]
     pub fn new(program: &'a Program) -> Result<Self, Box<dyn IError>> {
+        Self::with_options(program, SemanticCheckerOptions::default())
+    }
+
+    pub fn with_options(program: &'a Program, options: SemanticCheckerOptions) -> Result<Self, Box<dyn IError>> {
         let errors: Vec<SemanticCheckerError> = vec![];
-        Ok(Self { program, errors })
+        let warnings: Vec<SemanticCheckerError> = vec![];
+        Ok(Self {
+            program,
+            options,
+            scopes: vec![],
+            position: Position {
+                line: 0,
+                column: 0,
+                offset: 0,
+            },
+            current_function: None,
+            allowed_fallthrough: None,
+            errors,
+            warnings,
+        })
     }
 
     pub fn check(&mut self) {
@@ -39,7 +87,7 @@ impl<'a> SemanticChecker<'a> {
 
                 // std function
                 if let Some(std_function) = self.program.std_functions.get(&String::from(name)) {
-                    if arguments.len() != std_function.params.len() {
+                    if !std_function.variadic && arguments.len() != std_function.params.len() {
                         self.errors.push(SemanticCheckerError::new(
                             ErrorSeverity::HIGH,
                             format!(
@@ -52,6 +100,13 @@ impl<'a> SemanticChecker<'a> {
                         ));
                     }
 
+                    if let Some(argument) = arguments.iter().find(|argument| argument.value.name.is_some()) {
+                        self.errors.push(SemanticCheckerError::new(
+                            ErrorSeverity::HIGH,
+                            format!("Std function '{}' doesn't accept named arguments.\nAt {:?}.\n", name, argument.position),
+                        ));
+                    }
+
                     for argument in arguments {
                         if argument.value.passed_by == PassedBy::Reference {
                             self.errors.push(SemanticCheckerError::new(
@@ -73,22 +128,55 @@ impl<'a> SemanticChecker<'a> {
                 // user function
                 if let Some(function_declaration) = self.program.functions.get(&String::from(name)) {
                     let parameters = &function_declaration.value.parameters;
-                    if arguments.len() != parameters.len() {
+                    let required = parameters.iter().filter(|p| p.value.default_value.is_none()).count();
+                    if arguments.len() < required || arguments.len() > parameters.len() {
+                        let expected = match required == parameters.len() {
+                            true => parameters.len().to_string(),
+                            false => format!("from {} to {}", required, parameters.len()),
+                        };
                         self.errors.push(SemanticCheckerError::new(
                             ErrorSeverity::HIGH,
                             format!(
                                 "Invalid number of arguments for function '{}'. Expected {}, given {}.\nAt {:?}.\n",
                                 name,
-                                parameters.len(),
+                                expected,
                                 arguments.len(),
                                 position
                             ),
                         ))
                     }
 
-                    for idx in 0..parameters.len() {
-                        let parameter = parameters.get(idx).unwrap();
-                        if let Some(argument) = arguments.get(idx) {
+                    let binding = match function_declaration.value.bind_arguments(&arguments) {
+                        Ok(binding) => binding,
+                        Err(message) => {
+                            let position = arguments
+                                .iter()
+                                .find(|argument| argument.value.name.is_some())
+                                .map_or(position, |a| a.position);
+                            self.errors.push(SemanticCheckerError::new(
+                                ErrorSeverity::HIGH,
+                                format!("{}\nAt {:?}.\n", message, position),
+                            ));
+                            return;
+                        }
+                    };
+
+                    for (idx, parameter) in parameters.iter().enumerate() {
+                        let argument = binding
+                            .iter()
+                            .position(|bound| *bound == idx)
+                            .and_then(|argument_idx| arguments.get(argument_idx));
+                        // only possible with named arguments, positional ones were counted above
+                        if argument.is_none() && parameter.value.default_value.is_none() && arguments.len() >= required {
+                            self.errors.push(SemanticCheckerError::new(
+                                ErrorSeverity::HIGH,
+                                format!(
+                                    "Missing argument for parameter '{}' of function '{}'.\nAt {:?}.\n",
+                                    parameter.value.identifier.value, name, position
+                                ),
+                            ));
+                        }
+                        if let Some(argument) = argument {
                             if argument.value.passed_by != parameter.value.passed_by {
                                 self.errors.push(SemanticCheckerError::new(
                                     ErrorSeverity::HIGH,
@@ -102,56 +190,449 @@ impl<'a> SemanticChecker<'a> {
                                     ),
                                 ));
                             }
-
-                            if argument.value.passed_by == PassedBy::Reference {
-                                if let Expression::Variable(_) = argument.value.value.value {
-                                } else {
-                                    self.errors.push(SemanticCheckerError::new(ErrorSeverity::HIGH, format!(
-                                            "Parameter '{}' in function '{}' is passed by {:?}. Thus it needs to an identifier, but a complex expression was found.\nAt {:?}.\n",
-                                            parameter.value.identifier.value,
-                                            identifier.value,
-                                            PassedBy::Reference,
-                                            argument.position
-                                        ),
-                                    ));
-                                }
-                            }
                         }
                     }
 
                     return;
                 }
 
-                self.errors.push(SemanticCheckerError::new(
-                    ErrorSeverity::HIGH,
-                    format!("Use of undeclared function '{}'.\nAt {:?}.\n", name, position),
-                ))
+                let is_variable = self.scopes.iter().any(|scope| scope.iter().any(|variable| variable.name == *name));
+                let message = match is_variable {
+                    true => format!("'{}' is not a function.\nAt {:?}.\n", name, position),
+                    false => format!("Use of undeclared function '{}'.\nAt {:?}.\n", name, position),
+                };
+                self.errors.push(SemanticCheckerError::new(ErrorSeverity::HIGH, message))
             }
             _ => {}
         }
     }
+
+    fn check_ignored_return_value(&mut self, statement: &Node<Statement>) {
+        if !self.options.warn_on_ignored_return_values {
+            return;
+        }
+
+        if let Statement::FunctionCall { identifier, .. } = &statement.value {
+            let name = &identifier.value;
+            if FIRE_AND_FORGET_FUNCTIONS.contains(&name.as_str()) {
+                return;
+            }
+
+            let returns_value = match self.program.functions.get(name) {
+                Some(function_declaration) => function_declaration.value.return_type.value != Type::Void,
+                None => self.program.std_functions.contains_key(name),
+            };
+
+            if returns_value {
+                self.warnings.push(SemanticCheckerError::new(
+                    ErrorSeverity::LOW,
+                    format!("\nWarning:\nReturn value of '{}' is ignored.\nAt {:?}.\n", name, statement.position),
+                ));
+            }
+        }
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(vec![]);
+    }
+
+    fn pop_scope(&mut self) {
+        let scope = match self.scopes.pop() {
+            Some(scope) => scope,
+            None => return,
+        };
+
+        if !self.options.warn_on_unused_variables {
+            return;
+        }
+
+        for variable in scope.iter().filter(|variable| !variable.is_read) {
+            self.warnings.push(SemanticCheckerError::new(
+                ErrorSeverity::LOW,
+                format!(
+                    "\nWarning:\nVariable '{}' is declared but never read.\nAt {:?}.\n",
+                    variable.name, variable.position
+                ),
+            ));
+        }
+    }
+
+    // checks the statements in the current scope, the caller opens the scope
+    fn visit_block_statements(&mut self, block: &'a Node<Block>) {
+        let mut terminated_by = None;
+        for statement in &block.value.0 {
+            if let Some(terminated_by) = terminated_by {
+                self.check_unreachable_statement(statement, terminated_by);
+            }
+            self.visit_statement(statement);
+
+            match statement.value {
+                Statement::Return(_) if terminated_by.is_none() => terminated_by = Some("return"),
+                Statement::Break if terminated_by.is_none() => terminated_by = Some("break"),
+                Statement::Fallthrough if terminated_by.is_none() => terminated_by = Some("fallthrough"),
+                _ => {}
+            }
+        }
+    }
+
+    fn declare_variable(&mut self, identifier: &Node<String>, var_type: Option<Type>, report_if_unused: bool, is_const: bool) {
+        let redeclared = self
+            .scopes
+            .last()
+            .and_then(|scope| scope.iter().find(|variable| variable.name == identifier.value));
+        if let Some(redeclared) = redeclared {
+            self.errors.push(SemanticCheckerError::new(
+                ErrorSeverity::HIGH,
+                format!(
+                    "Cannot redeclare variable '{}' declared at {:?}.\nAt {:?}.\n",
+                    identifier.value, redeclared.position, identifier.position
+                ),
+            ));
+        }
+
+        let outer_scopes = &self.scopes[..self.scopes.len().saturating_sub(1)];
+        let shadowed = outer_scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.iter().rev().find(|variable| variable.name == identifier.value));
+        if let Some(shadowed) = shadowed {
+            let is_parameter = self.current_function.is_some_and(|function| {
+                function
+                    .parameters
+                    .iter()
+                    .any(|parameter| std::ptr::eq(&parameter.value.identifier, identifier))
+            });
+            let kind = match is_parameter {
+                true => "Parameter",
+                false => "Variable",
+            };
+            self.check_shadowing(identifier, kind, shadowed.position);
+        }
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(DeclaredVariable {
+                name: identifier.value.clone(),
+                var_type,
+                position: identifier.position,
+                is_read: !report_if_unused,
+                is_const,
+            });
+        }
+    }
+
+    fn infer_type(&self, expression: &Node<Expression>) -> Option<Type> {
+        // None means the type can't be told without running the program
+        match &expression.value {
+            Expression::Alternative(..)
+            | Expression::Concatenation(..)
+            | Expression::Greater(..)
+            | Expression::GreaterEqual(..)
+            | Expression::Less(..)
+            | Expression::LessEqual(..)
+            | Expression::Equal(..)
+            | Expression::NotEqual(..)
+            | Expression::BooleanNegation(..) => Some(Type::Bool),
+            Expression::BitwiseOr(..)
+            | Expression::BitwiseXor(..)
+            | Expression::BitwiseAnd(..)
+            | Expression::ShiftLeft(..)
+            | Expression::ShiftRight(..) => Some(Type::I64),
+            Expression::Addition(lhs, rhs)
+            | Expression::Subtraction(lhs, rhs)
+            | Expression::Multiplication(lhs, rhs)
+            | Expression::Division(lhs, rhs) => match (self.infer_type(lhs), self.infer_type(rhs)) {
+                (Some(Type::Str), Some(Type::I64)) | (Some(Type::I64), Some(Type::Str))
+                    if matches!(expression.value, Expression::Multiplication(..)) =>
+                {
+                    Some(Type::Str)
+                }
+                (Some(lhs_type), Some(rhs_type)) if lhs_type == rhs_type => Some(lhs_type),
+                _ => None,
+            },
+            Expression::ArithmeticNegation(value) | Expression::UnaryPlus(value) | Expression::Parenthesized(value) => self.infer_type(value),
+            Expression::Ternary { if_true, if_false, .. } => match (self.infer_type(if_true), self.infer_type(if_false)) {
+                (Some(true_type), Some(false_type)) if true_type == false_type => Some(true_type),
+                _ => None,
+            },
+            // aliases are out of scope here, so cases using them can't be told
+            Expression::Switch { cases, default, .. } => {
+                let case_types = cases
+                    .iter()
+                    .map(|case| &case.value.value)
+                    .chain(default.as_deref())
+                    .map(|value| self.infer_type(value))
+                    .collect::<Option<Vec<Type>>>()?;
+                match case_types.split_first() {
+                    Some((first, rest)) if rest.iter().all(|case_type| case_type == first) => Some(first.clone()),
+                    _ => None,
+                }
+            }
+            Expression::Casting { to_type, .. } => Some(to_type.value.clone()),
+            Expression::Tuple(values) => values
+                .iter()
+                .map(|value| self.infer_type(value))
+                .collect::<Option<Vec<Type>>>()
+                .map(Type::Tuple),
+            Expression::Array(values) => {
+                let element_types = values.iter().map(|value| self.infer_type(value)).collect::<Option<Vec<Type>>>()?;
+                match element_types.split_first() {
+                    Some((first, rest)) if rest.iter().all(|element_type| element_type == first) => Some(Type::Array(Box::new(first.clone()))),
+                    _ => None,
+                }
+            }
+            Expression::Index { value, .. } => match self.infer_type(value) {
+                Some(Type::Array(element_type)) => Some(*element_type),
+                _ => None,
+            },
+            Expression::Literal(literal) => match literal {
+                Literal::True | Literal::False => Some(Type::Bool),
+                Literal::String(_) => Some(Type::Str),
+                Literal::Char(_) => Some(Type::Char),
+                Literal::I64(_) => Some(Type::I64),
+                Literal::F64(_) => Some(Type::F64),
+            },
+            Expression::Variable(name) => self
+                .scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.iter().rev().find(|variable| variable.name == *name))
+                .and_then(|variable| variable.var_type.clone()),
+            Expression::FunctionCall { identifier, .. } => self
+                .program
+                .functions
+                .get(&identifier.value)
+                .map(|function| function.value.return_type.value.clone()),
+        }
+    }
+
+    fn check_shadowing(&mut self, identifier: &Node<String>, kind: &str, shadowed_position: Position) {
+        if !self.options.warn_on_shadowing {
+            return;
+        }
+
+        self.warnings.push(SemanticCheckerError::new(
+            ErrorSeverity::LOW,
+            format!(
+                "\nWarning:\n{} '{}' shadows a variable declared at {:?}.\nAt {:?}.\n",
+                kind, identifier.value, shadowed_position, identifier.position
+            ),
+        ));
+    }
+
+    fn find_variable(&mut self, name: &str, position: Position) -> Option<&mut DeclaredVariable> {
+        let declared = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.iter_mut().rev().find(|variable| variable.name == name));
+
+        if declared.is_none() {
+            self.errors.push(SemanticCheckerError::new(
+                ErrorSeverity::HIGH,
+                format!("Use of undeclared variable '{}'.\nAt {:?}.\n", name, position),
+            ));
+        }
+        declared
+    }
+
+    // a global variable is read-only inside functions, unless it is shadowed
+    fn is_global(&self, name: &str) -> bool {
+        let declared_in = self.scopes.iter().rposition(|scope| scope.iter().any(|variable| variable.name == name));
+        self.current_function.is_some() && declared_in == Some(0)
+    }
+
+    // assigning doesn't count as reading the variable
+    fn check_assignment_target(&mut self, identifier: &Node<String>) {
+        if self.is_global(&identifier.value) {
+            self.errors.push(SemanticCheckerError::new(
+                ErrorSeverity::HIGH,
+                format!(
+                    "Cannot modify global variable '{}' inside a function.\nAt {:?}.\n",
+                    identifier.value, identifier.position
+                ),
+            ));
+            return;
+        }
+        let is_const = self
+            .find_variable(&identifier.value, identifier.position)
+            .is_some_and(|declared| declared.is_const);
+        if is_const {
+            self.errors.push(SemanticCheckerError::new(
+                ErrorSeverity::HIGH,
+                format!("Cannot assign to constant '{}'.\nAt {:?}.\n", identifier.value, identifier.position),
+            ));
+        }
+    }
+
+    fn is_constant(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.iter().rev().find(|variable| variable.name == name))
+            .is_some_and(|variable| variable.is_const)
+    }
+
+    fn check_switch_condition(&mut self, condition: &'a Node<Expression>) {
+        self.visit_expression(condition);
+        if let Some(condition_type) = self.infer_type(condition).filter(|condition_type| *condition_type != Type::Bool) {
+            self.errors.push(SemanticCheckerError::new(
+                ErrorSeverity::HIGH,
+                format!(
+                    "Condition in 'switch case' has to evaluate to type '{}' - got '{}'.\nAt {:?}.\n",
+                    Type::Bool,
+                    condition_type,
+                    condition.position
+                ),
+            ));
+        }
+    }
+
+    fn check_function_returns(&mut self, function: &Node<FunctionDeclaration>) {
+        fn always_returns(block: &Block) -> bool {
+            block.0.iter().any(|statement| match &statement.value {
+                Statement::Return(_) => true,
+                Statement::Conditional {
+                    if_block,
+                    else_block: Some(else_block),
+                    ..
+                } => always_returns(&if_block.value) && always_returns(&else_block.value),
+                // the default only runs when no case matched, so it covers every other path
+                Statement::Switch {
+                    cases,
+                    default: Some(default),
+                    ..
+                } => cases.iter().all(|case| case_returns(&case.value.block.value)) && case_returns(&default.value),
+                _ => false,
+            })
+        }
+
+        // a case left with 'break' ends the switch, one left with 'fallthrough' may match no later case and skips the default
+        fn case_returns(block: &Block) -> bool {
+            fn may_leave_case(statements: &[Node<Statement>]) -> bool {
+                statements.iter().any(|statement| match &statement.value {
+                    Statement::Break | Statement::Fallthrough => true,
+                    Statement::Conditional { if_block, else_block, .. } => {
+                        may_leave_case(&if_block.value.0) || else_block.as_ref().is_some_and(|block| may_leave_case(&block.value.0))
+                    }
+                    _ => false,
+                })
+            }
+
+            // statements after the first return are never run
+            let statements = &block.0;
+            let reached = match statements.iter().position(|statement| matches!(statement.value, Statement::Return(_))) {
+                Some(index) => &statements[..index],
+                None => &statements[..],
+            };
+            always_returns(block) && !may_leave_case(reached)
+        }
+
+        let declaration = &function.value;
+        if declaration.return_type.value != Type::Void && !always_returns(&declaration.block.value) {
+            self.errors.push(SemanticCheckerError::new(
+                ErrorSeverity::HIGH,
+                format!(
+                    "Function '{}' may not return a value on all paths.\nAt {:?}.\n",
+                    declaration.identifier.value, function.position
+                ),
+            ));
+        }
+    }
+
+    fn check_return_value(&mut self, statement: &Node<Statement>, has_value: bool) {
+        let Some(function) = self.current_function else {
+            return;
+        };
+
+        let is_void = function.return_type.value == Type::Void;
+        let message = match (is_void, has_value) {
+            (false, false) => "Empty return in non-void function",
+            (true, true) => "Return with a value in void function",
+            _ => return,
+        };
+        self.errors.push(SemanticCheckerError::new(
+            ErrorSeverity::HIGH,
+            format!("{} '{}'.\nAt {:?}.\n", message, function.identifier.value, statement.position),
+        ));
+    }
+
+    fn check_unreachable_statement(&mut self, statement: &Node<Statement>, terminated_by: &str) {
+        if !self.options.warn_on_unreachable_code {
+            return;
+        }
+
+        self.warnings.push(SemanticCheckerError::new(
+            ErrorSeverity::LOW,
+            format!(
+                "\nWarning:\nUnreachable statement after '{}'.\nAt {:?}.\n",
+                terminated_by, statement.position
+            ),
+        ));
+    }
+
+    fn check_mixed_logical_operators(&mut self, expression: &Node<Expression>) {
+        if !self.options.warn_on_mixed_logical_operators {
+            return;
+        }
+
+        if let Expression::Alternative(lhs, rhs) = &expression.value {
+            let is_unparenthesized_concatenation = |node: &Node<Expression>| matches!(node.value, Expression::Concatenation(..));
+            if is_unparenthesized_concatenation(lhs) || is_unparenthesized_concatenation(rhs) {
+                self.warnings.push(SemanticCheckerError::new(
+                    ErrorSeverity::LOW,
+                    format!(
+                        "\nWarning:\nOperators '||' and '&&' mixed without parentheses - '&&' binds tighter. Consider adding parentheses, e.g. 'a || (b && c)'.\nAt {:?}.\n",
+                        expression.position
+                    ),
+                ));
+            }
+        }
+    }
 }
 
 impl<'a> Visitor<'a> for SemanticChecker<'a> {
     #![allow(unused_must_use)]
     fn visit_program(&mut self, program: &'a Program) -> Result<(), Box<dyn IError>> {
+        self.push_scope();
         for statement in &program.statements {
             self.visit_statement(&statement);
         }
 
+        // the top-level scope stays on the stack, so functions can read global variables
         for (_, function) in &program.functions {
-            self.visit_block(&function.value.block);
+            self.check_function_returns(function);
+            self.current_function = Some(&function.value);
+            self.push_scope();
+            // a default value can use the parameters declared before it
+            for parameter in &function.value.parameters {
+                if let Some(default_value) = &parameter.value.default_value {
+                    self.visit_expression(default_value);
+                }
+                self.declare_variable(
+                    &parameter.value.identifier,
+                    Some(parameter.value.parameter_type.value.clone()),
+                    false,
+                    false,
+                );
+            }
+            // the interpreter keeps the parameters and the body's variables in one scope
+            self.visit_block_statements(&function.value.block);
+            self.pop_scope();
+            self.current_function = None;
         }
+        self.pop_scope();
         Ok(())
     }
 
     fn visit_expression(&mut self, expression: &'a Node<Expression>) -> Result<(), Box<dyn IError>> {
+        self.position = expression.position;
         match &expression.value {
             Expression::FunctionCall { .. } => {
                 self.check_function_call(FunctionCallType::Expression(expression.clone()));
             }
             _ => {}
         }
+        self.check_mixed_logical_operators(expression);
 
         match &expression.value {
             Expression::Alternative(lhs, rhs)
@@ -165,11 +646,33 @@ impl<'a> Visitor<'a> for SemanticChecker<'a> {
             | Expression::Addition(lhs, rhs)
             | Expression::Subtraction(lhs, rhs)
             | Expression::Multiplication(lhs, rhs)
-            | Expression::Division(lhs, rhs) => {
+            | Expression::Division(lhs, rhs)
+            | Expression::BitwiseOr(lhs, rhs)
+            | Expression::BitwiseXor(lhs, rhs)
+            | Expression::BitwiseAnd(lhs, rhs)
+            | Expression::ShiftLeft(lhs, rhs)
+            | Expression::ShiftRight(lhs, rhs) => {
                 self.visit_expression(&lhs);
                 self.visit_expression(&rhs);
             }
-            Expression::BooleanNegation(value) | Expression::ArithmeticNegation(value) | Expression::Casting { value, .. } => {
+            Expression::Index { value, index } => {
+                self.visit_expression(value);
+                self.visit_expression(index);
+            }
+            Expression::Ternary {
+                condition,
+                if_true,
+                if_false,
+            } => {
+                self.visit_expression(condition);
+                self.visit_expression(if_true);
+                self.visit_expression(if_false);
+            }
+            Expression::Parenthesized(value)
+            | Expression::BooleanNegation(value)
+            | Expression::ArithmeticNegation(value)
+            | Expression::UnaryPlus(value)
+            | Expression::Casting { value, .. } => {
                 self.visit_expression(&value);
             }
             Expression::Literal(literal) => {
@@ -183,6 +686,25 @@ impl<'a> Visitor<'a> for SemanticChecker<'a> {
                     self.visit_argument(&arg);
                 }
             }
+            Expression::Tuple(values) | Expression::Array(values) => {
+                for value in values {
+                    self.visit_expression(value);
+                }
+            }
+            Expression::Switch { expressions, cases, default } => {
+                self.push_scope();
+                for expression in expressions {
+                    self.visit_switch_expression(expression);
+                }
+                for case in cases {
+                    self.check_switch_condition(&case.value.condition);
+                    self.visit_expression(&case.value.value);
+                }
+                if let Some(default) = default {
+                    self.visit_expression(default);
+                }
+                self.pop_scope();
+            }
         }
         Ok(())
     }
@@ -191,6 +713,7 @@ impl<'a> Visitor<'a> for SemanticChecker<'a> {
         match &statement.value {
             &Statement::FunctionCall { .. } => {
                 self.check_function_call(FunctionCallType::Statement(statement.clone()));
+                self.check_ignored_return_value(statement);
             }
             _ => {}
         }
@@ -201,14 +724,28 @@ impl<'a> Visitor<'a> for SemanticChecker<'a> {
                     self.visit_argument(&arg);
                 }
             }
-            Statement::Declaration { var_type, value, .. } => {
+            Statement::Declaration {
+                var_type,
+                identifier,
+                value,
+                is_const,
+            } => {
                 self.visit_type(&var_type);
                 if let Some(val) = value {
                     self.visit_expression(&val);
                 }
+                self.declare_variable(identifier, Some(var_type.value.clone()), true, *is_const);
             }
-            Statement::Assignment { value, .. } => {
+            Statement::Assignment { identifier, value } => {
                 self.visit_expression(&value);
+                self.check_assignment_target(identifier);
+            }
+            Statement::IndexAssignment { identifier, indices, value } => {
+                self.visit_expression(value);
+                for index in indices {
+                    self.visit_expression(index);
+                }
+                self.check_assignment_target(identifier);
             }
             Statement::Conditional {
                 condition,
@@ -227,6 +764,7 @@ impl<'a> Visitor<'a> for SemanticChecker<'a> {
                 assignment,
                 block,
             } => {
+                self.push_scope();
                 if let Some(decl) = declaration {
                     self.visit_statement(&decl);
                 }
@@ -235,34 +773,92 @@ impl<'a> Visitor<'a> for SemanticChecker<'a> {
                     self.visit_statement(&assign);
                 }
                 self.visit_block(&block);
+                self.pop_scope();
             }
-            Statement::Switch { expressions, cases } => {
+            Statement::Switch { expressions, cases, default } => {
+                self.push_scope();
                 for expr in expressions {
                     self.visit_switch_expression(&expr);
                 }
                 for case in cases {
                     self.visit_switch_case(&case);
                 }
+                if let Some(default) = default {
+                    self.visit_block(default);
+                }
+                self.pop_scope();
             }
             Statement::Return(value) => {
                 if let Some(val) = value {
                     self.visit_expression(&val);
                 }
+                self.check_return_value(statement, value.is_some());
             }
             Statement::Break => {}
+            Statement::Fallthrough => {
+                if !self.allowed_fallthrough.is_some_and(|allowed| std::ptr::eq(allowed, statement)) {
+                    self.errors.push(SemanticCheckerError::new(
+                        ErrorSeverity::HIGH,
+                        format!(
+                            "'fallthrough' can only be the last statement of a switch case.\nAt {:?}.\n",
+                            statement.position
+                        ),
+                    ));
+                }
+            }
+            Statement::Destructuring { targets, value } => {
+                self.visit_expression(value);
+                let expected_type = Type::Tuple(targets.iter().map(|(var_type, _)| var_type.value.clone()).collect());
+                if let Some(value_type) = self.infer_type(value).filter(|value_type| *value_type != expected_type) {
+                    self.errors.push(SemanticCheckerError::new(
+                        ErrorSeverity::HIGH,
+                        format!(
+                            "Cannot destructure value of type '{}' into '{}'.\nAt {:?}.\n",
+                            value_type, expected_type, statement.position
+                        ),
+                    ));
+                }
+                for (var_type, identifier) in targets {
+                    self.visit_type(var_type);
+                    self.declare_variable(identifier, Some(var_type.value.clone()), true, false);
+                }
+            }
         }
         Ok(())
     }
 
     fn visit_argument(&mut self, argument: &'a Node<Argument>) -> Result<(), Box<dyn IError>> {
         self.visit_expression(&argument.value.value);
+        // checked for every call, so also std functions and arguments without a parameter are covered
+        if let Some(message) = argument.value.invalid_reference() {
+            self.errors.push(SemanticCheckerError::new(
+                ErrorSeverity::HIGH,
+                format!("{}\nAt {:?}.\n", message, argument.position),
+            ));
+        }
+        if let (PassedBy::Reference, Expression::Variable(name)) = (&argument.value.passed_by, &argument.value.value.value) {
+            if self.is_constant(name) {
+                self.errors.push(SemanticCheckerError::new(
+                    ErrorSeverity::HIGH,
+                    format!("Cannot pass constant '{}' by reference.\nAt {:?}.\n", name, argument.position),
+                ));
+            } else if self.is_global(name) {
+                self.errors.push(SemanticCheckerError::new(
+                    ErrorSeverity::HIGH,
+                    format!(
+                        "Cannot pass global variable '{}' by reference inside a function.\nAt {:?}.\n",
+                        name, argument.position
+                    ),
+                ));
+            }
+        }
         Ok(())
     }
 
     fn visit_block(&mut self, block: &'a Node<Block>) -> Result<(), Box<dyn IError>> {
-        for statement in &block.value.0 {
-            self.visit_statement(statement);
-        }
+        self.push_scope();
+        self.visit_block_statements(block);
+        self.pop_scope();
         Ok(())
     }
 
@@ -272,13 +868,22 @@ impl<'a> Visitor<'a> for SemanticChecker<'a> {
     }
 
     fn visit_switch_case(&mut self, switch_case: &'a Node<SwitchCase>) -> Result<(), Box<dyn IError>> {
-        self.visit_expression(&switch_case.value.condition);
+        self.check_switch_condition(&switch_case.value.condition);
+        let last_statement = switch_case.value.block.value.0.last();
+        let outer_fallthrough = std::mem::replace(
+            &mut self.allowed_fallthrough,
+            last_statement.filter(|statement| statement.value == Statement::Fallthrough),
+        );
         self.visit_block(&switch_case.value.block);
+        self.allowed_fallthrough = outer_fallthrough;
         Ok(())
     }
 
     fn visit_switch_expression(&mut self, switch_expression: &'a Node<SwitchExpression>) -> Result<(), Box<dyn IError>> {
         self.visit_expression(&switch_expression.value.expression);
+        if let Some(alias) = &switch_expression.value.alias {
+            self.declare_variable(alias, None, false, false);
+        }
         Ok(())
     }
 
@@ -290,7 +895,744 @@ impl<'a> Visitor<'a> for SemanticChecker<'a> {
         Ok(())
     }
 
-    fn visit_variable(&mut self, _variable: &'a String) -> Result<(), Box<dyn IError>> {
+    fn visit_variable(&mut self, variable: &'a String) -> Result<(), Box<dyn IError>> {
+        if let Some(declared) = self.find_variable(variable, self.position) {
+            declared.is_read = true;
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use crate::{
+        errors::IError,
+        lazy_stream_reader::LazyStreamReader,
+        lexer::{Lexer, LexerOptions},
+        parser::{IParser, Parser},
+    };
+
+    use super::*;
+
+    fn on_warning(_err: Box<dyn IError>) {}
+
+    fn parse(text: &str) -> Program {
+        let options = LexerOptions {
+            max_comment_length: 100,
+            max_identifier_length: 100,
+            max_string_length: 1000,
+            strict_strings: false,
+        };
+        let reader = LazyStreamReader::new(BufReader::new(text.as_bytes()));
+        let lexer = Lexer::new(reader, options, on_warning);
+        let mut parser = Parser::new(lexer);
+        parser.parse().unwrap()
+    }
+
+    fn check(program: &Program, options: SemanticCheckerOptions) -> SemanticChecker<'_> {
+        let mut checker = SemanticChecker::with_options(program, options).unwrap();
+        checker.check();
+        checker
+    }
+
+    #[test]
+    fn warns_on_mixed_logical_operators() {
+        let program = parse("bool a; bool b; bool c; bool x = a || b && c;");
+        let checker = check(
+            &program,
+            SemanticCheckerOptions {
+                warn_on_mixed_logical_operators: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(checker.errors.len(), 0);
+        assert_eq!(checker.warnings.len(), 1);
+        assert!(checker.warnings[0].message().contains("'a || (b && c)'"));
+    }
+
+    #[test]
+    fn no_warning_on_parenthesized_logical_operators() {
+        let program = parse("bool a; bool b; bool c; bool x = a || (b && c); bool y = (a || b) && c;");
+        let checker = check(
+            &program,
+            SemanticCheckerOptions {
+                warn_on_mixed_logical_operators: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(checker.errors.len(), 0);
+        assert_eq!(checker.warnings.len(), 0);
+    }
+
+    #[test]
+    fn std_function_arity() {
+        let valid = r#"
+            print(); print("a"); print("a", 1, true); println(); abs(1); min(1, 2); max(1, 2);
+            len("a"); substring("a", 0, 1); to_upper("a"); to_lower("a"); sqrt(1.0); floor(1.0); ceil(1.0); round(1.0); pow(1, 2);
+            push([1], 2); pop([1]); len([1]); split("a,b", ","); contains("ab", "b"); index_of("ab", "b");
+        "#;
+        let program = parse(valid);
+        let checker = check(&program, SemanticCheckerOptions::default());
+        assert_eq!(checker.errors.len(), 0);
+
+        let data = [
+            ("mod(1);", "mod", 2, 1),
+            ("abs(1, 2);", "abs", 1, 2),
+            ("to_lower(\"a\", \"b\");", "to_lower", 1, 2),
+            ("push([1]);", "push", 2, 1),
+            ("split(\"a\");", "split", 2, 1),
+            ("index_of(\"a\");", "index_of", 2, 1),
+        ];
+        for (code, name, expected, given) in data {
+            let program = parse(code);
+            let checker = check(&program, SemanticCheckerOptions::default());
+            assert_eq!(checker.errors.len(), 1);
+            assert!(checker.errors[0].message().starts_with(&format!(
+                "Invalid number of arguments for function '{}'. Expected {}, given {}.",
+                name, expected, given
+            )));
+        }
+    }
+
+    #[test]
+    fn default_parameters_arity() {
+        let code = "fn f(i64 a, i64 b = 2, i64 c = 3): void {} f(1); f(1, 2); f(1, 2, 3); f(); f(1, 2, 3, 4);";
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        assert_eq!(checker.errors.len(), 2);
+        for (error, given) in checker.errors.iter().zip([0, 4]) {
+            assert!(error.message().starts_with(&format!(
+                "Invalid number of arguments for function 'f'. Expected from 1 to 3, given {}.",
+                given
+            )));
+        }
+    }
+
+    #[test]
+    fn reports_references_to_non_variables() {
+        let code = r#"
+            fn inc(&i64 x): void { x += 1; }
+            i64 a = 1;
+            inc(&a);
+            inc(&5);
+            inc(&(1 + 2));
+            print(&5);
+            undeclared(&(a + 1));
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let literal = "Cannot pass a literal by reference - only variables can be passed by reference.";
+        let expression = "Cannot pass an expression by reference - only variables can be passed by reference.";
+        let expected = [
+            format!("{}\nAt line: 5, column: 18.\n", literal),
+            format!("{}\nAt line: 6, column: 18.\n", expression),
+            format!("{}\nAt line: 7, column: 20.\n", literal),
+            String::from("Parameter in function 'print' passed by Reference - should be passed by Value.\nAt line: 7, column: 20.\n"),
+            format!("{}\nAt line: 8, column: 25.\n", expression),
+            String::from("Use of undeclared function 'undeclared'.\nAt line: 8, column: 13.\n"),
+        ];
+        let mut messages: Vec<String> = checker.errors.iter().map(|error| error.message()).collect();
+        messages.sort();
+        let mut expected = expected.to_vec();
+        expected.sort();
+        assert_eq!(messages, expected);
+    }
+
+    #[test]
+    fn fallthrough_only_ends_switch_cases() {
+        let code = r#"
+            i64 x = 1;
+            switch (x) {
+                (x > 0) -> { if (true) { fallthrough; } }
+                (x > 1) -> { fallthrough; x = 2; }
+                (x > 2) -> { fallthrough; }
+                default -> { fallthrough; }
+            }
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let lines: Vec<String> = checker.errors.iter().map(|error| error.message()).collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines
+            .iter()
+            .all(|line| line.starts_with("'fallthrough' can only be the last statement of a switch case.")));
+    }
+
+    #[test]
+    fn calling_a_variable() {
+        let program = parse("i64 x = 1;\nx();\nundefined();");
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let messages: Vec<String> = checker.errors.iter().map(|error| error.message()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "'x' is not a function.\nAt line: 2, column: 1.\n",
+                "Use of undeclared function 'undefined'.\nAt line: 3, column: 1.\n"
+            ]
+        );
+    }
+
+    #[test]
+    fn globals_are_read_only_in_functions() {
+        let code = r#"
+            i64 total = 0;
+            [i64] values = [1];
+            fn f(i64 total_copy = total): i64 {
+                total = 1;
+                values[0] = 2;
+                g(&total);
+                return total + total_copy;
+            }
+            fn g(&i64 x): void {}
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let messages: Vec<String> = checker.errors.iter().map(|error| error.message()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Cannot modify global variable 'total' inside a function.\nAt line: 5, column: 17.\n",
+                "Cannot modify global variable 'values' inside a function.\nAt line: 6, column: 17.\n",
+                "Cannot pass global variable 'total' by reference inside a function.\nAt line: 7, column: 20.\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_return_value_mismatches() {
+        let code = r#"
+            fn f(): i64 {
+                if (true) { return; }
+                return 1;
+            }
+            fn g(): void {
+                return 1;
+            }
+            fn h(): void { return; }
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let expected = [
+            "Empty return in non-void function 'f'.\nAt line: 3, column: 29.\n",
+            "Return with a value in void function 'g'.\nAt line: 7, column: 17.\n",
+        ];
+        let mut messages: Vec<String> = checker.errors.iter().map(|error| error.message()).collect();
+        messages.sort();
+        assert_eq!(messages, expected);
+    }
+
+    #[test]
+    fn named_arguments() {
+        let code = r#"
+            fn f(i64 a, i64 b = 2, i64 c = 3): void {}
+            f(1, c = 2); f(b = 1, a = 2);
+            f(1, d = 2);
+            f(1, a = 2);
+            f(b = 1);
+            abs(value = 1);
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let expected = [
+            "Function 'f' has no parameter named 'd'.\nAt line: 4, column: 18.\n",
+            "Parameter 'a' of function 'f' is given more than once.\nAt line: 5, column: 18.\n",
+            "Missing argument for parameter 'a' of function 'f'.\nAt line: 6, column: 13.\n",
+            "Std function 'abs' doesn't accept named arguments.\nAt line: 7, column: 17.\n",
+        ];
+        assert_eq!(checker.errors.len(), expected.len());
+        for (error, message) in checker.errors.iter().zip(expected) {
+            assert_eq!(error.message(), message);
+        }
+    }
+
+    #[test]
+    fn default_values_see_preceding_parameters() {
+        let code = "fn f(i64 a, i64 b = a + 1): void {} fn g(i64 a = b, i64 b = 1): void {}";
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        assert_eq!(checker.errors.len(), 1);
+        assert!(checker.errors[0].message().starts_with("Use of undeclared variable 'b'."));
+    }
+
+    #[test]
+    fn redeclaration_in_same_scope() {
+        let code = "i64 x = 1;\nstr x = \"a\";\nfn f(i64 a): void { if (true) { bool b; bool b; } }";
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        assert_eq!(checker.errors.len(), 2);
+        assert_eq!(
+            checker.errors[0].message(),
+            "Cannot redeclare variable 'x' declared at line: 1, column: 5.\nAt line: 2, column: 5.\n"
+        );
+        assert!(checker.errors[1].message().starts_with("Cannot redeclare variable 'b'"));
+    }
+
+    #[test]
+    fn parameter_redeclared_in_function_body() {
+        let code = "fn f(i64 a): void {\n    i64 a = 2;\n}";
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        assert_eq!(checker.errors.len(), 1);
+        assert_eq!(
+            checker.errors[0].message(),
+            "Cannot redeclare variable 'a' declared at line: 1, column: 10.\nAt line: 2, column: 9.\n"
+        );
+    }
+
+    #[test]
+    fn shadowing_in_nested_scope_is_allowed() {
+        let code = "i64 x = 1; if (true) { i64 x = 2; } fn f(i64 a): void { for (i64 i = 0; i < 1; i += 1) { i64 a = i; } }";
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        assert_eq!(checker.errors.len(), 0);
+    }
+
+    #[test]
+    fn mixed_logical_operators_warning_is_opt_in() {
+        let program = parse("bool a; bool b; bool c; bool x = a || b && c;");
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        assert_eq!(checker.warnings.len(), 0);
+    }
+
+    #[test]
+    fn warns_on_ignored_return_value() {
+        let code = r#"
+            fn f(): i64 { return 1; }
+            fn g(): void {}
+            f(); g(); i64 x = f(); abs(1); println("a"); print("a");
+        "#;
+        let program = parse(code);
+        let checker = check(
+            &program,
+            SemanticCheckerOptions {
+                warn_on_ignored_return_values: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(checker.errors.len(), 0);
+        assert_eq!(checker.warnings.len(), 2);
+        assert!(checker.warnings[0].message().contains("Return value of 'f' is ignored."));
+        assert!(checker.warnings[1].message().contains("Return value of 'abs' is ignored."));
+
+        let checker = check(&program, SemanticCheckerOptions::default());
+        assert_eq!(checker.warnings.len(), 0);
+    }
+
+    #[test]
+    fn warns_on_unused_variables() {
+        let code = r#"
+            fn f(): i64 {
+                i64 unused = 1;
+                i64 used = 2;
+                return used;
+            }
+            i64 x = 1;
+            i64 y;
+            y = x;
+            for (i64 i = 0; i < 3; i = i + 1) {
+                str s = "a";
+            }
+        "#;
+        let program = parse(code);
+        let checker = check(
+            &program,
+            SemanticCheckerOptions {
+                warn_on_unused_variables: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(checker.errors.len(), 0);
+        let expected = [
+            ("s", "line: 11, column: 21"),
+            // globals are reported last, as functions can still read them
+            ("unused", "line: 3, column: 21"),
+            ("y", "line: 8, column: 17"),
+        ];
+        assert_eq!(checker.warnings.len(), expected.len());
+        for (warning, (name, position)) in checker.warnings.iter().zip(expected) {
+            assert_eq!(
+                warning.message(),
+                format!("\nWarning:\nVariable '{}' is declared but never read.\nAt {}.\n", name, position)
+            );
+        }
+    }
+
+    #[test]
+    fn reports_undeclared_variables() {
+        let code = r#"
+            i64 counter = 0;
+            countr = 1;
+            i64 x = conter + 1;
+            if (true) { i64 inner = 1; }
+            x = inner;
+            fn f(i64 a): i64 { return inner; }
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let expected = [
+            ("countr", "line: 3, column: 13"),
+            ("conter", "line: 4, column: 21"),
+            ("inner", "line: 6, column: 17"),
+            ("inner", "line: 7, column: 39"),
+        ];
+        assert_eq!(checker.errors.len(), expected.len());
+        for (error, (name, position)) in checker.errors.iter().zip(expected) {
+            assert_eq!(error.message(), format!("Use of undeclared variable '{}'.\nAt {}.\n", name, position));
+        }
+    }
+
+    #[test]
+    fn no_error_on_declared_variables() {
+        let code = r#"
+            fn f(i64 a, &i64 b): i64 { b = a; return a + b; }
+            i64 x = 1;
+            if (true) {
+                str x = "shadowed";
+                x = "still shadowed";
+            }
+            for (i64 i = 0; i < 3; i = i + 1) {
+                x = x + i;
+            }
+            switch (x: value) {
+                (value > 1) -> { x = value; }
+            }
+            i64 y = f(x, &x);
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        assert_eq!(checker.errors.len(), 0);
+    }
+
+    #[test]
+    fn reports_assignment_to_constants() {
+        let code = r#"
+            fn inc(&i64 x): void { x += 1; }
+            const i64 limit = 10;
+            i64 counter = 0;
+            counter = limit;
+            limit = 5;
+            limit += 1;
+            inc(&limit);
+            for (const i64 i = 0; i < limit; i += 1) {}
+            const [i64] values = [1, 2];
+            values[0] = 3;
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let expected = [
+            "Cannot assign to constant 'limit'.\nAt line: 6, column: 13.\n",
+            "Cannot assign to constant 'limit'.\nAt line: 7, column: 13.\n",
+            "Cannot pass constant 'limit' by reference.\nAt line: 8, column: 18.\n",
+            "Cannot assign to constant 'i'.\nAt line: 9, column: 46.\n",
+            "Cannot assign to constant 'values'.\nAt line: 11, column: 13.\n",
+        ];
+        assert_eq!(checker.errors.len(), expected.len());
+        for (error, message) in checker.errors.iter().zip(expected) {
+            assert_eq!(error.message(), message);
+        }
+    }
+
+    #[test]
+    fn reports_destructuring_type_mismatch() {
+        let code = r#"
+            fn pair(): (i64, str) { return (1, "a"); }
+            (i64 a, str b) = pair();
+            (i64 c, i64 d) = pair();
+            (i64 e, i64 f) = (1, 2, 3);
+            (i64 g, i64 h) = (a, a);
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let expected = [
+            "Cannot destructure value of type '(i64, str)' into '(i64, i64)'.\nAt line: 4, column: 13.\n",
+            "Cannot destructure value of type '(i64, i64, i64)' into '(i64, i64)'.\nAt line: 5, column: 13.\n",
+        ];
+        assert_eq!(checker.errors.len(), expected.len());
+        for (error, message) in checker.errors.iter().zip(expected) {
+            assert_eq!(error.message(), message);
+        }
+    }
+
+    #[test]
+    fn warns_on_unreachable_code() {
+        let code = r#"
+            fn f(i64 x): i64 {
+                if (x > 0) {
+                    return 1;
+                }
+                return x;
+                x = 1;
+                x = 2;
+            }
+            for (i64 i = 0; i < 3; i = i + 1) {
+                break;
+                println(i);
+            }
+        "#;
+        let program = parse(code);
+        let checker = check(
+            &program,
+            SemanticCheckerOptions {
+                warn_on_unreachable_code: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(checker.errors.len(), 0);
+        let expected = [
+            ("break", "line: 12, column: 17"),
+            ("return", "line: 7, column: 17"),
+            ("return", "line: 8, column: 17"),
+        ];
+        assert_eq!(checker.warnings.len(), expected.len());
+        for (warning, (terminated_by, position)) in checker.warnings.iter().zip(expected) {
+            assert_eq!(
+                warning.message(),
+                format!("\nWarning:\nUnreachable statement after '{}'.\nAt {}.\n", terminated_by, position)
+            );
+        }
+    }
+
+    #[test]
+    fn no_warning_on_return_inside_branch() {
+        let code = r#"
+            fn f(i64 x): i64 {
+                if (x > 0) {
+                    return 1;
+                } else {
+                    x = 2;
+                }
+                switch (x) {
+                    (x > 1) -> { break; }
+                }
+                return x;
+            }
+        "#;
+        let program = parse(code);
+        let checker = check(
+            &program,
+            SemanticCheckerOptions {
+                warn_on_unreachable_code: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(checker.errors.len(), 0);
+        assert_eq!(checker.warnings.len(), 0);
+    }
+
+    #[test]
+    fn reports_missing_return() {
+        let code = r#"
+            fn missing_in_branch(i64 x): i64 {
+                if (x > 0) {
+                    return 1;
+                } else {
+                    x = 2;
+                }
+            }
+            fn missing_else(i64 x): i64 {
+                if (x > 0) {
+                    return 1;
+                }
+            }
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let mut messages: Vec<String> = checker.errors.iter().map(|error| error.message()).collect();
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                "Function 'missing_else' may not return a value on all paths.\nAt line: 9, column: 13.\n",
+                "Function 'missing_in_branch' may not return a value on all paths.\nAt line: 2, column: 13.\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn no_error_when_all_paths_return() {
+        let code = r#"
+            fn both_branches(i64 x): i64 {
+                if (x > 0) {
+                    return 1;
+                } else {
+                    if (x < 0) { return -1; } else { return 0; }
+                }
+            }
+            fn after_loop(i64 x): i64 {
+                for (; x < 5; x = x + 1) { return x; }
+                return 5;
+            }
+            fn nothing(): void {}
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        assert_eq!(checker.errors.len(), 0);
+    }
+
+    #[test]
+    fn switch_with_default_returns_on_all_paths() {
+        let code = r#"
+            fn sign(i64 x): i64 {
+                switch (x: value) {
+                    (value > 0) -> { return 1; }
+                    (value < 0) -> { if (value < -10) { return -10; } else { return -1; } }
+                    default -> { return 0; }
+                }
+            }
+            fn no_default(i64 x): i64 {
+                switch (x) {
+                    (x > 0) -> { return 1; }
+                }
+            }
+            fn breaking(i64 x): i64 {
+                switch (x) {
+                    (x > 0) -> { if (x > 5) { break; } return 1; }
+                    default -> { return 0; }
+                }
+            }
+            fn falling_through(i64 x): i64 {
+                switch (x) {
+                    (x > 0) -> { fallthrough; }
+                    (x > 5) -> { return 5; }
+                    default -> { return 0; }
+                }
+            }
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        let mut messages: Vec<String> = checker.errors.iter().map(|error| error.message()).collect();
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                "Function 'breaking' may not return a value on all paths.\nAt line: 14, column: 13.\n",
+                "Function 'falling_through' may not return a value on all paths.\nAt line: 20, column: 13.\n",
+                "Function 'no_default' may not return a value on all paths.\nAt line: 9, column: 13.\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn warns_on_shadowing() {
+        let code = r#"
+            i64 x = 1;
+            fn f(i64 x): i64 { return x; }
+            if (x > 0) {
+                str x = "inner";
+            }
+        "#;
+        let program = parse(code);
+        let checker = check(
+            &program,
+            SemanticCheckerOptions {
+                warn_on_shadowing: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(checker.errors.len(), 0);
+        assert_eq!(
+            checker.warnings.iter().map(|warning| warning.message()).collect::<Vec<String>>(),
+            vec![
+                "\nWarning:\nVariable 'x' shadows a variable declared at line: 2, column: 17.\nAt line: 5, column: 21.\n",
+                "\nWarning:\nParameter 'x' shadows a variable declared at line: 2, column: 17.\nAt line: 3, column: 22.\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn no_warning_without_shadowing() {
+        let code = r#"
+            i64 x = 1;
+            fn f(i64 a): i64 { i64 b = a; return b; }
+            if (x > 0) { i64 y = x; }
+            if (x > 0) { i64 y = x; }
+        "#;
+        let program = parse(code);
+        let checker = check(
+            &program,
+            SemanticCheckerOptions {
+                warn_on_shadowing: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(checker.errors.len(), 0);
+        assert_eq!(checker.warnings.len(), 0);
+    }
+
+    #[test]
+    fn reports_non_boolean_switch_case_conditions() {
+        let code = r#"
+            fn f(): str { return "a"; }
+            i64 x = 1;
+            switch (x: value) {
+                (1) -> {}
+                (x + 1) -> {}
+                (f()) -> {}
+                (x < 5) -> {}
+                (value == 1) -> {}
+                (value) -> {}
+            }
+        "#;
+        let program = parse(code);
+        let checker = check(&program, SemanticCheckerOptions::default());
+
+        assert_eq!(
+            checker.errors.iter().map(|error| error.message()).collect::<Vec<String>>(),
+            vec![
+                "Condition in 'switch case' has to evaluate to type 'bool' - got 'i64'.\nAt line: 5, column: 18.\n",
+                "Condition in 'switch case' has to evaluate to type 'bool' - got 'i64'.\nAt line: 6, column: 20.\n",
+                "Condition in 'switch case' has to evaluate to type 'bool' - got 'str'.\nAt line: 7, column: 18.\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn no_warning_on_read_variables() {
+        let code = r#"
+            fn inc(&i64 value): void { value = value + 1; }
+            i64 a = 1;
+            i64 b = a;
+            inc(&b);
+        "#;
+        let program = parse(code);
+        let checker = check(
+            &program,
+            SemanticCheckerOptions {
+                warn_on_unused_variables: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(checker.errors.len(), 0);
+        assert_eq!(checker.warnings.len(), 0);
+    }
+}
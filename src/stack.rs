@@ -6,8 +6,13 @@ use crate::{
     value::Value,
 };
 
+const DEFAULT_MAX_DEPTH: usize = 500;
+
 #[derive(Debug, Clone)]
-pub struct Stack<'a>(pub Vec<StackFrame<'a>>);
+pub struct Stack<'a> {
+    pub frames: Vec<StackFrame<'a>>,
+    max_depth: usize,
+}
 
 #[derive(Clone)]
 pub struct StackFrame<'a> {
@@ -28,55 +33,112 @@ impl<'a> StackFrame<'a> {
     }
 }
 
+impl<'a> Default for StackFrame<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> Stack<'a> {
     pub fn new() -> Self {
-        Stack(vec![StackFrame::new()])
+        Self::with_max_depth(DEFAULT_MAX_DEPTH)
+    }
+
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Stack {
+            frames: vec![StackFrame::new()],
+            max_depth,
+        }
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
     }
 
     pub fn push_stack_frame(&mut self) -> Result<(), StackOverflowError> {
-        if self.0.len() == 500 {
+        if self.frames.len() >= self.max_depth {
             return Err(StackOverflowError::new(ErrorSeverity::HIGH, String::from("Stack overflow.")));
         }
-        self.0.push(StackFrame::new());
+        self.frames.push(StackFrame::new());
         Ok(())
     }
 
     pub fn pop_stack_frame(&mut self) {
-        self.0.pop();
+        self.frames.pop();
+    }
+
+    pub fn global_variables(&self) -> Vec<(&'a str, Rc<RefCell<Value>>, bool)> {
+        self.frames.first().map_or(vec![], |frame| frame.scope_manager.global_variables())
     }
 
     pub fn push_scope(&mut self) {
-        if let Some(last_frame) = self.0.last_mut() {
+        if let Some(last_frame) = self.frames.last_mut() {
             last_frame.scope_manager.push_scope();
         }
     }
 
     pub fn pop_scope(&mut self) {
-        if let Some(last_frame) = self.0.last_mut() {
+        if let Some(last_frame) = self.frames.last_mut() {
             last_frame.scope_manager.pop_scope();
         }
     }
 
     pub fn get_variable(&mut self, name: &'a str) -> Result<&Rc<RefCell<Value>>, ScopeManagerError> {
-        if let Some(last_frame) = self.0.last_mut() {
+        if self.is_global(name) {
+            return Ok(self.frames[0].scope_manager.get_global_variable(name).unwrap());
+        }
+        if let Some(last_frame) = self.frames.last_mut() {
             return last_frame.scope_manager.get_variable(name);
         }
         unreachable!();
     }
 
+    // functions can read top-level variables they don't shadow, but never the locals of their callers
+    pub fn is_global(&self, name: &'a str) -> bool {
+        match self.frames.split_first() {
+            Some((global_frame, [.., last_frame])) => {
+                last_frame.scope_manager.get_variable(name).is_err() && global_frame.scope_manager.get_global_variable(name).is_some()
+            }
+            _ => false,
+        }
+    }
+
     pub fn assign_variable(&mut self, name: &'a str, value: Rc<RefCell<Value>>) -> Result<(), ScopeManagerError> {
-        if let Some(last_frame) = self.0.last_mut() {
+        if self.is_global(name) {
+            return Err(ScopeManagerError::new(
+                ErrorSeverity::HIGH,
+                format!("Cannot modify global variable '{}' inside a function.", name),
+            ));
+        }
+        if let Some(last_frame) = self.frames.last_mut() {
             last_frame.scope_manager.assign_variable(name, value)?;
         }
         Ok(())
     }
 
     pub fn declare_variable(&mut self, name: &'a str, value: Rc<RefCell<Value>>) -> Result<(), ScopeManagerError> {
-        if let Some(last_frame) = self.0.last_mut() {
+        if let Some(last_frame) = self.frames.last_mut() {
             last_frame.scope_manager.declare_variable(name, value)?;
         }
         Ok(())
     }
+
+    pub fn declare_constant(&mut self, name: &'a str, value: Rc<RefCell<Value>>) -> Result<(), ScopeManagerError> {
+        if let Some(last_frame) = self.frames.last_mut() {
+            last_frame.scope_manager.declare_constant(name, value)?;
+        }
+        Ok(())
+    }
+
+    pub fn is_constant(&self, name: &'a str) -> bool {
+        self.frames.last().is_some_and(|frame| frame.scope_manager.is_constant(name))
+    }
+}
+
+impl<'a> Default for Stack<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -89,13 +151,13 @@ mod tests {
     fn test_stack_push_pop_frame() {
         let mut stack = Stack::new();
 
-        assert_eq!(stack.0.len(), 1);
+        assert_eq!(stack.frames.len(), 1);
 
         stack.push_stack_frame().unwrap();
-        assert_eq!(stack.0.len(), 2);
+        assert_eq!(stack.frames.len(), 2);
 
         stack.pop_stack_frame();
-        assert_eq!(stack.0.len(), 1);
+        assert_eq!(stack.frames.len(), 1);
     }
 
     #[test]
@@ -106,7 +168,7 @@ mod tests {
             stack.push_stack_frame().unwrap();
         }
 
-        assert_eq!(stack.0.len(), 500);
+        assert_eq!(stack.frames.len(), 500);
         let result = stack.push_stack_frame();
         if let Err(e) = result {
             assert_eq!(e.message(), "Stack overflow.");
@@ -118,12 +180,12 @@ mod tests {
         let mut stack = Stack::new();
 
         stack.push_scope();
-        if let Some(last_frame) = stack.0.last() {
+        if let Some(last_frame) = stack.frames.last() {
             assert_eq!(last_frame.scope_manager.len(), 2);
         }
 
         stack.pop_scope();
-        if let Some(last_frame) = stack.0.last() {
+        if let Some(last_frame) = stack.frames.last() {
             assert_eq!(last_frame.scope_manager.len(), 1);
         }
     }
@@ -144,4 +206,72 @@ mod tests {
         let updated_value = stack.get_variable(var_name).unwrap();
         assert_eq!(updated_value, &new_value);
     }
+
+    #[test]
+    fn test_frames_are_isolated() {
+        let mut stack = Stack::new();
+        stack.push_stack_frame().unwrap();
+        stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(42)))).unwrap();
+
+        stack.push_stack_frame().unwrap();
+        assert_eq!(
+            stack.get_variable("x").err().unwrap().message(),
+            "Variable 'x' not declared in this scope."
+        );
+        assert!(stack.assign_variable("x", Rc::new(RefCell::new(Value::I64(1)))).is_err());
+
+        stack.pop_stack_frame();
+        assert_eq!(*stack.get_variable("x").unwrap().borrow(), Value::I64(42));
+    }
+
+    #[test]
+    fn test_globals_are_readable_in_functions() {
+        let mut stack = Stack::new();
+        stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(42)))).unwrap();
+        stack.push_scope();
+        stack.declare_variable("y", Rc::new(RefCell::new(Value::I64(1)))).unwrap();
+
+        stack.push_stack_frame().unwrap();
+        assert_eq!(*stack.get_variable("x").unwrap().borrow(), Value::I64(42));
+        // only the top-level scope is global
+        assert!(stack.get_variable("y").is_err());
+        assert_eq!(
+            stack.assign_variable("x", Rc::new(RefCell::new(Value::I64(1)))).err().unwrap().message(),
+            "Cannot modify global variable 'x' inside a function."
+        );
+
+        stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(7)))).unwrap();
+        assert!(!stack.is_global("x"));
+        assert_eq!(*stack.get_variable("x").unwrap().borrow(), Value::I64(7));
+    }
+
+    #[test]
+    fn test_global_variables() {
+        let mut stack = Stack::new();
+        stack.declare_variable("x", Rc::new(RefCell::new(Value::I64(1)))).unwrap();
+        stack.declare_constant("c", Rc::new(RefCell::new(Value::I64(2)))).unwrap();
+        stack.push_scope();
+        stack.declare_variable("y", Rc::new(RefCell::new(Value::I64(3)))).unwrap();
+
+        let mut globals = stack.global_variables();
+        globals.sort_by_key(|(name, _, _)| *name);
+        assert_eq!(
+            globals,
+            vec![
+                ("c", Rc::new(RefCell::new(Value::I64(2))), true),
+                ("x", Rc::new(RefCell::new(Value::I64(1))), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_configurable_max_depth() {
+        let mut stack = Stack::with_max_depth(3);
+        assert_eq!(stack.max_depth(), 3);
+
+        stack.push_stack_frame().unwrap();
+        stack.push_stack_frame().unwrap();
+        assert_eq!(stack.push_stack_frame().err().unwrap().message(), "Stack overflow.");
+        assert_eq!(stack.frames.len(), 3);
+    }
 }
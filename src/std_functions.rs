@@ -1,118 +1,1028 @@
 use std::{
     cell::RefCell,
     collections::HashMap,
-    io::{self, Write},
+    fmt::Debug,
+    io::{BufRead, Write},
     rc::Rc,
 };
 
 use crate::{
     ast::Type,
-    errors::{ErrorSeverity, StdFunctionError},
-    value::Value,
+    errors::{ComputationError, ErrorSeverity, IError, StdFunctionError},
+    value::{Value, MAX_ARRAY_SIZE},
 };
 
-#[derive(Debug, Clone, PartialEq)]
+// Output is written through the writer owned by the interpreter (stdout by default),
+// input is read through its reader (stdin by default).
+// Output of `print` is flushed right away, so it shows up even without a trailing newline.
+// `println` relies on the line buffering of the writer.
+pub type StdFunctionExecute = Rc<dyn Fn(&Vec<Rc<RefCell<Value>>>, &mut dyn Write, &mut dyn BufRead) -> Result<Option<Value>, StdFunctionError>>;
+
+#[derive(Clone)]
 pub struct StdFunction {
     pub params: Vec<Type>,
-    pub execute: fn(&Vec<Rc<RefCell<Value>>>) -> Result<Option<Value>, StdFunctionError>,
+    pub variadic: bool,
+    // no side effects and the result depends only on the arguments, so calls can be memoized
+    pub is_pure: bool,
+    pub execute: StdFunctionExecute,
+}
+
+impl Debug for StdFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StdFunction")
+            .field("params", &self.params)
+            .field("variadic", &self.variadic)
+            .field("is_pure", &self.is_pure)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for StdFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.params == other.params && self.variadic == other.variadic && self.is_pure == other.is_pure && Rc::ptr_eq(&self.execute, &other.execute)
+    }
 }
 
 impl StdFunction {
-    fn print() -> Self {
-        let params = vec![Type::Str];
-        let execute = |params: &Vec<Rc<RefCell<Value>>>| -> Result<Option<Value>, StdFunctionError> {
-            if let Some(value) = params.get(0) {
-                let value = value.borrow();
-                match &*value {
-                    Value::String(text) => {
-                        println!("{}", text);
-                        Ok(None)
-                    }
-                    _ => Err(StdFunctionError::new(
+    // a function provided by the program embedding the interpreter, called with arguments of the given types
+    pub fn custom<F>(name: &str, params: Vec<Type>, function: F) -> Self
+    where
+        F: Fn(&Vec<Rc<RefCell<Value>>>) -> Result<Option<Value>, ComputationError> + 'static,
+    {
+        let name = name.to_owned();
+        let expected = params.clone();
+        let execute = move |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| {
+            if params.len() != expected.len() {
+                return Err(StdFunctionError::new(
+                    ErrorSeverity::HIGH,
+                    format!(
+                        "Std function '{}' expected {} arguments, but was given {}.",
+                        name,
+                        expected.len(),
+                        params.len()
+                    ),
+                ));
+            }
+            for (param, expected_type) in params.iter().zip(&expected) {
+                let given_type = param.borrow().to_type();
+                if given_type != *expected_type {
+                    return Err(StdFunctionError::new(
                         ErrorSeverity::HIGH,
                         format!(
-                            "Std function 'print' expected '{:?}' as the only argument, but was given '{:?}'.",
-                            Type::Str,
-                            value.to_type()
+                            "Std function '{}' expected '{}' as an argument, but was given '{}'.",
+                            name, expected_type, given_type
                         ),
-                    )),
+                    ));
                 }
-            } else {
-                Err(StdFunctionError::new(
-                    ErrorSeverity::HIGH,
-                    String::from("Missing argument for 'print' function."),
-                ))
             }
+            function(params).map_err(|err| StdFunctionError::new(ErrorSeverity::HIGH, err.message()))
         };
-        StdFunction { params, execute }
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: false,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn print() -> Self {
+        // accepts any number of arguments of any type
+        let params = vec![];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                let text = write_arguments(writer, params, false)?;
+                writer
+                    .flush()
+                    .map_err(|_| StdFunctionError::new(ErrorSeverity::HIGH, String::from("Failed to write output.")))?;
+                Ok(Some(Value::String(text)))
+            };
+        StdFunction {
+            params,
+            variadic: true,
+            is_pure: false,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn println() -> Self {
+        // accepts any number of arguments of any type
+        let params = vec![];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                let text = write_arguments(writer, params, true)?;
+                Ok(Some(Value::String(text)))
+            };
+        StdFunction {
+            params,
+            variadic: true,
+            is_pure: false,
+            execute: Rc::new(execute),
+        }
     }
 
     fn input() -> Self {
         let params = vec![Type::Str];
-        let execute = |params: &Vec<Rc<RefCell<Value>>>| -> Result<Option<Value>, StdFunctionError> {
-            if let Some(value) = params.get(0) {
-                let value = value.borrow();
-                match &*value {
-                    Value::String(prompt) => {
-                        print!("{}", prompt);
-                        io::stdout().flush().unwrap();
-                        let mut input = String::new();
-                        match io::stdin().read_line(&mut input) {
-                            Ok(_) => Ok(Some(Value::String(input.trim().to_string()))),
-                            Err(_) => Err(StdFunctionError::new(ErrorSeverity::HIGH, String::from("Failed to read input."))),
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, writer: &mut dyn Write, reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let Some(value) = params.get(0) {
+                    let value = value.borrow();
+                    match &*value {
+                        Value::String(prompt) => {
+                            write!(writer, "{}", prompt)
+                                .and_then(|_| writer.flush())
+                                .map_err(|_| StdFunctionError::new(ErrorSeverity::HIGH, String::from("Failed to write output.")))?;
+                            let input = read_input_line(reader)?;
+                            Ok(Some(Value::String(input.trim().to_string())))
                         }
+                        _ => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!(
+                                "Std function 'input' expected '{}' as the only argument, but was given '{}'.",
+                                Type::Str,
+                                value.to_type()
+                            ),
+                        )),
                     }
-                    _ => Err(StdFunctionError::new(
+                } else {
+                    Err(StdFunctionError::new(
                         ErrorSeverity::HIGH,
-                        format!(
-                            "Std function 'input' expected '{:?}' as the only argument, but was given '{:?}'.",
-                            Type::Str,
-                            value.to_type()
-                        ),
-                    )),
+                        String::from("Missing argument for 'input' function."),
+                    ))
                 }
-            } else {
-                Err(StdFunctionError::new(
-                    ErrorSeverity::HIGH,
-                    String::from("Missing argument for 'input' function."),
-                ))
-            }
-        };
-        StdFunction { params, execute }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: false,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn read_line() -> Self {
+        // returns an empty string once the end of input is reached
+        let params = vec![];
+        let execute =
+            |_params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                let mut input = read_input_line(reader)?;
+                if input.ends_with('\n') {
+                    input.pop();
+                    if input.ends_with('\r') {
+                        input.pop();
+                    }
+                }
+                Ok(Some(Value::String(input)))
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: false,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn input_int() -> Self {
+        let params = vec![];
+        let execute =
+            |_params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                let input = read_input_line(reader)?;
+                let input = input.trim();
+                match input.parse::<i64>() {
+                    Ok(int) => Ok(Some(Value::I64(int))),
+                    Err(_) => Err(StdFunctionError::new(ErrorSeverity::HIGH, format!("Invalid integer input: '{}'.", input))),
+                }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: false,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn input_float() -> Self {
+        let params = vec![];
+        let execute =
+            |_params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                let input = read_input_line(reader)?;
+                let input = input.trim();
+                match input.parse::<f64>() {
+                    Ok(float) if float.is_finite() => Ok(Some(Value::F64(float))),
+                    _ => Err(StdFunctionError::new(ErrorSeverity::HIGH, format!("Invalid float input: '{}'.", input))),
+                }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: false,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn abs() -> Self {
+        // accepts i64 or f64
+        let params = vec![Type::I64];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let Some(value) = params.first() {
+                    let value = value.borrow();
+                    match &*value {
+                        Value::I64(i64) => match i64.checked_abs() {
+                            Some(result) => Ok(Some(Value::I64(result))),
+                            None => Err(StdFunctionError::new(
+                                ErrorSeverity::HIGH,
+                                format!("Overflow occurred when calling 'abs' on '{}'.", i64),
+                            )),
+                        },
+                        Value::F64(f64) => Ok(Some(Value::F64(f64.abs()))),
+                        _ => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!("Cannot call 'abs' on type '{}'.", value.to_type()),
+                        )),
+                    }
+                } else {
+                    Err(StdFunctionError::new(
+                        ErrorSeverity::HIGH,
+                        String::from("Missing argument for 'abs' function."),
+                    ))
+                }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn sqrt() -> Self {
+        let params = vec![Type::F64];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let Some(Value::F64(f64)) = params.first().map(|value| value.borrow().clone()) {
+                    if f64 < 0.0 {
+                        return Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!("Cannot calculate 'sqrt' of negative number '{}'.", f64),
+                        ));
+                    }
+                }
+                map_float(params, "sqrt", f64::sqrt)
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn floor() -> Self {
+        let params = vec![Type::F64];
+        let execute = |params: &Vec<Rc<RefCell<Value>>>,
+                       _writer: &mut dyn Write,
+                       _reader: &mut dyn BufRead|
+         -> Result<Option<Value>, StdFunctionError> { map_float(params, "floor", f64::floor) };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn ceil() -> Self {
+        let params = vec![Type::F64];
+        let execute = |params: &Vec<Rc<RefCell<Value>>>,
+                       _writer: &mut dyn Write,
+                       _reader: &mut dyn BufRead|
+         -> Result<Option<Value>, StdFunctionError> { map_float(params, "ceil", f64::ceil) };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn round() -> Self {
+        let params = vec![Type::F64];
+        let execute = |params: &Vec<Rc<RefCell<Value>>>,
+                       _writer: &mut dyn Write,
+                       _reader: &mut dyn BufRead|
+         -> Result<Option<Value>, StdFunctionError> { map_float(params, "round", f64::round) };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn len() -> Self {
+        // accepts a string or an array
+        let params = vec![Type::Str];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let Some(value) = params.first() {
+                    let value = value.borrow();
+                    let length = match &*value {
+                        Value::String(text) => text.chars().count(),
+                        Value::Array(_, values) => values.borrow().len(),
+                        _ => {
+                            return Err(StdFunctionError::new(
+                                ErrorSeverity::HIGH,
+                                format!("Cannot call 'len' on type '{}'.", value.to_type()),
+                            ))
+                        }
+                    };
+                    Ok(Some(Value::I64(length as i64)))
+                } else {
+                    Err(StdFunctionError::new(
+                        ErrorSeverity::HIGH,
+                        String::from("Missing argument for 'len' function."),
+                    ))
+                }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn push() -> Self {
+        // the element type is only known at runtime, so it is checked against the array's
+        let params = vec![Type::Array(Box::new(Type::Void)), Type::Void];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let (Some(array), Some(value)) = (params.first(), params.get(1)) {
+                    let array = array.borrow();
+                    let value = value.borrow();
+                    match &*array {
+                        // the estimate walks the whole array, so it only runs when the buffer is full and would double
+                        Value::Array(element_type, values)
+                            if *element_type == value.to_type()
+                                && values.borrow().len() == values.borrow().capacity()
+                                && array.approx_size().saturating_mul(2) > MAX_ARRAY_SIZE =>
+                        {
+                            Err(StdFunctionError::new(
+                                ErrorSeverity::HIGH,
+                                format!("Array would exceed the maximum size of {} bytes.", MAX_ARRAY_SIZE),
+                            ))
+                        }
+                        Value::Array(element_type, values) if *element_type == value.to_type() => {
+                            values.borrow_mut().push(value.deep_copy());
+                            Ok(None)
+                        }
+                        Value::Array(..) => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!("Cannot push value of type '{}' to array of type '{}'.", value.to_type(), array.to_type()),
+                        )),
+                        _ => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!("Cannot call 'push' on type '{}'.", array.to_type()),
+                        )),
+                    }
+                } else {
+                    Err(StdFunctionError::new(
+                        ErrorSeverity::HIGH,
+                        String::from("Missing arguments for 'push' function."),
+                    ))
+                }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: false,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn pop() -> Self {
+        let params = vec![Type::Array(Box::new(Type::Void))];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let Some(array) = params.first() {
+                    let array = array.borrow();
+                    match &*array {
+                        Value::Array(_, values) => match values.borrow_mut().pop() {
+                            Some(value) => Ok(Some(value)),
+                            None => Err(StdFunctionError::new(
+                                ErrorSeverity::HIGH,
+                                String::from("Cannot pop from an empty array."),
+                            )),
+                        },
+                        _ => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!("Cannot call 'pop' on type '{}'.", array.to_type()),
+                        )),
+                    }
+                } else {
+                    Err(StdFunctionError::new(
+                        ErrorSeverity::HIGH,
+                        String::from("Missing argument for 'pop' function."),
+                    ))
+                }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: false,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn substring() -> Self {
+        let params = vec![Type::Str, Type::I64, Type::I64];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let (Some(text), Some(start), Some(length)) = (params.first(), params.get(1), params.get(2)) {
+                    let text = text.borrow();
+                    let start = start.borrow();
+                    let length = length.borrow();
+                    match (&*text, &*start, &*length) {
+                        (Value::String(text), Value::I64(start), Value::I64(length)) => {
+                            // offsets are counted in characters, not bytes
+                            let text_length = text.chars().count() as i64;
+                            if *start < 0 || *length < 0 || start.saturating_add(*length) > text_length {
+                                return Err(StdFunctionError::new(
+                                    ErrorSeverity::HIGH,
+                                    format!(
+                                        "Range starting at {} with length {} is out of bounds for string of length {}.",
+                                        start, length, text_length
+                                    ),
+                                ));
+                            }
+                            let result: String = text.chars().skip(*start as usize).take(*length as usize).collect();
+                            Ok(Some(Value::String(result)))
+                        }
+                        _ => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!(
+                                "Std function 'substring' expected arguments of types '{}', '{}', '{}', but was given '{}', '{}', '{}'.",
+                                Type::Str,
+                                Type::I64,
+                                Type::I64,
+                                text.to_type(),
+                                start.to_type(),
+                                length.to_type()
+                            ),
+                        )),
+                    }
+                } else {
+                    Err(StdFunctionError::new(
+                        ErrorSeverity::HIGH,
+                        String::from("Missing arguments for 'substring' function."),
+                    ))
+                }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn split() -> Self {
+        let params = vec![Type::Str, Type::Str];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let (Some(text), Some(delimiter)) = (params.first(), params.get(1)) {
+                    let text = text.borrow();
+                    let delimiter = delimiter.borrow();
+                    match (&*text, &*delimiter) {
+                        (Value::String(_), Value::String(delimiter)) if delimiter.is_empty() => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            String::from("Cannot split string on an empty delimiter."),
+                        )),
+                        (Value::String(text), Value::String(delimiter)) => {
+                            let segments = text.split(delimiter.as_str()).map(|segment| Value::String(segment.to_owned())).collect();
+                            Ok(Some(Value::Array(Type::Str, Rc::new(RefCell::new(segments)))))
+                        }
+                        _ => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!(
+                                "Std function 'split' expected arguments of types '{}', '{}', but was given '{}', '{}'.",
+                                Type::Str,
+                                Type::Str,
+                                text.to_type(),
+                                delimiter.to_type()
+                            ),
+                        )),
+                    }
+                } else {
+                    Err(StdFunctionError::new(
+                        ErrorSeverity::HIGH,
+                        String::from("Missing arguments for 'split' function."),
+                    ))
+                }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn contains() -> Self {
+        let params = vec![Type::Str, Type::Str];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                search_string(params, "contains", |haystack, needle| Value::Bool(haystack.contains(needle)))
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn index_of() -> Self {
+        let params = vec![Type::Str, Type::Str];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                // counted in characters, like the offsets of 'substring'
+                search_string(params, "index_of", |haystack, needle| {
+                    Value::I64(
+                        haystack
+                            .find(needle)
+                            .map_or(-1, |byte_index| haystack[..byte_index].chars().count() as i64),
+                    )
+                })
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn to_upper() -> Self {
+        let params = vec![Type::Str];
+        let execute = |params: &Vec<Rc<RefCell<Value>>>,
+                       _writer: &mut dyn Write,
+                       _reader: &mut dyn BufRead|
+         -> Result<Option<Value>, StdFunctionError> { map_string(params, "to_upper", str::to_uppercase) };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn to_lower() -> Self {
+        let params = vec![Type::Str];
+        let execute = |params: &Vec<Rc<RefCell<Value>>>,
+                       _writer: &mut dyn Write,
+                       _reader: &mut dyn BufRead|
+         -> Result<Option<Value>, StdFunctionError> { map_string(params, "to_lower", str::to_lowercase) };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn min() -> Self {
+        // accepts two i64 or two f64
+        let params = vec![Type::I64, Type::I64];
+        let execute = |params: &Vec<Rc<RefCell<Value>>>,
+                       _writer: &mut dyn Write,
+                       _reader: &mut dyn BufRead|
+         -> Result<Option<Value>, StdFunctionError> { select_number(params, "min", true) };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn max() -> Self {
+        // accepts two i64 or two f64
+        let params = vec![Type::I64, Type::I64];
+        let execute = |params: &Vec<Rc<RefCell<Value>>>,
+                       _writer: &mut dyn Write,
+                       _reader: &mut dyn BufRead|
+         -> Result<Option<Value>, StdFunctionError> { select_number(params, "max", false) };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+
+    fn pow() -> Self {
+        // accepts two i64 or two f64
+        let params = vec![Type::I64, Type::I64];
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let (Some(base), Some(exponent)) = (params.first(), params.get(1)) {
+                    let base = base.borrow();
+                    let exponent = exponent.borrow();
+                    match (&*base, &*exponent) {
+                        (Value::I64(_), Value::I64(exp)) if *exp < 0 => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!("Cannot raise i64 to negative power '{}'.", exp),
+                        )),
+                        (Value::I64(base), Value::I64(exp)) => match u32::try_from(*exp).ok().and_then(|exp| base.checked_pow(exp)) {
+                            Some(result) => Ok(Some(Value::I64(result))),
+                            None => Err(StdFunctionError::new(
+                                ErrorSeverity::HIGH,
+                                String::from("Overflow occurred when calling 'pow' on i64s."),
+                            )),
+                        },
+                        (Value::F64(base), Value::F64(exp)) => {
+                            let result = base.powf(*exp);
+                            if result.is_infinite() || result.is_nan() {
+                                return Err(StdFunctionError::new(
+                                    ErrorSeverity::HIGH,
+                                    String::from("Invalid result when calling 'pow' on f64s."),
+                                ));
+                            }
+                            Ok(Some(Value::F64(result)))
+                        }
+                        _ => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!(
+                                "Cannot perform 'pow' between values of type '{}' and '{}'.",
+                                base.to_type(),
+                                exponent.to_type()
+                            ),
+                        )),
+                    }
+                } else {
+                    Err(StdFunctionError::new(
+                        ErrorSeverity::HIGH,
+                        String::from("Missing arguments for 'pow' function."),
+                    ))
+                }
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
     }
 
     fn modulo() -> Self {
         let params = vec![Type::I64, Type::I64];
-        let execute = |params: &Vec<Rc<RefCell<Value>>>| -> Result<Option<Value>, StdFunctionError> {
-            if let (Some(val1), Some(val2)) = (params.get(0), params.get(1)) {
-                let val1 = val1.borrow();
-                let val2 = val2.borrow();
-                match (&*val1, &*val2) {
-                    (Value::I64(val1), Value::I64(val2)) => Ok(Some(Value::I64(*val1 % *val2))),
-                    _ => Err(StdFunctionError::new(
+        let execute =
+            |params: &Vec<Rc<RefCell<Value>>>, _writer: &mut dyn Write, _reader: &mut dyn BufRead| -> Result<Option<Value>, StdFunctionError> {
+                if let (Some(val1), Some(val2)) = (params.get(0), params.get(1)) {
+                    let val1 = val1.borrow();
+                    let val2 = val2.borrow();
+                    match (&*val1, &*val2) {
+                        (Value::I64(_), Value::I64(0)) => Err(StdFunctionError::new(ErrorSeverity::HIGH, String::from("Division by zero."))),
+                        (Value::I64(val1), Value::I64(val2)) => match val1.checked_rem(*val2) {
+                            Some(result) => Ok(Some(Value::I64(result))),
+                            None => Err(StdFunctionError::new(
+                                ErrorSeverity::HIGH,
+                                format!("Overflow occurred when performing modulo operation on i64s: {} % {}.", val1, val2),
+                            )),
+                        },
+                        _ => Err(StdFunctionError::new(
+                            ErrorSeverity::HIGH,
+                            format!(
+                                "Cannot perform modulo operation between values of types '{}' and '{}'.",
+                                val1.to_type(),
+                                val2.to_type()
+                            ),
+                        )),
+                    }
+                } else {
+                    Err(StdFunctionError::new(
                         ErrorSeverity::HIGH,
-                        format!(
-                            "Cannot perform modulo operation between values of types '{:?}' and '{:?}'.",
-                            val1.to_type(),
-                            val2.to_type()
-                        ),
-                    )),
+                        String::from("Missing arguments for 'mod' function."),
+                    ))
                 }
-            } else {
-                Err(StdFunctionError::new(
+            };
+        StdFunction {
+            params,
+            variadic: false,
+            is_pure: true,
+            execute: Rc::new(execute),
+        }
+    }
+}
+
+fn read_input_line(reader: &mut dyn BufRead) -> Result<String, StdFunctionError> {
+    let mut input = String::new();
+    reader
+        .read_line(&mut input)
+        .map_err(|_| StdFunctionError::new(ErrorSeverity::HIGH, String::from("Failed to read input.")))?;
+    Ok(input)
+}
+
+fn join_arguments(params: &[Rc<RefCell<Value>>]) -> String {
+    params
+        .iter()
+        .map(|value| value.borrow().to_display_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn map_float(params: &[Rc<RefCell<Value>>], name: &str, op: fn(f64) -> f64) -> Result<Option<Value>, StdFunctionError> {
+    // i64 arguments are rejected, they have to be cast explicitly
+    if let Some(value) = params.first() {
+        let value = value.borrow();
+        match &*value {
+            Value::F64(f64) => Ok(Some(Value::F64(op(*f64)))),
+            _ => Err(StdFunctionError::new(
+                ErrorSeverity::HIGH,
+                format!("Cannot call '{}' on type '{}'.", name, value.to_type()),
+            )),
+        }
+    } else {
+        Err(StdFunctionError::new(
+            ErrorSeverity::HIGH,
+            format!("Missing argument for '{}' function.", name),
+        ))
+    }
+}
+
+fn map_string(params: &[Rc<RefCell<Value>>], name: &str, op: fn(&str) -> String) -> Result<Option<Value>, StdFunctionError> {
+    if let Some(value) = params.first() {
+        let value = value.borrow();
+        match &*value {
+            Value::String(text) => Ok(Some(Value::String(op(text)))),
+            _ => Err(StdFunctionError::new(
+                ErrorSeverity::HIGH,
+                format!("Cannot call '{}' on type '{}'.", name, value.to_type()),
+            )),
+        }
+    } else {
+        Err(StdFunctionError::new(
+            ErrorSeverity::HIGH,
+            format!("Missing argument for '{}' function.", name),
+        ))
+    }
+}
+
+fn search_string(params: &[Rc<RefCell<Value>>], name: &str, op: fn(&str, &str) -> Value) -> Result<Option<Value>, StdFunctionError> {
+    if let (Some(haystack), Some(needle)) = (params.first(), params.get(1)) {
+        let haystack = haystack.borrow();
+        let needle = needle.borrow();
+        match (&*haystack, &*needle) {
+            (Value::String(haystack), Value::String(needle)) => Ok(Some(op(haystack, needle))),
+            _ => Err(StdFunctionError::new(
+                ErrorSeverity::HIGH,
+                format!(
+                    "Std function '{}' expected arguments of types '{}', '{}', but was given '{}', '{}'.",
+                    name,
+                    Type::Str,
+                    Type::Str,
+                    haystack.to_type(),
+                    needle.to_type()
+                ),
+            )),
+        }
+    } else {
+        Err(StdFunctionError::new(
+            ErrorSeverity::HIGH,
+            format!("Missing arguments for '{}' function.", name),
+        ))
+    }
+}
+
+fn select_number(params: &[Rc<RefCell<Value>>], name: &str, smaller: bool) -> Result<Option<Value>, StdFunctionError> {
+    if let (Some(val1), Some(val2)) = (params.first(), params.get(1)) {
+        let val1 = val1.borrow();
+        let val2 = val2.borrow();
+        let first_is_smaller = match (&*val1, &*val2) {
+            (Value::I64(a), Value::I64(b)) => a <= b,
+            (Value::F64(a), Value::F64(b)) => a <= b,
+            _ => {
+                return Err(StdFunctionError::new(
                     ErrorSeverity::HIGH,
-                    String::from("Missing arguments for 'mod' function."),
+                    format!(
+                        "Cannot perform '{}' between values of type '{}' and '{}'.",
+                        name,
+                        val1.to_type(),
+                        val2.to_type()
+                    ),
                 ))
             }
         };
-        StdFunction { params, execute }
+        let result = match first_is_smaller == smaller {
+            true => val1.clone(),
+            false => val2.clone(),
+        };
+        Ok(Some(result))
+    } else {
+        Err(StdFunctionError::new(
+            ErrorSeverity::HIGH,
+            format!("Missing arguments for '{}' function.", name),
+        ))
     }
 }
 
+fn write_arguments(writer: &mut dyn Write, params: &[Rc<RefCell<Value>>], newline: bool) -> Result<String, StdFunctionError> {
+    // returns printed text without the trailing newline
+    let text = join_arguments(params);
+    let result = match newline {
+        true => writeln!(writer, "{}", text),
+        false => write!(writer, "{}", text),
+    };
+    result.map_err(|_| StdFunctionError::new(ErrorSeverity::HIGH, String::from("Failed to write output.")))?;
+    Ok(text)
+}
+
 pub fn get_std_functions() -> HashMap<String, StdFunction> {
     let mut std_functions: HashMap<String, StdFunction> = HashMap::new();
     std_functions.insert("print".to_owned(), StdFunction::print());
+    std_functions.insert("println".to_owned(), StdFunction::println());
     std_functions.insert("input".to_owned(), StdFunction::input());
+    std_functions.insert("read_line".to_owned(), StdFunction::read_line());
+    std_functions.insert("input_int".to_owned(), StdFunction::input_int());
+    std_functions.insert("input_float".to_owned(), StdFunction::input_float());
     std_functions.insert("mod".to_owned(), StdFunction::modulo());
+    std_functions.insert("abs".to_owned(), StdFunction::abs());
+    std_functions.insert("pow".to_owned(), StdFunction::pow());
+    std_functions.insert("sqrt".to_owned(), StdFunction::sqrt());
+    std_functions.insert("floor".to_owned(), StdFunction::floor());
+    std_functions.insert("ceil".to_owned(), StdFunction::ceil());
+    std_functions.insert("round".to_owned(), StdFunction::round());
+    std_functions.insert("len".to_owned(), StdFunction::len());
+    std_functions.insert("push".to_owned(), StdFunction::push());
+    std_functions.insert("pop".to_owned(), StdFunction::pop());
+    std_functions.insert("substring".to_owned(), StdFunction::substring());
+    std_functions.insert("split".to_owned(), StdFunction::split());
+    std_functions.insert("contains".to_owned(), StdFunction::contains());
+    std_functions.insert("index_of".to_owned(), StdFunction::index_of());
+    std_functions.insert("to_upper".to_owned(), StdFunction::to_upper());
+    std_functions.insert("to_lower".to_owned(), StdFunction::to_lower());
+    std_functions.insert("min".to_owned(), StdFunction::min());
+    std_functions.insert("max".to_owned(), StdFunction::max());
     std_functions
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use crate::errors::IError;
+
+    use super::*;
+
+    fn create_arguments(values: Vec<Value>) -> Vec<Rc<RefCell<Value>>> {
+        values.into_iter().map(|value| Rc::new(RefCell::new(value))).collect()
+    }
+
+    #[test]
+    fn join_print_arguments() {
+        let data = [
+            vec![],
+            vec![Value::String(String::from("hello"))],
+            vec![Value::String(String::from("x =")), Value::I64(5), Value::Bool(true)],
+            vec![Value::F64(2.0), Value::F64(0.1), Value::Bool(false), Value::Char('c')],
+        ];
+
+        let expected = ["", "hello", "x = 5 true", "2.0 0.1 false c"];
+
+        for idx in 0..data.len() {
+            assert_eq!(join_arguments(&create_arguments(data[idx].clone())), expected[idx]);
+        }
+    }
+
+    #[test]
+    fn push_stops_at_maximum_array_size() {
+        let push = StdFunction::push();
+        let count = MAX_ARRAY_SIZE / 2 / std::mem::size_of::<Value>() + 1;
+        let mut values = vec![Value::Bool(false); count];
+        values.shrink_to_fit();
+        let array = Value::Array(Type::Bool, Rc::new(RefCell::new(values)));
+
+        let arguments = create_arguments(vec![array, Value::Bool(true)]);
+        assert_eq!(
+            (push.execute)(&arguments, &mut io::sink(), &mut io::empty()).err().unwrap().message(),
+            format!("Array would exceed the maximum size of {} bytes.", MAX_ARRAY_SIZE)
+        );
+
+        let small_array = Value::Array(Type::Bool, Rc::new(RefCell::new(vec![Value::Bool(false)])));
+        let arguments = create_arguments(vec![small_array, Value::Bool(true)]);
+        assert_eq!((push.execute)(&arguments, &mut io::sink(), &mut io::empty()).unwrap(), None);
+    }
+
+    #[test]
+    fn print_without_newline() {
+        let print = StdFunction::print();
+        let mut output: Vec<u8> = vec![];
+        (print.execute)(&create_arguments(vec![Value::String(String::from("a"))]), &mut output, &mut io::empty()).unwrap();
+        (print.execute)(&create_arguments(vec![Value::String(String::from("b"))]), &mut output, &mut io::empty()).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "ab");
+    }
+
+    #[test]
+    fn println_appends_newline() {
+        let println = StdFunction::println();
+        let mut output: Vec<u8> = vec![];
+        (println.execute)(&create_arguments(vec![Value::String(String::from("a"))]), &mut output, &mut io::empty()).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "a\n");
+    }
+
+    #[test]
+    fn print_accepts_any_number_of_arguments() {
+        let print = StdFunction::print();
+        let println = StdFunction::println();
+        assert!(print.variadic && println.variadic);
+
+        let data = [
+            vec![],
+            vec![Value::F64(1.5)],
+            vec![Value::Char('a'), Value::I64(2), Value::String(String::from("three"))],
+        ];
+
+        let expected = ["", "1.5", "a 2 three"];
+
+        for idx in 0..data.len() {
+            let mut output: Vec<u8> = vec![];
+            let returned = Some(Value::String(String::from(expected[idx])));
+            assert_eq!(
+                (print.execute)(&create_arguments(data[idx].clone()), &mut output, &mut io::empty()).unwrap(),
+                returned
+            );
+            assert_eq!(
+                (println.execute)(&create_arguments(data[idx].clone()), &mut output, &mut io::empty()).unwrap(),
+                returned
+            );
+            assert_eq!(String::from_utf8(output).unwrap(), format!("{}{}\n", expected[idx], expected[idx]));
+        }
+    }
+
+    #[test]
+    fn read_line_strips_newline() {
+        let read_line = StdFunction::read_line();
+        let mut output: Vec<u8> = vec![];
+        let mut input = "first\nsecond\r\nlast".as_bytes();
+
+        for expected in ["first", "second", "last", ""] {
+            let returned = (read_line.execute)(&vec![], &mut output, &mut input).unwrap();
+            assert_eq!(returned, Some(Value::String(String::from(expected))));
+        }
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn input_numbers() {
+        let input_int = StdFunction::input_int();
+        let input_float = StdFunction::input_float();
+        let mut output: Vec<u8> = vec![];
+        let mut input = " 42\n-7\n2.5\n1e3\n".as_bytes();
+
+        assert_eq!((input_int.execute)(&vec![], &mut output, &mut input).unwrap(), Some(Value::I64(42)));
+        assert_eq!((input_int.execute)(&vec![], &mut output, &mut input).unwrap(), Some(Value::I64(-7)));
+        assert_eq!((input_float.execute)(&vec![], &mut output, &mut input).unwrap(), Some(Value::F64(2.5)));
+        assert_eq!((input_float.execute)(&vec![], &mut output, &mut input).unwrap(), Some(Value::F64(1000.0)));
+    }
+
+    #[test]
+    fn input_numbers_fail() {
+        let input_int = StdFunction::input_int();
+        let input_float = StdFunction::input_float();
+        let mut output: Vec<u8> = vec![];
+        let mut input = "2.5\nabc\ninf\n".as_bytes();
+
+        assert_eq!(
+            (input_int.execute)(&vec![], &mut output, &mut input).err().unwrap().message(),
+            "Invalid integer input: '2.5'."
+        );
+        assert_eq!(
+            (input_float.execute)(&vec![], &mut output, &mut input).err().unwrap().message(),
+            "Invalid float input: 'abc'."
+        );
+        assert_eq!(
+            (input_float.execute)(&vec![], &mut output, &mut input).err().unwrap().message(),
+            "Invalid float input: 'inf'."
+        );
+        assert_eq!(
+            (input_int.execute)(&vec![], &mut output, &mut input).err().unwrap().message(),
+            "Invalid integer input: ''."
+        );
+    }
+
+    #[test]
+    fn custom_argument_count() {
+        let custom = StdFunction::custom("add", vec![Type::I64, Type::I64], |_| Ok(None));
+        assert_eq!(
+            (custom.execute)(&create_arguments(vec![Value::I64(1)]), &mut io::sink(), &mut io::empty())
+                .err()
+                .unwrap()
+                .message(),
+            "Std function 'add' expected 2 arguments, but was given 1."
+        );
+        assert_eq!(
+            (custom.execute)(&create_arguments(vec![Value::I64(1), Value::I64(2)]), &mut io::sink(), &mut io::empty()).unwrap(),
+            None
+        );
+    }
+}
@@ -1,14 +1,18 @@
 #[cfg(test)]
 mod tests {
-    use std::{cell::RefCell, io::BufReader, rc::Rc};
+    use std::{
+        cell::RefCell,
+        io::{self, BufReader, Write},
+        rc::Rc,
+    };
 
     use crate::{
-        ast::Program,
-        errors::IError,
+        ast::{Program, Type},
+        errors::{ComputationError, ErrorSeverity, IError},
         interpreter::Interpreter,
         lazy_stream_reader::LazyStreamReader,
         lexer::{Lexer, LexerOptions},
-        parser::{IParser, Parser},
+        parser::{IParser, Parser, ParserOptions},
         semantic_checker::SemanticChecker,
         value::Value,
     };
@@ -16,24 +20,331 @@ mod tests {
     fn on_warning(_err: Box<dyn IError>) {}
 
     fn setup_program(text: BufReader<&[u8]>) -> Program {
+        let program = parse_program(text);
+        assert_no_semantic_errors(&program);
+        program
+    }
+
+    fn parse_program(text: BufReader<&[u8]>) -> Program {
         let options = LexerOptions {
             max_comment_length: 100,
             max_identifier_length: 100,
+            max_string_length: 1000,
+            strict_strings: false,
         };
         let reader = LazyStreamReader::new(text);
         let lexer = Lexer::new(reader, options, on_warning);
         let mut parser = Parser::new(lexer);
-        let program = parser.parse().unwrap();
-        let mut checker = SemanticChecker::new(&program).unwrap();
+        parser.parse().unwrap()
+    }
+
+    fn assert_no_semantic_errors(program: &Program) {
+        let mut checker = SemanticChecker::new(program).unwrap();
         checker.check();
         assert_eq!(checker.errors.len(), 0);
-        program
     }
 
     fn create_interpreter<'a>(program: &'a Program) -> Interpreter<'a> {
         Interpreter::new(program)
     }
 
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn program_output() {
+        let text = BufReader::new(
+            r#"
+    fn square(i64 x): i64 {
+      return x * x;
+    }
+
+    for (i64 i = 1; i <= 3; i = i + 1) {
+      println(i, "squared is", square(i));
+    }
+    print("done");
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let output = SharedBuffer::default();
+        let mut interpreter = create_interpreter(&program).with_writer(Box::new(output.clone()));
+        interpreter.interpret().unwrap();
+        assert_eq!(output.text(), "1 squared is 1\n2 squared is 4\n3 squared is 9\ndone");
+    }
+
+    #[test]
+    fn program_input() {
+        let text = BufReader::new(
+            r#"
+    str name = read_line();
+    i64 age = input("Age: ") as i64;
+    str rest = read_line();
+    println("Hello", name, age);
+    print(len(rest));
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let output = SharedBuffer::default();
+        let input = BufReader::new("Alice\r\n 42 \n".as_bytes());
+        let mut interpreter = create_interpreter(&program)
+            .with_writer(Box::new(output.clone()))
+            .with_input(Box::new(input));
+        interpreter.interpret().unwrap();
+        assert_eq!(output.text(), "Age: Hello Alice 42\n0");
+    }
+
+    #[test]
+    fn for_loop_steps() {
+        let text = BufReader::new(
+            r#"
+    i64 calls = 0;
+    fn step(&i64 counter): void {
+      counter += 1;
+    }
+
+    i64 sum = 0;
+    for (i64 i = 0; i < 10; i += 2) {
+      sum += i;
+    }
+    for (; calls < 3; step(&calls)) {}
+    i64 product = 3;
+    product *= 4;
+    product -= 2;
+    product /= 5;
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("sum").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(20)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("calls").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(3)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("product").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(2)))
+        );
+    }
+
+    fn parse_with_options(text: &str, parser_options: ParserOptions) -> Parser<Lexer<BufReader<&[u8]>>> {
+        let options = LexerOptions {
+            max_comment_length: 100,
+            max_identifier_length: 100,
+            max_string_length: 1000,
+            strict_strings: false,
+        };
+        let lexer = Lexer::new(LazyStreamReader::new(BufReader::new(text.as_bytes())), options, on_warning);
+        Parser::with_options(lexer, parser_options)
+    }
+
+    #[test]
+    fn recovering_parser_reports_all_syntax_errors() {
+        let text = "i64 x = 1 + ;\nprintln(x);\nfn f(): i64 { return 1; }\nprintln(1;\ni64 y = 2;\n";
+        let recovering_options = ParserOptions {
+            recover_from_errors: true,
+            ..Default::default()
+        };
+        let mut parser = parse_with_options(text, recovering_options);
+        let error = parser.parse().err().unwrap();
+
+        let first = "Couldn't create multiplicative term while parsing additive term.\nAt line: 1, column: 13.";
+        let second = "Unexpected token - ';'. Expected ')'.\nAt line: 4, column: 10.";
+        assert_eq!(parser.errors.len(), 2);
+        assert_eq!(parser.errors[0].message(), first);
+        assert_eq!(parser.errors[1].message(), second);
+        assert_eq!(error.message(), format!("{}\n\n{}", first, second));
+
+        // strict mode stops at the first error
+        let mut parser = parse_with_options(text, ParserOptions::default());
+        assert_eq!(parser.parse().err().unwrap().message(), first);
+        assert!(parser.errors.is_empty());
+    }
+
+    #[test]
+    fn recovering_parser_resumes_in_nested_blocks() {
+        let text =
+            "fn f(i64 x): i64 {\n    if (x > 0) {\n        x = (1;\n        x = 2;\n    }\n    return x;\n}\nif (true { x = 1; }\ni64 y = 1;\n";
+        let recovering_options = ParserOptions {
+            recover_from_errors: true,
+            ..Default::default()
+        };
+        let mut parser = parse_with_options(text, recovering_options);
+        assert!(parser.parse().is_err());
+
+        let messages: Vec<String> = parser.errors.iter().map(|error| error.message()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Unexpected token - ';'. Expected ')'.\nAt line: 3, column: 15.",
+                "Unexpected token - '{'. Expected ')'.\nAt line: 8, column: 10.",
+            ]
+        );
+    }
+
+    #[test]
+    fn recovering_parser_reports_redeclarations() {
+        let text = "fn f(): void {}\nfn f(): void {}\nfn print(): void {}\n";
+        let recovering_options = ParserOptions {
+            recover_from_errors: true,
+            ..Default::default()
+        };
+        let mut parser = parse_with_options(text, recovering_options);
+        assert!(parser.parse().is_err());
+        assert_eq!(parser.errors.len(), 2);
+        assert!(parser.errors[0].message().starts_with("Redeclaration of function 'f'."));
+        assert!(parser.errors[1].message().starts_with("Redeclaration of function 'print'."));
+    }
+
+    #[test]
+    fn no_std_reports_builtins_as_undeclared() {
+        let text = BufReader::new("print(\"hello\");".as_bytes());
+        let options = LexerOptions {
+            max_comment_length: 100,
+            max_identifier_length: 100,
+            max_string_length: 1000,
+            strict_strings: false,
+        };
+        let lexer = Lexer::new(LazyStreamReader::new(text), options, on_warning);
+        let parser_options = ParserOptions {
+            disable_std_functions: true,
+            ..Default::default()
+        };
+        let program = Parser::with_options(lexer, parser_options).parse().unwrap();
+        assert!(program.std_functions.is_empty());
+
+        let mut checker = SemanticChecker::new(&program).unwrap();
+        checker.check();
+        assert_eq!(checker.errors.len(), 1);
+        assert_eq!(
+            checker.errors[0].message(),
+            "Use of undeclared function 'print'.\nAt line: 1, column: 1.\n"
+        );
+    }
+
+    #[test]
+    fn echo_last_result() {
+        let text = BufReader::new(
+            r#"
+    fn square(i64 x): i64 {
+      return x * x;
+    }
+    i64 x = 3;
+    square(x);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let output = SharedBuffer::default();
+        let mut interpreter = create_interpreter(&program).with_writer(Box::new(output.clone())).with_result_echo(true);
+        interpreter.interpret().unwrap();
+        assert_eq!(output.text(), "9\n");
+
+        let output = SharedBuffer::default();
+        let mut interpreter = create_interpreter(&program).with_writer(Box::new(output.clone()));
+        interpreter.interpret().unwrap();
+        assert_eq!(output.text(), "");
+    }
+
+    #[test]
+    fn call_memoization() {
+        let text = BufReader::new(
+            r#"
+    fn square(i64 x): i64 {
+      return x * x;
+    }
+    fn noisy_square(i64 x): i64 {
+      print(x);
+      return x * x;
+    }
+    i64 x = 3;
+    i64 pure = square(x) + square(x);
+    i64 impure = noisy_square(x) + noisy_square(x);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        for (memoize, expected_calls) in [(true, 3), (false, 4)] {
+            let output = SharedBuffer::default();
+            let mut interpreter = create_interpreter(&program)
+                .with_writer(Box::new(output.clone()))
+                .with_call_memoization(memoize);
+            interpreter.interpret().unwrap();
+
+            assert_eq!(interpreter.function_calls(), expected_calls);
+            assert_eq!(output.text(), "33");
+            assert_eq!(
+                interpreter.stack().get_variable("pure").unwrap().clone(),
+                Rc::new(RefCell::new(Value::I64(18)))
+            );
+            assert_eq!(
+                interpreter.stack().get_variable("impure").unwrap().clone(),
+                Rc::new(RefCell::new(Value::I64(18)))
+            );
+        }
+    }
+
+    #[test]
+    fn bitwise_operators() {
+        let text = BufReader::new(
+            r#"
+    fn set_flag(&i64 flags, i64 bit): void {
+      flags = flags | 1 << bit;
+    }
+
+    i64 flags = 0;
+    set_flag(&flags, 0);
+    set_flag(&flags, 3);
+    i64 masked = flags & 0xF ^ 2;
+    bool is_set = (flags >> 3 & 1) == 1;
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("flags").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(9)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("masked").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(11)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("is_set").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Bool(true)))
+        );
+    }
+
     #[test]
     fn if_statement() {
         let text = BufReader::new(
@@ -105,15 +416,14 @@ mod tests {
     }
 
     #[test]
-    fn reference() {
+    fn multiple_return_values() {
         let text = BufReader::new(
             r#"
-    fn foo(&i64 x): void {
-      x = x + 1;
+    fn div_mod(i64 a, i64 b): (i64, i64) {
+      return (a / b, mod(a, b));
     }
 
-    i64 x = 2;
-    foo(&x);
+    (i64 quotient, i64 remainder) = div_mod(17, 5);
     "#
             .as_bytes(),
         );
@@ -122,24 +432,34 @@ mod tests {
         let mut interpreter = create_interpreter(&program);
         interpreter.interpret().unwrap();
         assert_eq!(
-            interpreter.stack().get_variable("x").unwrap().clone(),
+            interpreter.stack().get_variable("quotient").unwrap().clone(),
             Rc::new(RefCell::new(Value::I64(3)))
         );
+        assert_eq!(
+            interpreter.stack().get_variable("remainder").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(2)))
+        );
     }
 
     #[test]
-    fn recursion() {
+    fn functions_read_global_variables() {
         let text = BufReader::new(
             r#"
-    fn fib(i64 x): i64 {
-      if (x == 1 || x == 2) {
-        return 1;
-      }
+    const i64 limit = 10;
 
-      return fib(x - 1) + fib(x - 2);
+    fn below_limit(i64 x): bool {
+      return x < limit;
     }
 
-    i64 x = fib(6);
+    fn shadows_limit(): i64 {
+      i64 limit = 1;
+      limit = limit + 1;
+      return limit;
+    }
+
+    bool small = below_limit(3);
+    bool big = below_limit(30);
+    i64 shadowed = shadows_limit();
     "#
             .as_bytes(),
         );
@@ -148,31 +468,137 @@ mod tests {
         let mut interpreter = create_interpreter(&program);
         interpreter.interpret().unwrap();
         assert_eq!(
-            interpreter.stack().get_variable("x").unwrap().clone(),
-            Rc::new(RefCell::new(Value::I64(8)))
+            interpreter.stack().get_variable("small").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Bool(true)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("big").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Bool(false)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("shadowed").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(2)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("limit").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(10)))
         );
     }
 
     #[test]
-    fn is_prime() {
+    fn registered_std_function() {
         let text = BufReader::new(
             r#"
-    fn is_prime(i64 x): bool {
-      if (x < 2) {
-        return false;
-      }
+    i64 doubled = double(21);
+    println(double(doubled));
+    "#
+            .as_bytes(),
+        );
 
-      for (i64 i = 2; i < x / 2; i = i + 1) {
-        if (mod(x, i) == 0) {
-          return false;
-        }
-      }
+        let mut program = parse_program(text);
+        program.register_std_function("double", vec![Type::I64], |arguments| match &*arguments[0].borrow() {
+            Value::I64(value) => value
+                .checked_mul(2)
+                .map(|doubled| Some(Value::I64(doubled)))
+                .ok_or_else(|| ComputationError::new(ErrorSeverity::HIGH, format!("Cannot double '{}'.", value))),
+            _ => unreachable!(),
+        });
+        assert_no_semantic_errors(&program);
 
-      return true;
+        let output = SharedBuffer::default();
+        let mut interpreter = create_interpreter(&program).with_writer(Box::new(output.clone()));
+        interpreter.interpret().unwrap();
+        assert_eq!(output.text(), "84\n");
+        assert_eq!(
+            interpreter.stack().get_variable("doubled").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(42)))
+        );
+
+        // the arguments are checked against the registered parameters
+        let text = BufReader::new("double(1, 2);\n\n".as_bytes());
+        let mut program = parse_program(text);
+        program.register_std_function("double", vec![Type::I64], |_| Ok(None));
+        let mut checker = SemanticChecker::new(&program).unwrap();
+        checker.check();
+        assert_eq!(
+            checker.errors[0].message(),
+            "Invalid number of arguments for function 'double'. Expected 1, given 2.\nAt line: 1, column: 1.\n"
+        );
     }
 
-    bool is_5 = is_prime(5);
-    bool is_6 = is_prime(6);
+    #[test]
+    fn entry_point_return_is_exit_code() {
+        let text = BufReader::new(
+            r#"
+    i64 answer = 40;
+    println("top level");
+
+    fn main(): i64 {
+      println("main");
+      return 42;
+    }
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let output = SharedBuffer::default();
+        let mut interpreter = create_interpreter(&program)
+            .with_writer(Box::new(output.clone()))
+            .with_entry_point(Some("main"));
+        interpreter.interpret().unwrap();
+        assert_eq!(output.text(), "top level\nmain\n");
+        assert_eq!(interpreter.exit_code(), Some(42));
+
+        // without the entry point only the top-level statements run
+        let output = SharedBuffer::default();
+        let mut interpreter = create_interpreter(&program).with_writer(Box::new(output.clone()));
+        interpreter.interpret().unwrap();
+        assert_eq!(output.text(), "top level\n");
+        assert_eq!(interpreter.exit_code(), None);
+    }
+
+    #[test]
+    fn invalid_entry_points() {
+        let text = BufReader::new(
+            r#"
+    fn main(): void {}
+    fn flag(): bool { return true; }
+    fn sum(i64 a): i64 { return a; }
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let error = |entry_point: &str| {
+            let mut interpreter = create_interpreter(&program).with_entry_point(Some(entry_point));
+            interpreter.interpret().err().unwrap().message()
+        };
+        let mut interpreter = create_interpreter(&program).with_entry_point(Some("main"));
+        interpreter.interpret().unwrap();
+        assert_eq!(interpreter.exit_code(), None);
+
+        assert_eq!(error("start"), "Entry point function 'start' not found.");
+        assert!(error("flag").starts_with("Entry point 'flag' has to return 'void' or 'i64', but returns 'bool'."));
+        assert!(error("sum").starts_with("Entry point 'sum' cannot have parameters without default values."));
+    }
+
+    #[test]
+    fn switch_default_case() {
+        let text = BufReader::new(
+            r#"
+    fn grade(i64 points): char {
+      char result;
+      switch (points: p) {
+        (p >= 90) -> { result = 'A'; break; }
+        (p >= 50) -> { result = 'C'; }
+        default -> { result = 'F'; }
+      }
+      return result;
+    }
+
+    char a = grade(95);
+    char f = grade(10);
     "#
             .as_bytes(),
         );
@@ -181,12 +607,594 @@ mod tests {
         let mut interpreter = create_interpreter(&program);
         interpreter.interpret().unwrap();
         assert_eq!(
-            interpreter.stack().get_variable("is_5").unwrap().clone(),
-            Rc::new(RefCell::new(Value::Bool(true)))
+            interpreter.stack().get_variable("a").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Char('A')))
         );
         assert_eq!(
-            interpreter.stack().get_variable("is_6").unwrap().clone(),
-            Rc::new(RefCell::new(Value::Bool(false)))
+            interpreter.stack().get_variable("f").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Char('F')))
+        );
+    }
+
+    #[test]
+    fn switch_alias_is_a_copy() {
+        let text = BufReader::new(
+            r#"
+    [i64] a = [1, 2];
+    switch (a: alias) {
+      (true) -> { alias[0] = 99; }
+    }
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("a").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Array(
+                Type::I64,
+                Rc::new(RefCell::new(vec![Value::I64(1), Value::I64(2)]))
+            )))
+        );
+    }
+
+    #[test]
+    fn switch_as_expression() {
+        let text = BufReader::new(
+            r#"
+    fn describe(i64 x): str {
+      return switch (x * 2: doubled) {
+        (doubled > 20) -> "big",
+        (doubled > 10) -> "medium",
+        default -> "small"
+      };
+    }
+
+    str big = describe(11);
+    str medium = describe(6);
+    i64 next = switch () { (big == "big") -> 1, (true) -> 0 } + 1;
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("big").unwrap().clone(),
+            Rc::new(RefCell::new(Value::String(String::from("big"))))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("medium").unwrap().clone(),
+            Rc::new(RefCell::new(Value::String(String::from("medium"))))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("next").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(2)))
+        );
+    }
+
+    #[test]
+    fn mutual_recursion_before_declaration() {
+        let text = BufReader::new(
+            r#"
+    bool ten_is_even = is_even(10);
+    bool seven_is_even = is_even(7);
+
+    fn is_odd(i64 n): bool {
+      if (n == 0) { return false; }
+      return is_even(n - 1);
+    }
+
+    fn is_even(i64 n): bool {
+      if (n == 0) { return true; }
+      return is_odd(n - 1);
+    }
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("ten_is_even").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Bool(true)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("seven_is_even").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Bool(false)))
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        let text = BufReader::new(
+            r#"
+    fn sum([i64] values, i64 count): i64 {
+      i64 total = 0;
+      for (i64 i = 0; i < count; i += 1) {
+        total += values[i];
+      }
+      return total;
+    }
+
+    [[str]] words = [["a", "b"], ["c"]];
+    i64 total = sum([1, 2, 3], 3);
+    str word = words[1][0];
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("total").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(6)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("word").unwrap().clone(),
+            Rc::new(RefCell::new(Value::String(String::from("c"))))
+        );
+    }
+
+    #[test]
+    fn array_element_assignment() {
+        let text = BufReader::new(
+            r#"
+    fn fill(&[i64] values, i64 value): void {
+      values[0] = value;
+    }
+
+    fn try_fill([i64] values): void {
+      values[0] = 100;
+    }
+
+    [i64] a = [1, 2, 3];
+    a[1] = 20;
+    a[2] += 5;
+    [i64] b = a;
+    b[0] = 7;
+    fill(&a, 9);
+    try_fill(a);
+    [[i64]] m = [[1, 2], [3, 4]];
+    m[1][0] = 30;
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        let array = |values: Vec<Value>| Value::Array(Type::I64, Rc::new(RefCell::new(values)));
+        let matrix = |values: Vec<Value>| Value::Array(Type::Array(Box::new(Type::I64)), Rc::new(RefCell::new(values)));
+        assert_eq!(
+            interpreter.stack().get_variable("a").unwrap().clone(),
+            Rc::new(RefCell::new(array(vec![Value::I64(9), Value::I64(20), Value::I64(8)])))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("b").unwrap().clone(),
+            Rc::new(RefCell::new(array(vec![Value::I64(7), Value::I64(20), Value::I64(8)])))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("m").unwrap().clone(),
+            Rc::new(RefCell::new(matrix(vec![
+                array(vec![Value::I64(1), Value::I64(2)]),
+                array(vec![Value::I64(30), Value::I64(4)])
+            ])))
+        );
+    }
+
+    #[test]
+    fn destructured_arrays_are_copied() {
+        let text = BufReader::new(
+            r#"
+    [i64] a = [1, 2];
+    ([i64] b, i64 c) = (a, 1);
+    b[0] = 99;
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        let array = |values: Vec<Value>| Rc::new(RefCell::new(Value::Array(Type::I64, Rc::new(RefCell::new(values)))));
+        assert_eq!(
+            interpreter.stack().get_variable("a").unwrap().clone(),
+            array(vec![Value::I64(1), Value::I64(2)])
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("b").unwrap().clone(),
+            array(vec![Value::I64(99), Value::I64(2)])
+        );
+    }
+
+    #[test]
+    fn emptied_array_keeps_its_type() {
+        let text = BufReader::new(
+            r#"
+    [i64] a = [1];
+    pop(a);
+    push(a, "oops");
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        let error = interpreter.interpret().err().unwrap();
+        assert!(error.message().starts_with("Cannot push value of type 'str' to array of type '[i64]'."));
+        assert_eq!(
+            interpreter.stack().get_variable("a").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Array(Type::I64, Rc::new(RefCell::new(vec![])))))
+        );
+    }
+
+    #[test]
+    fn infinite_recursion() {
+        let text = BufReader::new(
+            r#"
+    fn recurse(i64 depth): i64 {
+      return recurse(depth + 1);
+    }
+
+    recurse(0);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program).with_max_call_depth(50);
+        let error = interpreter.interpret().err().unwrap();
+        assert!(error
+            .message()
+            .starts_with("Maximum recursion depth (50) exceeded in function 'recurse'."));
+    }
+
+    #[test]
+    fn runtime_error_code_snippet() {
+        let code = r#"
+    i64 x = 2;
+    i64 y = x - "b";
+    "#;
+
+        let program = setup_program(BufReader::new(code.as_bytes()));
+        let mut interpreter = create_interpreter(&program).with_source(code);
+        let error = interpreter.interpret().err().unwrap();
+        assert!(error.message().ends_with("\nAt line:\n    i64 y = x - \"b\";\n              ^"));
+
+        let mut interpreter = create_interpreter(&program);
+        let error = interpreter.interpret().err().unwrap();
+        assert!(!error.message().contains("At line:\n"));
+    }
+
+    #[test]
+    fn operand_type_error_points_at_operator() {
+        let code = r#"
+    bool b = (1 + 2) < "a";
+    "#;
+
+        let program = setup_program(BufReader::new(code.as_bytes()));
+        let mut interpreter = create_interpreter(&program).with_source(code);
+        let error = interpreter.interpret().err().unwrap();
+        assert!(error.message().contains("At line: 2, column: 22."));
+        assert!(error.message().ends_with("\n    bool b = (1 + 2) < \"a\";\n                     ^"));
+    }
+
+    #[test]
+    fn reference() {
+        let text = BufReader::new(
+            r#"
+    fn foo(&i64 x): void {
+      x = x + 1;
+    }
+
+    i64 x = 2;
+    foo(&x);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("x").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(3)))
+        );
+    }
+
+    #[test]
+    fn reference_reassigned_through_calls() {
+        let text = BufReader::new(
+            r#"
+    fn reset(&i64 value): void {
+      value = 5;
+    }
+
+    fn reset_twice(&i64 counter): void {
+      counter = 0;
+      reset(&counter);
+      counter += 1;
+    }
+
+    i64 a = 1;
+    reset(&a);
+    i64 b = 1;
+    reset_twice(&b);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("a").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(5)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("b").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(6)))
+        );
+    }
+
+    #[test]
+    fn discarded_call_results() {
+        let text = BufReader::new(
+            r#"
+    fn next(&i64 x): i64 {
+      x = x + 1;
+      return x;
+    }
+
+    fn log(&i64 x): void {
+      next(&x);
+      print("");
+    }
+
+    i64 x = 2;
+    log(&x);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("x").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(3)))
+        );
+    }
+
+    #[test]
+    fn default_parameters() {
+        let text = BufReader::new(
+            r#"
+    fn greet(str name, str greeting = "Hello", i64 times = 1 + 1): str {
+      str result = "";
+      for (i64 i = 0; i < times; i = i + 1) {
+        result = result + greeting + " " + name + "! ";
+      }
+      return result;
+    }
+
+    str a = greet("Ann");
+    str b = greet("Bob", "Hi");
+    str c = greet("Cid", "Hey", 1);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+
+        let expected = [("a", "Hello Ann! Hello Ann! "), ("b", "Hi Bob! Hi Bob! "), ("c", "Hey Cid! ")];
+        for (name, value) in expected {
+            assert_eq!(
+                interpreter.stack().get_variable(name).unwrap().clone(),
+                Rc::new(RefCell::new(Value::String(String::from(value))))
+            );
+        }
+    }
+
+    #[test]
+    fn default_parameters_in_function_scope() {
+        let text = BufReader::new(
+            r#"
+    fn f(i64 x = 10): i64 {
+      return x * 2;
+    }
+
+    fn range_end(i64 start, i64 end = start + 5): i64 {
+      return end;
+    }
+
+    i64 a = f();
+    i64 b = f(3);
+    i64 c = range_end(1);
+    i64 d = range_end(1, 2);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+
+        let expected = [("a", 20), ("b", 6), ("c", 6), ("d", 2)];
+        for (name, value) in expected {
+            assert_eq!(
+                interpreter.stack().get_variable(name).unwrap().clone(),
+                Rc::new(RefCell::new(Value::I64(value)))
+            );
+        }
+    }
+
+    #[test]
+    fn named_arguments() {
+        let text = BufReader::new(
+            r#"
+    fn volume(i64 width, i64 height = 2, i64 depth = 1): i64 {
+      return width * height * depth;
+    }
+
+    i64 a = volume(3, depth = 4);
+    i64 b = volume(height = 5, width = 2);
+    i64 c = volume(1, depth = 2, height = 3);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+
+        let expected = [("a", 24), ("b", 10), ("c", 6)];
+        for (name, value) in expected {
+            assert_eq!(
+                interpreter.stack().get_variable(name).unwrap().clone(),
+                Rc::new(RefCell::new(Value::I64(value)))
+            );
+        }
+    }
+
+    #[test]
+    fn recursion() {
+        let text = BufReader::new(
+            r#"
+    fn fib(i64 x): i64 {
+      if (x == 1 || x == 2) {
+        return 1;
+      }
+
+      return fib(x - 1) + fib(x - 2);
+    }
+
+    i64 x = fib(6);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("x").unwrap().clone(),
+            Rc::new(RefCell::new(Value::I64(8)))
+        );
+    }
+
+    #[test]
+    fn is_prime() {
+        let text = BufReader::new(
+            r#"
+    fn is_prime(i64 x): bool {
+      if (x < 2) {
+        return false;
+      }
+
+      for (i64 i = 2; i < x / 2; i = i + 1) {
+        if (mod(x, i) == 0) {
+          return false;
+        }
+      }
+
+      return true;
+    }
+
+    bool is_5 = is_prime(5);
+    bool is_6 = is_prime(6);
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("is_5").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Bool(true)))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("is_6").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Bool(false)))
+        );
+    }
+
+    #[test]
+    fn chars() {
+        let text = BufReader::new(
+            r#"
+    fn next_char(char c): char {
+      return (c as i64 + 1) as char;
+    }
+
+    char c = 'a';
+    char next = next_char(c);
+    str text = next as str + '\'' as str;
+    bool same = next == 'b';
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("next").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Char('b')))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("text").unwrap().clone(),
+            Rc::new(RefCell::new(Value::String(String::from("b'"))))
+        );
+        assert_eq!(
+            interpreter.stack().get_variable("same").unwrap().clone(),
+            Rc::new(RefCell::new(Value::Bool(true)))
+        );
+    }
+
+    #[test]
+    fn switch_runs_only_first_matching_case() {
+        let text = BufReader::new(
+            r#"
+    i64 x = 10;
+    str first;
+    switch (x) {
+      (x > 0) -> { first = first + "a"; }
+      (x > 1) -> { first = first + "b"; }
+    }
+
+    str all;
+    switch (x) {
+      (x > 0) -> { all = all + "a"; fallthrough; }
+      (x > 100) -> { all = all + "x"; }
+      (x > 1) -> { all = all + "b"; fallthrough; }
+      (x > 2) -> { all = all + "c"; }
+      (x > 3) -> { all = all + "d"; }
+    }
+    "#
+            .as_bytes(),
+        );
+
+        let program = setup_program(text);
+        let mut interpreter = create_interpreter(&program);
+        interpreter.interpret().unwrap();
+        assert_eq!(
+            interpreter.stack().get_variable("first").unwrap().clone(),
+            Rc::new(RefCell::new(Value::String(String::from("a"))))
+        );
+        // 'fallthrough' keeps checking the conditions, it doesn't run the next case unconditionally
+        assert_eq!(
+            interpreter.stack().get_variable("all").unwrap().clone(),
+            Rc::new(RefCell::new(Value::String(String::from("abc"))))
         );
     }
 
@@ -199,6 +1207,7 @@ mod tests {
     switch (x) {
       (x > 0) -> {
         text = ">0";
+        fallthrough;
       }
       (x > 1) -> {
         text = ">1";
@@ -41,4 +41,85 @@ world"#
             assert_eq!(stream_reader.position().column, *exp_col);
         }
     }
+
+    #[test]
+    fn newline_at_end_of_input() {
+        for text in ["a\n", "a\r", "a\r\n"] {
+            let mut stream_reader = LazyStreamReader::new(BufReader::new(text.as_bytes()));
+
+            assert_eq!(*stream_reader.next().unwrap(), 'a');
+            assert_eq!(*stream_reader.next().unwrap(), '\n');
+            assert_eq!(*stream_reader.next().unwrap(), ETX);
+            assert_eq!(stream_reader.position().line, 2);
+            assert_eq!(stream_reader.position().column, 1);
+            assert_eq!(stream_reader.position().offset, text.len());
+        }
+    }
+
+    #[test]
+    fn error_code_snippet() {
+        let code = BufReader::new("i64 x = 5 $ 2;\nx = 1;".as_bytes());
+        let mut stream_reader = LazyStreamReader::new(code);
+        while *stream_reader.next().unwrap() != '$' {}
+
+        assert_eq!(stream_reader.error_code_snippet(), "\nAt line:\ni64 x = 5 $ 2;\n          ^");
+    }
+
+    #[test]
+    fn error_code_snippet_long_line() {
+        let text = format!("{}${}", "a".repeat(1000), "b".repeat(1000));
+        let code = BufReader::new(text.as_bytes());
+        let mut stream_reader = LazyStreamReader::new(code);
+        while *stream_reader.next().unwrap() != '$' {}
+
+        let snippet = stream_reader.error_code_snippet();
+        let lines: Vec<&str> = snippet.split('\n').collect();
+        let (code_line, caret_line) = (lines[2], lines[3]);
+
+        assert_eq!(code_line, format!("...{}${}...", "a".repeat(40), "b".repeat(40)));
+        assert_eq!(caret_line.find('^').unwrap(), code_line.find('$').unwrap());
+    }
+
+    #[test]
+    fn columns_count_tabs_and_multibyte_chars() {
+        let code = BufReader::new("\tą\tx\n\tx".as_bytes());
+        let mut stream_reader = LazyStreamReader::new(code);
+
+        let expected: Vec<(char, u32, u32)> = vec![
+            ('\t', 1, 1),
+            ('ą', 1, 5),
+            ('\t', 1, 6),
+            ('x', 1, 9),
+            ('\n', 1, 10),
+            ('\t', 2, 1),
+            ('x', 2, 5),
+        ];
+        for (exp_char, exp_line, exp_col) in &expected {
+            assert_eq!(*stream_reader.next().unwrap(), *exp_char);
+            assert_eq!(stream_reader.position().line, *exp_line);
+            assert_eq!(stream_reader.position().column, *exp_col);
+        }
+    }
+
+    #[test]
+    fn configurable_tab_width() {
+        let code = BufReader::new("a\t$".as_bytes());
+        let mut stream_reader = LazyStreamReader::new(code).with_tab_width(8);
+        while *stream_reader.next().unwrap() != '$' {}
+
+        assert_eq!(stream_reader.position().column, 9);
+        assert_eq!(stream_reader.error_code_snippet(), "\nAt line:\na       $\n        ^");
+    }
+
+    #[test]
+    fn invalid_utf8_is_replaced() {
+        let bytes: &[u8] = &[b'a', 0xFF, b'b'];
+        let mut stream_reader = LazyStreamReader::new(BufReader::new(bytes));
+
+        assert_eq!(*stream_reader.next().unwrap(), 'a');
+        assert_eq!(*stream_reader.next().unwrap(), char::REPLACEMENT_CHARACTER);
+        assert_eq!(*stream_reader.next().unwrap(), 'b');
+        assert_eq!(stream_reader.position().column, 3);
+        assert_eq!(stream_reader.position().offset, 2);
+    }
 }
@@ -20,6 +20,8 @@ mod tests {
         let lexer_options = LexerOptions {
             max_comment_length: 100,
             max_identifier_length: 20,
+            max_string_length: 100,
+            strict_strings: false,
         };
 
         let lexer = Lexer::new(reader, lexer_options, on_warning);
@@ -76,7 +78,7 @@ mod tests {
 
     #[test]
     fn operators() {
-        let text = "+* / --> <<= > >= ! != = == & && || ";
+        let text = "+* / --> < <= > >= ! != = == & && || ";
         let mut lexer = create_lexer_with_skip(text);
         let expected_tokens: Vec<TokenCategory> = vec![
             TokenCategory::Plus,
@@ -148,6 +150,111 @@ mod tests {
         assert_eq!(token.value, TokenValue::String(expected.to_string()));
     }
 
+    #[test]
+    fn raw_strings() {
+        let text = r#"r"C:\path\n\u{41}" "C:\\path\\n\\u{41}" r"" r x"#;
+        let mut lexer = create_lexer_with_skip(text);
+
+        let raw = lexer.generate_token().unwrap();
+        let escaped = lexer.generate_token().unwrap();
+        assert_eq!(raw.category, TokenCategory::StringValue);
+        assert_eq!(raw.value, escaped.value);
+        assert_eq!(raw.value, TokenValue::String(String::from(r"C:\path\n\u{41}")));
+
+        let empty = lexer.generate_token().unwrap();
+        assert_eq!(empty.value, TokenValue::String(String::new()));
+
+        // without a quote right after it, 'r' is a regular identifier
+        let identifier = lexer.generate_token().unwrap();
+        assert_eq!(identifier.category, TokenCategory::Identifier);
+        assert_eq!(identifier.value, TokenValue::String(String::from("r")));
+    }
+
+    #[test]
+    fn raw_string_with_newline_fails() {
+        let text = "r\"a\nb\"";
+        let mut lexer = create_lexer_with_skip(text);
+
+        assert!(lexer.generate_token().err().unwrap().message().contains("Unexpected newline in string"));
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        let text = r#""\u{41}\u{1F600}""#;
+        let mut lexer = create_lexer_with_skip(text);
+
+        let token = lexer.generate_token().unwrap();
+        assert_eq!(token.value, TokenValue::String(String::from("A\u{1F600}")));
+    }
+
+    thread_local! {
+        static WARNINGS: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(vec![]) };
+    }
+
+    fn collect_warning(warning: Box<dyn IError>) {
+        WARNINGS.with(|warnings| warnings.borrow_mut().push(warning.message()));
+    }
+
+    #[test]
+    fn unclosed_string_warns_by_default() {
+        let text = r#""my_string"#;
+        let reader = LazyStreamReader::new(BufReader::new(text.as_bytes()));
+        let lexer_options = LexerOptions {
+            max_comment_length: 100,
+            max_identifier_length: 20,
+            max_string_length: 100,
+            strict_strings: false,
+        };
+        let mut lexer = Lexer::new(reader, lexer_options, collect_warning);
+        let _ = lexer.generate_token().unwrap();
+
+        let token = lexer.generate_token().unwrap();
+        assert_eq!(token.value, TokenValue::String(String::from("my_string")));
+
+        let warnings = WARNINGS.with(|warnings| warnings.borrow().clone());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("String not closed"));
+    }
+
+    #[test]
+    fn malformed_unicode_escapes() {
+        let text = r#""\u{}|\u{110000}|\u{1234567}|\u41""#;
+        let reader = LazyStreamReader::new(BufReader::new(text.as_bytes()));
+        let lexer_options = LexerOptions {
+            max_comment_length: 100,
+            max_identifier_length: 20,
+            max_string_length: 100,
+            strict_strings: false,
+        };
+        let mut lexer = Lexer::new(reader, lexer_options, collect_warning);
+        let _ = lexer.generate_token().unwrap();
+
+        // malformed escapes are kept as written
+        let token = lexer.generate_token().unwrap();
+        assert_eq!(token.value, TokenValue::String(String::from(r"\u{}|\u{110000}|\u{1234567}|\u41")));
+
+        let warnings = WARNINGS.with(|warnings| warnings.borrow().clone());
+        let escapes = [r"\u{}", r"\u{110000}", r"\u{1234567}", r"\u"];
+        assert_eq!(warnings.len(), escapes.len());
+        for (warning, escape) in warnings.iter().zip(escapes) {
+            assert!(warning.contains(&format!("Invalid unicode escape detected '{}'", escape)));
+        }
+    }
+
+    #[test]
+    fn chars() {
+        let text = r#"'a' ' ' '\n' '\'' '\\' '\"'"#;
+        let mut lexer = create_lexer_with_skip(text);
+
+        let expected = ['a', ' ', '\n', '\'', '\\', '"'];
+
+        for char in expected {
+            let token = lexer.generate_token().unwrap();
+            assert_eq!(token.category, TokenCategory::CharValue);
+            assert_eq!(token.value, TokenValue::Char(char));
+        }
+    }
+
     #[test]
     fn numbers() {
         let text = "123 0 5 12.3 2.0 0.0";
@@ -169,10 +276,44 @@ mod tests {
         }
     }
 
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn numbers_with_separators() {
+        let text = "1_000 1_000_000 3.141_592 1_2.3_4";
+        let mut lexer = create_lexer_with_skip(text);
+
+        let expected: Vec<(TokenCategory, TokenValue)> = vec![
+            (TokenCategory::I64Value, TokenValue::I64(1000)),
+            (TokenCategory::I64Value, TokenValue::I64(1000000)),
+            (TokenCategory::F64Value, TokenValue::F64(3.141592)),
+            (TokenCategory::F64Value, TokenValue::F64(12.34)),
+        ];
+
+        for (category, value) in &expected {
+            let token = lexer.generate_token().unwrap();
+            assert_eq!(token.category, *category);
+            assert_eq!(token.value, *value);
+        }
+    }
+
+    #[test]
+    fn hexadecimal_numbers() {
+        let text = "0x0 0xff 0X1A 0xDEAD_BEEF 0x7fff_ffff_ffff_ffff";
+        let mut lexer = create_lexer_with_skip(text);
+
+        let expected = [0, 255, 26, 0xDEADBEEF, i64::MAX];
+
+        for value in expected {
+            let token = lexer.generate_token().unwrap();
+            assert_eq!(token.category, TokenCategory::I64Value);
+            assert_eq!(token.value, TokenValue::I64(value));
+        }
+    }
+
     #[test]
     fn keyword_or_identifier() {
         let text = "fn for if else return i64 f64
-        str void bool true false as switch break my_identifier1";
+        str char void bool true false as switch break const my_identifier1";
         let mut lexer = create_lexer_with_skip(text);
 
         let expected: Vec<(TokenCategory, TokenValue)> = vec![
@@ -184,6 +325,7 @@ mod tests {
             (TokenCategory::I64, TokenValue::Null),
             (TokenCategory::F64, TokenValue::Null),
             (TokenCategory::String, TokenValue::Null),
+            (TokenCategory::Char, TokenValue::Null),
             (TokenCategory::Void, TokenValue::Null),
             (TokenCategory::Bool, TokenValue::Null),
             (TokenCategory::True, TokenValue::Null),
@@ -191,6 +333,7 @@ mod tests {
             (TokenCategory::As, TokenValue::Null),
             (TokenCategory::Switch, TokenValue::Null),
             (TokenCategory::Break, TokenValue::Null),
+            (TokenCategory::Const, TokenValue::Null),
             (TokenCategory::Identifier, TokenValue::String("my_identifier1".to_owned())),
         ];
 
@@ -210,7 +353,7 @@ mod edge_case_tests {
         errors::IError,
         lazy_stream_reader::LazyStreamReader,
         lexer::{Lexer, LexerOptions},
-        tokens::TokenCategory,
+        tokens::{TokenCategory, TokenValue},
     };
 
     fn on_warning(warning: Box<dyn IError>) {
@@ -224,6 +367,8 @@ mod edge_case_tests {
         let lexer_options = LexerOptions {
             max_comment_length: 100,
             max_identifier_length: 20,
+            max_string_length: 50,
+            strict_strings: false,
         };
 
         let lexer = Lexer::new(reader, lexer_options, on_warning);
@@ -258,12 +403,88 @@ mod edge_case_tests {
     }
 
     #[test]
-    fn extend_to_next_or_warning() {
-        let text = "|";
+    fn bitwise_operators() {
+        let text = "| || ^ & && << <= < >> >= >";
+        let mut lexer = create_lexer_with_skip(text);
+
+        let expected = [
+            TokenCategory::BitOr,
+            TokenCategory::Or,
+            TokenCategory::BitXor,
+            TokenCategory::Reference,
+            TokenCategory::And,
+            TokenCategory::ShiftLeft,
+            TokenCategory::LessOrEqual,
+            TokenCategory::Less,
+            TokenCategory::ShiftRight,
+            TokenCategory::GreaterOrEqual,
+            TokenCategory::Greater,
+        ];
+
+        for category in expected {
+            assert_eq!(lexer.generate_token().unwrap().category, category);
+        }
+    }
+
+    #[test]
+    fn compound_assign_operators() {
+        let text = "+= + -= - -> *= * /= /";
         let mut lexer = create_lexer_with_skip(text);
 
+        let expected = [
+            TokenCategory::PlusAssign,
+            TokenCategory::Plus,
+            TokenCategory::MinusAssign,
+            TokenCategory::Minus,
+            TokenCategory::Arrow,
+            TokenCategory::MultiplyAssign,
+            TokenCategory::Multiply,
+            TokenCategory::DivideAssign,
+            TokenCategory::Divide,
+        ];
+
+        for category in expected {
+            assert_eq!(lexer.generate_token().unwrap().category, category);
+        }
+    }
+
+    #[test]
+    fn for_each_token() {
+        let text = "i64 x = 5; # note";
+        let mut lexer = create_lexer(text);
+
+        let mut categories = vec![];
+        lexer.for_each_token(|token| categories.push(token.category.clone())).unwrap();
+
+        let expected = vec![
+            TokenCategory::STX,
+            TokenCategory::I64,
+            TokenCategory::Identifier,
+            TokenCategory::Assign,
+            TokenCategory::I64Value,
+            TokenCategory::Semicolon,
+            TokenCategory::Comment,
+            TokenCategory::ETX,
+        ];
+        assert_eq!(categories, expected);
+    }
+
+    #[test]
+    fn too_long_string() {
+        let text = format!("\"{}\"", "a".repeat(60));
+        let mut lexer = create_lexer_with_skip(text.as_str());
+
         let result = lexer.generate_token();
-        assert_eq!(result.unwrap().category, TokenCategory::Or);
+        assert!(result.err().unwrap().message().contains("String literal too long (max 50)."));
+    }
+
+    #[test]
+    fn string_at_max_length() {
+        let text = format!("\"{}\"", "a".repeat(50));
+        let mut lexer = create_lexer_with_skip(text.as_str());
+
+        let token = lexer.generate_token().unwrap();
+        assert_eq!(token.value, TokenValue::String("a".repeat(50)));
     }
 
     #[test]
@@ -285,6 +506,24 @@ mod edge_case_tests {
         assert_eq!(result.unwrap().category, TokenCategory::StringValue);
     }
 
+    #[test]
+    fn string_unclosed_strict() {
+        for text in [r#""my_string"#, r#"r"my_string"#] {
+            let reader = LazyStreamReader::new(BufReader::new(text.as_bytes()));
+            let lexer_options = LexerOptions {
+                max_comment_length: 100,
+                max_identifier_length: 20,
+                max_string_length: 50,
+                strict_strings: true,
+            };
+            let mut lexer = Lexer::new(reader, lexer_options, on_warning);
+            let _ = lexer.generate_token().unwrap();
+
+            let result = lexer.generate_token();
+            assert!(result.err().unwrap().message().contains("String not closed"));
+        }
+    }
+
     #[test]
     fn int_overflow() {
         // 1 more than limit
@@ -303,4 +542,48 @@ mod edge_case_tests {
         let result = lexer.generate_token();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn invalid_number_separators() {
+        let data = ["_5", "5_", "1__0", "1_.5", "1._5", "0_1"];
+
+        for text in data {
+            let mut lexer = create_lexer_with_skip(text);
+
+            let result = lexer.generate_token();
+            assert!(result.is_err(), "'{}' should not be lexed", text);
+        }
+    }
+
+    #[test]
+    fn hexadecimal_floats_rejected() {
+        for text in ["0x1.8", "0x1p3", "0x1.8p3"] {
+            let mut lexer = create_lexer_with_skip(text);
+
+            let message = lexer.generate_token().err().unwrap().message();
+            assert!(message.contains("Hexadecimal float literals are not supported.\nAt line: 1, column: 4"));
+        }
+    }
+
+    #[test]
+    fn invalid_hexadecimal_numbers() {
+        for text in ["0x", "0xg", "0x_1", "0x1_", "0x8000_0000_0000_0000"] {
+            let mut lexer = create_lexer_with_skip(text);
+
+            let result = lexer.generate_token();
+            assert!(result.is_err(), "'{}' should not be lexed", text);
+        }
+    }
+
+    #[test]
+    fn invalid_chars() {
+        let data = ["''", "'ab'", "'a", "'\\s'", "'\n'"];
+
+        for text in data {
+            let mut lexer = create_lexer_with_skip(text);
+
+            let result = lexer.generate_token();
+            assert!(result.is_err());
+        }
+    }
 }
@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        env,
+        io::{self, Cursor, ErrorKind, Write},
+        rc::Rc,
+    };
+
+    use crate::{
+        check_program, flag_given, open_error_message, open_file, parse_source, parse_warnings, read_source, run_file, run_repl, run_source,
+        run_source_with_options, value::Value, RunOptions,
+    };
+
+    #[derive(Clone, Default)]
+    struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn run_repl_lines(lines: &str, options: &RunOptions) -> String {
+        let output = SharedOutput::default();
+        run_repl(Box::new(Cursor::new(lines.to_owned())), Box::new(output.clone()), options);
+        let text = String::from_utf8_lossy(&output.0.borrow()).into_owned();
+        text
+    }
+
+    #[test]
+    fn time_flag() {
+        let args = |given: &[&str]| -> Vec<String> { given.iter().map(|arg| arg.to_string()).collect() };
+
+        assert!(!flag_given(&args(&["tkom", "file.rp"]), "--time"));
+        assert!(!flag_given(&args(&["tkom", "file.rp", "--echo"]), "--time"));
+        assert!(flag_given(&args(&["tkom", "file.rp", "--echo", "--time"]), "--time"));
+        // the path is not a flag, even when named like one
+        assert!(!flag_given(&args(&["tkom", "--time"]), "--time"));
+    }
+
+    #[test]
+    fn open_missing_file() {
+        let path = env::temp_dir().join("tkom_missing_file.rp");
+        let path = path.to_str().unwrap();
+        assert_eq!(open_file(path).err().unwrap(), format!("File '{}' not found.", path));
+    }
+
+    #[test]
+    fn open_directory() {
+        let path = env::temp_dir();
+        let path = path.to_str().unwrap();
+        assert_eq!(open_file(path).err().unwrap(), format!("'{}' is a directory.", path));
+    }
+
+    #[test]
+    fn open_existing_file() {
+        assert!(open_file("Cargo.toml").is_ok());
+    }
+
+    #[test]
+    fn open_error_messages() {
+        let data = [
+            (ErrorKind::NotFound, "File 'a.rp' not found."),
+            (ErrorKind::PermissionDenied, "Permission denied reading 'a.rp'."),
+            (ErrorKind::IsADirectory, "'a.rp' is a directory."),
+            (ErrorKind::InvalidData, "Cannot open file 'a.rp': invalid data."),
+        ];
+
+        for (kind, message) in data {
+            assert_eq!(open_error_message("a.rp", &io::Error::from(kind)), message);
+        }
+    }
+
+    #[test]
+    fn run_source_end_to_end() {
+        assert!(run_source("i64 x = 2;\ni64 y = x * 21;\n").is_ok());
+
+        assert_eq!(
+            run_source("x = 1;\ny = 2;\n").err().unwrap().message(),
+            "Use of undeclared variable 'x'.\nAt line: 1, column: 1.\n\nUse of undeclared variable 'y'.\nAt line: 2, column: 1.\n"
+        );
+
+        assert!(run_source("i64 x = 1 / 0;\n").err().unwrap().message().starts_with("Division by zero."));
+        assert!(run_source("println(1;\n").is_err());
+    }
+
+    #[test]
+    fn run_source_returns_exit_code() {
+        let options = RunOptions {
+            entry_point: Some(String::from("main")),
+            ..Default::default()
+        };
+        assert_eq!(
+            run_source_with_options("fn main(): i64 { return 3; }", &options).unwrap().exit_code,
+            Some(3)
+        );
+        assert_eq!(run_source_with_options("fn main(): void {}", &options).unwrap().exit_code, None);
+    }
+
+    #[test]
+    fn memoization_keeps_side_effects() {
+        let source = r#"
+            [i64] log = [1];
+            fn f(i64 x): i64 { push(log, x); return len(log); }
+            println(f(5) + f(5));
+            println(len(log));
+        "#;
+        for memoize_calls in [false, true] {
+            let options = RunOptions {
+                memoize_calls,
+                ..Default::default()
+            };
+            assert_eq!(run_source_with_options(source, &options).unwrap().output, "5\n3\n");
+        }
+    }
+
+    #[test]
+    fn run_source_captures_output_and_final_result() {
+        let source = r#"
+            fn square(i64 x): i64 { println("squaring", x); return x * x; }
+            print("start;");
+            square(4);
+        "#;
+        let result = run_source(source).unwrap();
+
+        assert_eq!(result.output, "start;squaring 4\n");
+        assert_eq!(result.last_result, Some(Value::I64(16)));
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.exit_code, None);
+
+        let result = run_source(r#"print("\u{}");"#).unwrap();
+        assert_eq!(result.output, r"\u{}");
+        assert_eq!(result.last_result, Some(Value::String(String::from(r"\u{}"))));
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("Invalid unicode escape detected"));
+    }
+
+    #[test]
+    fn strict_strings_option() {
+        let source = "print(\"abc";
+        let result = run_source(source);
+        assert!(!result.err().unwrap().message().contains("String not closed"));
+
+        let options = RunOptions {
+            strict_strings: true,
+            ..Default::default()
+        };
+        let result = run_source_with_options(source, &options);
+        assert!(result.err().unwrap().message().contains("String not closed"));
+    }
+
+    #[test]
+    fn warnings_from_flag() {
+        let options = parse_warnings("unused,shadowing").unwrap();
+        assert!(options.warn_on_unused_variables && options.warn_on_shadowing);
+        assert!(!options.warn_on_unreachable_code && !options.warn_on_mixed_logical_operators && !options.warn_on_ignored_return_values);
+
+        let options = parse_warnings("all").unwrap();
+        assert!(options.warn_on_unreachable_code && options.warn_on_mixed_logical_operators && options.warn_on_ignored_return_values);
+
+        assert_eq!(parse_warnings("unused,typo").err().unwrap(), "Unknown warning 'typo'.");
+    }
+
+    #[test]
+    fn run_with_warnings() {
+        let source = "fn f(): void { i64 x = 1; }";
+        assert!(run_source(source).unwrap().warnings.is_empty());
+
+        let options = RunOptions {
+            warnings: parse_warnings("unused").unwrap(),
+            ..Default::default()
+        };
+        let result = run_source_with_options(source, &options).unwrap();
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("'x'"));
+    }
+
+    #[test]
+    fn examples_pass_the_checks() {
+        for entry in std::fs::read_dir("examples").unwrap() {
+            let path = entry.unwrap().path();
+            let source = read_source(path.to_str().unwrap()).unwrap();
+            let program = parse_source(&source, &RunOptions::default()).unwrap();
+            assert!(check_program(&program, &Default::default()).is_ok(), "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn run_missing_file() {
+        let path = env::temp_dir().join("tkom_missing_file.rp");
+        let path = path.to_str().unwrap();
+        assert_eq!(run_file(path).err().unwrap().message(), format!("File '{}' not found.", path));
+    }
+
+    #[test]
+    fn repl_keeps_declarations_between_lines() {
+        let lines = "i64 x = 5;\nprintln(x);\nfn double(i64 a): i64 { return a * 2; }\nx = double(x);\nprintln(x);\n";
+        assert_eq!(run_repl_lines(lines, &RunOptions::default()), "> > 5\n> > > 10\n> \n");
+    }
+
+    #[test]
+    fn repl_reports_errors_and_continues() {
+        let lines = "i64 x = 1;\nprintln(x;\nprintln(y);\nfor (i64 i = 0; i < 3; i = i + 1) { i64 z = i / 0; }\nprintln(x);\n";
+        let output = run_repl_lines(lines, &RunOptions::default());
+
+        assert!(output.ends_with("> 1\n> \n"));
+        assert_eq!(output.matches("> ").count(), 6);
+        assert!(output.contains("Variable 'y' not declared in this scope.\nAt line: 1, column: 9.\n\nAt line:\nprintln(y);\n        ^"));
+        assert!(output.contains("Division by zero."));
+    }
+
+    #[test]
+    fn repl_keeps_constants_and_shared_arrays() {
+        let lines =
+            "const i64 limit = 3;\nlimit = 4;\n[i64] a = [1];\nfn grow(&[i64] values): void { push(values, 2); }\ngrow(&a);\nprintln(limit, a);\n";
+        let output = run_repl_lines(lines, &RunOptions::default());
+
+        assert!(output.contains("Cannot assign to constant 'limit'."));
+        assert!(output.ends_with("> 3 [1, 2]\n> \n"));
+    }
+
+    #[test]
+    fn repl_echoes_results() {
+        let options = RunOptions {
+            echo_result: true,
+            ..Default::default()
+        };
+        assert_eq!(run_repl_lines("i64 x = -42;\nabs(x);\n", &options), "> > 42\n> \n");
+        assert_eq!(run_repl_lines("println(\"hello\");\nprint(\"x\");\n", &options), "> hello\n> x> \n");
+    }
+
+    #[test]
+    fn echo_skips_printed_values() {
+        let options = RunOptions {
+            echo_result: true,
+            ..Default::default()
+        };
+        let result = run_source_with_options("println(\"hello\");", &options).unwrap();
+        assert_eq!(result.output, "hello\n");
+    }
+}
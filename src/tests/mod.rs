@@ -1,3 +1,4 @@
 pub mod accept;
 pub mod lazy_stream_reader_tests;
 pub mod lexer_tests;
+pub mod main_tests;
@@ -16,6 +16,11 @@ pub enum TokenCategory {
     Minus,
     Multiply,
     Divide,
+    // Bitwise arithmetic
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
     // Boolean arithmetic
     Negate,
     And,
@@ -37,16 +42,25 @@ pub enum TokenCategory {
     False,
     Return,
     Switch,
+    Default,
     Break,
+    Fallthrough,
+    Const,
     // Type keywords
     Bool,
     String,
+    Char,
     I64,
     F64,
     Void,
     // Others
     Assign,
+    PlusAssign,
+    MinusAssign,
+    MultiplyAssign,
+    DivideAssign,
     Colon,
+    QuestionMark,
     Semicolon,
     Comma,
     Reference,
@@ -58,6 +72,7 @@ pub enum TokenCategory {
     Comment,
     // Literals
     StringValue,
+    CharValue,
     I64Value,
     F64Value,
 }
@@ -77,6 +92,10 @@ impl Debug for TokenCategory {
             Minus => "-",
             Multiply => "*",
             Divide => "/",
+            BitOr => "|",
+            BitXor => "^",
+            ShiftLeft => "<<",
+            ShiftRight => ">>",
             Negate => "!",
             And => "&&",
             Or => "||",
@@ -95,14 +114,23 @@ impl Debug for TokenCategory {
             False => "false",
             Return => "return",
             Switch => "switch",
+            Default => "default",
             Break => "break",
+            Fallthrough => "fallthrough",
+            Const => "const",
             Bool => "bool type",
             String => "str type",
+            Char => "char type",
             I64 => "i64 type",
             F64 => "f64 type",
             Void => "void",
             Assign => "=",
+            PlusAssign => "+=",
+            MinusAssign => "-=",
+            MultiplyAssign => "*=",
+            DivideAssign => "/=",
             Colon => ":",
+            QuestionMark => "?",
             Semicolon => ";",
             Comma => ",",
             Reference => "&",
@@ -112,6 +140,7 @@ impl Debug for TokenCategory {
             Identifier => "identifier",
             Comment => "comment",
             StringValue => "str value",
+            CharValue => "char value",
             I64Value => "i64 value",
             F64Value => "f64 value",
         };
@@ -123,6 +152,7 @@ impl Debug for TokenCategory {
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenValue {
     String(String),
+    Char(char),
     F64(f64),
     I64(i64),
     Null,
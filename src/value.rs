@@ -1,26 +1,38 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
 use crate::{
     ast::Type,
     errors::{ComputationError, ErrorSeverity},
 };
 
+// upper bounds for values growing at runtime, so a typo or a runaway loop doesn't allocate gigabytes - see 'approx_size'
+pub const MAX_STRING_SIZE: usize = 1_000_000;
+pub const MAX_ARRAY_SIZE: usize = 64_000_000;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     I64(i64),
     F64(f64),
     String(String),
+    Char(char),
     Bool(bool),
+    Tuple(Vec<Value>),
+    // element type and the elements - shared, so reading an array doesn't copy them, see 'deep_copy'
+    // the type is kept apart from the elements, as an array emptied by 'pop' still has one
+    Array(Type, Rc<RefCell<Vec<Value>>>),
 }
 
 impl Value {
-    pub fn default_value(var_type: Type) -> Result<Value, ComputationError> {
+    pub fn default_value(var_type: &Type) -> Result<Value, ComputationError> {
         match var_type {
             Type::Bool => Ok(Value::Bool(false)),
             Type::I64 => Ok(Value::I64(0)),
             Type::F64 => Ok(Value::F64(0.0)),
             Type::Str => Ok(Value::String("".to_owned())),
+            Type::Char => Ok(Value::Char('\0')),
             a => Err(ComputationError::new(
                 ErrorSeverity::HIGH,
-                format!("Cannot create default value for type '{:?}'.", a),
+                format!("Cannot create default value for type '{}'.", a),
             )),
         }
     }
@@ -31,6 +43,21 @@ impl Value {
             Value::F64(_) => Type::F64,
             Value::I64(_) => Type::I64,
             Value::String(_) => Type::Str,
+            Value::Char(_) => Type::Char,
+            Value::Tuple(values) => Type::Tuple(values.iter().map(Value::to_type).collect()),
+            Value::Array(element_type, _) => Type::Array(Box::new(element_type.clone())),
+        }
+    }
+
+    // arrays have value semantics, so storing one in a variable has to copy its elements
+    pub fn deep_copy(&self) -> Value {
+        match self {
+            Value::Tuple(values) => Value::Tuple(values.iter().map(Value::deep_copy).collect()),
+            Value::Array(element_type, values) => Value::Array(
+                element_type.clone(),
+                Rc::new(RefCell::new(values.borrow().iter().map(Value::deep_copy).collect())),
+            ),
+            value => value.clone(),
         }
     }
 
@@ -40,6 +67,44 @@ impl Value {
             _ => Err(ComputationError::new(ErrorSeverity::HIGH, String::from("Given value is not a boolean."))),
         }
     }
+
+    // text used by print, println and casting to str
+    pub fn to_display_string(&self) -> String {
+        match self {
+            Value::I64(i64) => i64.to_string(),
+            // round floats keep their fraction so they don't look like i64s
+            Value::F64(f64) if f64.is_finite() && f64.fract() == 0.0 => format!("{:.1}", f64),
+            Value::F64(f64) => f64.to_string(),
+            Value::String(string) => string.clone(),
+            Value::Char(char) => char.to_string(),
+            Value::Bool(bool) => bool.to_string(),
+            Value::Tuple(values) => {
+                let values: Vec<String> = values.iter().map(Value::to_display_string).collect();
+                format!("({})", values.join(", "))
+            }
+            Value::Array(_, values) => {
+                let values: Vec<String> = values.borrow().iter().map(Value::to_display_string).collect();
+                format!("[{}]", values.join(", "))
+            }
+        }
+    }
+
+    // estimated number of bytes used by the value, including its heap allocations
+    pub fn approx_size(&self) -> usize {
+        let heap_size = match self {
+            Value::String(string) => string.len(),
+            Value::Tuple(values) => values.iter().map(Value::approx_size).sum(),
+            Value::Array(_, values) => values.borrow().iter().map(Value::approx_size).sum(),
+            Value::I64(_) | Value::F64(_) | Value::Char(_) | Value::Bool(_) => 0,
+        };
+        std::mem::size_of::<Value>() + heap_size
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_display_string())
+    }
 }
 
 #[cfg(test)]
@@ -50,28 +115,40 @@ mod tests {
 
     #[test]
     fn default_values() {
-        let data = [Type::Bool, Type::I64, Type::F64, Type::Str];
+        let data = [Type::Bool, Type::I64, Type::F64, Type::Str, Type::Char];
 
-        let expected = [Value::Bool(false), Value::I64(0), Value::F64(0.0), Value::String(String::from(""))];
+        let expected = [
+            Value::Bool(false),
+            Value::I64(0),
+            Value::F64(0.0),
+            Value::String(String::from("")),
+            Value::Char('\0'),
+        ];
 
         for idx in 0..data.len() {
-            assert_eq!(Value::default_value(data[idx]).unwrap(), expected[idx]);
+            assert_eq!(Value::default_value(&data[idx]).unwrap(), expected[idx]);
         }
     }
 
     #[test]
     fn default_values_fail() {
         assert_eq!(
-            Value::default_value(Type::Void).err().unwrap().message(),
+            Value::default_value(&Type::Void).err().unwrap().message(),
             String::from("Cannot create default value for type 'void'.")
         );
     }
 
     #[test]
     fn value_to_type() {
-        let values = [Value::Bool(true), Value::I64(5), Value::F64(5.5), Value::String(String::from("hello"))];
+        let values = [
+            Value::Bool(true),
+            Value::I64(5),
+            Value::F64(5.5),
+            Value::String(String::from("hello")),
+            Value::Char('a'),
+        ];
 
-        let exp = [Type::Bool, Type::I64, Type::F64, Type::Str];
+        let exp = [Type::Bool, Type::I64, Type::F64, Type::Str, Type::Char];
 
         for idx in 0..values.len() {
             assert_eq!(values[idx].to_type(), exp[idx]);
@@ -86,4 +163,51 @@ mod tests {
             String::from("Given value is not a boolean.")
         );
     }
+
+    #[test]
+    fn display_string() {
+        let data = [
+            (Value::I64(-5), "-5"),
+            (Value::F64(2.0), "2.0"),
+            (Value::F64(-0.5), "-0.5"),
+            (Value::F64(1.25), "1.25"),
+            (Value::F64(f64::INFINITY), "inf"),
+            (Value::String(String::from("hello")), "hello"),
+            (Value::Char('ż'), "ż"),
+            (Value::Bool(true), "true"),
+            (Value::Bool(false), "false"),
+            (
+                Value::Tuple(vec![Value::I64(1), Value::String(String::from("a")), Value::Tuple(vec![Value::F64(2.0)])]),
+                "(1, a, (2.0))",
+            ),
+            (
+                Value::Array(Type::Char, Rc::new(RefCell::new(vec![Value::Char('a'), Value::Char('b')]))),
+                "[a, b]",
+            ),
+        ];
+
+        for (value, expected) in data {
+            assert_eq!(value.to_display_string(), expected);
+            assert_eq!(value.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn approx_size() {
+        let base = std::mem::size_of::<Value>();
+
+        assert_eq!(Value::I64(1).approx_size(), base);
+        assert_eq!(Value::Bool(true).approx_size(), base);
+        assert_eq!(Value::String(String::new()).approx_size(), base);
+        assert_eq!(Value::String("a".repeat(100)).approx_size(), base + 100);
+        assert_eq!(Value::String(String::from("żółw")).approx_size(), base + "żółw".len());
+
+        let row = |values: Vec<Value>| Value::Array(Type::I64, Rc::new(RefCell::new(values)));
+        let matrix = Value::Array(
+            Type::Array(Box::new(Type::I64)),
+            Rc::new(RefCell::new(vec![row(vec![Value::I64(1), Value::I64(2)]), row(vec![Value::I64(3)])])),
+        );
+        // the outer array, both rows and three numbers
+        assert_eq!(matrix.approx_size(), 6 * base);
+    }
 }